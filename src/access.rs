@@ -0,0 +1,425 @@
+//! Raw, bus-agnostic register access.
+//!
+//! The [`RegisterAccess`] trait is implemented by the bus-specific devices, such as
+//! [`I2CDevice`](crate::i2c::I2CDevice) and [`SPIDevice`](crate::spi::SPIDevice), and provides
+//! the byte-level operations that the typed register accessors are built upon.
+//!
+//! The typed accessors of [`I2CRegisterAccess`](crate::i2c::I2CRegisterAccess) and
+//! [`SPIRegisterAccess`](crate::spi::SPIRegisterAccess) forward to the bus-agnostic helpers
+//! of this module, passing the register address of the bus-specific register trait.
+
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::backing::RegisterBacking;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::error::Error;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::fifo::{self, FifoCount};
+#[cfg(all(feature = "bitflags", any(feature = "i2c", feature = "spi")))]
+use crate::flags::{self, FlagsRegister};
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::indexed::{ArrayAddress, IndexedRegister};
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::poll::Timeout;
+use crate::register_address::RegisterAddress;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::sizes::{RegisterSizeInformation, MAX_BYTES};
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::status::{self, ClearSemantics, StatusRegister};
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::{FromBits, HardwareRegister, ToBits};
+
+/// Byte-level access to the registers of a single device.
+#[allow(clippy::module_name_repetitions)]
+pub trait RegisterAccess {
+    /// The error type of the underlying bus.
+    type Error;
+
+    /// Returns the address of the device on the bus, if the bus addresses devices.
+    fn device_address(&self) -> Option<u16> {
+        None
+    }
+
+    /// Reads `buffer.len()` bytes starting at the specified register address.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn read_raw<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress;
+
+    /// Writes the provided bytes starting at the specified register address.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn write_raw<A>(&mut self, address: A, data: &[u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress;
+
+    /// Reads `buffer.len()` bytes at the specified register address in a single transfer,
+    /// regardless of the device's [`AddressingMode`](crate::addressing::AddressingMode),
+    /// e.g. to drain a FIFO data register.
+    ///
+    /// Defaults to [`read_raw`](Self::read_raw).
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn read_raw_burst<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        self.read_raw(address, buffer)
+    }
+}
+
+impl<T> RegisterAccess for &mut T
+where
+    T: RegisterAccess,
+{
+    type Error = T::Error;
+
+    fn device_address(&self) -> Option<u16> {
+        T::device_address(self)
+    }
+
+    fn read_raw<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        T::read_raw(self, address, buffer)
+    }
+
+    fn write_raw<A>(&mut self, address: A, data: &[u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        T::write_raw(self, address, data)
+    }
+
+    fn read_raw_burst<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        T::read_raw_burst(self, address, buffer)
+    }
+}
+
+/// Encodes a register address into its big-endian on-the-wire representation.
+///
+/// Returns the buffer and the number of bytes in use, as per [`RegisterAddress::ADDR_BYTES`].
+#[must_use]
+pub fn encode_address<A>(address: &A) -> ([u8; 2], usize)
+where
+    A: RegisterAddress,
+{
+    encode_value::<A>(address.value())
+}
+
+/// Encodes a register address value of type `A` into its on-the-wire representation.
+pub(crate) fn encode_value<A>(value: u16) -> ([u8; 2], usize)
+where
+    A: RegisterAddress,
+{
+    let bytes = value.to_be_bytes();
+    if A::ADDR_BYTES > 1 {
+        (bytes, 2)
+    } else {
+        ([bytes[1], 0], 1)
+    }
+}
+
+/// Reads the register at the specified address.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn read_register<T, Reg, A, Size>(access: &mut T, address: A) -> Result<Reg, T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress,
+    Size: RegisterSizeInformation,
+{
+    let mut buffer = [0_u8; MAX_BYTES];
+    let buffer = &mut buffer[..Size::BYTES];
+    access.read_raw(address, buffer)?;
+    Ok(Reg::from_bits(Reg::Target::from_register_bytes(
+        buffer,
+        Reg::BYTE_ORDER,
+    )))
+}
+
+/// Writes the register at the specified address.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn write_register<T, Reg, A, Size>(
+    access: &mut T,
+    address: A,
+    value: &Reg,
+) -> Result<(), T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress,
+    Size: RegisterSizeInformation,
+{
+    let mut buffer = [0_u8; MAX_BYTES];
+    let buffer = &mut buffer[..Size::BYTES];
+    value.to_bits().to_register_bytes(buffer, Reg::BYTE_ORDER);
+    access.write_raw(address, buffer)
+}
+
+/// Reads the register at the specified address, applies `f` and writes back the result.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn modify_register<T, Reg, A, Size, F>(
+    access: &mut T,
+    address: A,
+    f: F,
+) -> Result<Reg, T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+    F: FnOnce(Reg) -> Reg,
+{
+    let value = f(read_register(access, address)?);
+    write_register(access, address, &value)?;
+    Ok(value)
+}
+
+/// Writes the reset value, if any, to the register at the specified address.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn reset_register<T, Reg, A, Size>(
+    access: &mut T,
+    address: A,
+    reset: Option<Reg>,
+) -> Result<Option<Reg>, T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress,
+    Size: RegisterSizeInformation,
+{
+    match reset {
+        Some(value) => {
+            write_register(access, address, &value)?;
+            Ok(Some(value))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Reads the register of an [`IndexedRegister`] family at the specified index.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn read_indexed<T, Reg, R, Size>(
+    access: &mut T,
+    index: usize,
+) -> Result<Reg, Error<T::Error>>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: IndexedRegister<R, Size>,
+    Reg::Backing: RegisterBacking,
+    R: ArrayAddress,
+    Size: RegisterSizeInformation,
+{
+    let address = Reg::REGISTERS
+        .get(index)
+        .map_err(|e| e.map_bus(|e| match e {}))?;
+    read_register(access, address).map_err(Error::Bus)
+}
+
+/// Writes the register of an [`IndexedRegister`] family at the specified index.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn write_indexed<T, Reg, R, Size>(
+    access: &mut T,
+    index: usize,
+    value: &Reg,
+) -> Result<(), Error<T::Error>>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: IndexedRegister<R, Size>,
+    Reg::Backing: RegisterBacking,
+    R: ArrayAddress,
+    Size: RegisterSizeInformation,
+{
+    let address = Reg::REGISTERS
+        .get(index)
+        .map_err(|e| e.map_bus(|e| match e {}))?;
+    write_register(access, address, value).map_err(Error::Bus)
+}
+
+/// Fills `frames` with bursts read from the FIFO data register at the specified address.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn read_fifo<T, Reg, A, Size>(
+    access: &mut T,
+    address: A,
+    frames: &mut [Reg],
+) -> Result<(), T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+{
+    fifo::read_frames(frames, Size::BYTES, Reg::BYTE_ORDER, |bytes| {
+        access.read_raw_burst(address, bytes)
+    })
+}
+
+/// Reads the FIFO level from the `Count` register at `count_address`, then reads as many
+/// frames as are available and fit into `frames` from the FIFO data register at `address`.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn read_fifo_counted<T, Count, CA, CSize, Reg, A, Size>(
+    access: &mut T,
+    count_address: CA,
+    address: A,
+    frames: &mut [Reg],
+) -> Result<usize, T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Count: HardwareRegister<CSize> + ToBits + FromBits<Count::Target> + FifoCount,
+    Count::Target: RegisterBacking,
+    CA: RegisterAddress,
+    CSize: RegisterSizeInformation,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+{
+    let count: Count = read_register(access, count_address)?;
+    let available = count.frames(Size::BYTES).min(frames.len());
+    read_fifo(access, address, &mut frames[..available])?;
+    Ok(available)
+}
+
+/// Reads the [`StatusRegister`] at the specified address and clears the flags that were read.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn take_status<T, Reg, A, Size>(access: &mut T, address: A) -> Result<Reg, T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: StatusRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+{
+    let flags: Reg = read_register(access, address)?;
+    if Reg::CLEAR != ClearSemantics::ClearOnRead {
+        clear_status(access, address, &flags)?;
+    }
+    Ok(flags)
+}
+
+/// Clears the specified flags of the [`StatusRegister`] at the specified address.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn clear_status<T, Reg, A, Size>(
+    access: &mut T,
+    address: A,
+    flags: &Reg,
+) -> Result<(), T::Error>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: StatusRegister<Size> + ToBits,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress,
+    Size: RegisterSizeInformation,
+{
+    let mut buffer = [0_u8; MAX_BYTES];
+    let buffer = &mut buffer[..Size::BYTES];
+    flags.to_bits().to_register_bytes(buffer, Reg::BYTE_ORDER);
+    status::clear(access, address, Reg::CLEAR, buffer)
+}
+
+/// Reads the [`FlagsRegister`] at the specified address.
+#[cfg(all(feature = "bitflags", any(feature = "i2c", feature = "spi")))]
+pub(crate) fn read_flags<T, Reg, A>(access: &mut T, address: A) -> Result<Reg, Error<T::Error>>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<crate::sizes::R1> + ToBits<Target = u8> + FromBits<u8> + FlagsRegister,
+    A: RegisterAddress,
+{
+    let flags = read_register(access, address).map_err(Error::Bus)?;
+    flags::check(flags)
+}
+
+/// Reads the register at the specified address until it satisfies the predicate, blocking
+/// for the poll interval between the reads.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn wait_until<T, Reg, A, Size, P, D>(
+    access: &mut T,
+    address: A,
+    mut predicate: P,
+    timeout: Timeout,
+    delay: &mut D,
+) -> Result<Reg, Error<T::Error>>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+    P: FnMut(&Reg) -> bool,
+    D: embedded_hal::delay::DelayNs,
+{
+    let mut deadline = timeout.start();
+    loop {
+        let value = read_register(access, address).map_err(Error::Bus)?;
+        if predicate(&value) {
+            return Ok(value);
+        }
+        match deadline.next_delay_us() {
+            Some(us) => delay.delay_us(us),
+            None => return Err(Error::Timeout),
+        }
+    }
+}
+
+/// Reads the register at the specified address until it satisfies the predicate, awaiting
+/// the poll interval between the reads.
+#[cfg(all(feature = "async", any(feature = "i2c", feature = "spi")))]
+pub(crate) async fn wait_until_async<T, Reg, A, Size, P, D>(
+    access: &mut T,
+    address: A,
+    mut predicate: P,
+    timeout: Timeout,
+    delay: &mut D,
+) -> Result<Reg, Error<T::Error>>
+where
+    T: RegisterAccess + ?Sized,
+    Reg: HardwareRegister<Size> + ToBits + FromBits<Reg::Target>,
+    Reg::Target: RegisterBacking,
+    A: RegisterAddress + Copy,
+    Size: RegisterSizeInformation,
+    P: FnMut(&Reg) -> bool,
+    D: embedded_hal_async::delay::DelayNs,
+{
+    let mut deadline = timeout.start();
+    loop {
+        let value = read_register(access, address).map_err(Error::Bus)?;
+        if predicate(&value) {
+            return Ok(value);
+        }
+        match deadline.next_delay_us() {
+            Some(us) => delay.delay_us(us).await,
+            None => return Err(Error::Timeout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::{RegisterAddress16, RegisterAddress8};
+
+    #[test]
+    fn encode_8bit() {
+        let (bytes, len) = encode_address(&RegisterAddress8::new(0x20));
+        assert_eq!(&bytes[..len], &[0x20]);
+    }
+
+    #[test]
+    fn encode_16bit() {
+        let (bytes, len) = encode_address(&RegisterAddress16::new(0x1234));
+        assert_eq!(&bytes[..len], &[0x12, 0x34]);
+    }
+}
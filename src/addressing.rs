@@ -0,0 +1,209 @@
+//! How a device advances the register address during multi-byte transfers.
+//!
+//! Multi-byte registers and block transfers rely on the device advancing its register
+//! address after every byte. Not every device does so unconditionally: some wrap the
+//! address within a block of registers, some only increment if a flag is set in the
+//! register address, e.g. the most significant bit of the sub-address on many ST sensors,
+//! and some do not increment at all.
+//!
+//! The [`AddressingMode`] of an [`I2CDevice`](crate::i2c::I2CDevice) or
+//! [`SPIDevice`](crate::spi::SPIDevice) describes this behavior, and multi-byte transfers
+//! are split or flagged accordingly.
+
+use core::ops::Range;
+
+/// How a device advances the register address during multi-byte transfers.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressingMode {
+    /// The address is incremented after every byte.
+    #[default]
+    AutoIncrement,
+    /// The address is incremented after every byte, but wraps around at the end of each
+    /// aligned block of `block` registers. Transfers crossing a block boundary are split.
+    ///
+    /// A block size of zero is treated as [`AutoIncrement`](Self::AutoIncrement).
+    Wrap {
+        /// The number of registers in a block.
+        block: u16,
+    },
+    /// The address is not incremented. Every byte is transferred separately.
+    NoIncrement,
+    /// The address is only incremented if the `flag` bits are set in the first address byte.
+    /// They are set for multi-byte transfers.
+    IncrementFlag {
+        /// The bits to set in the first address byte, e.g. `0x80`.
+        flag: u8,
+    },
+}
+
+/// A part of a transfer that the device handles in a single transaction.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Segment {
+    /// The register address of the first byte.
+    pub(crate) address: u16,
+    /// The bits to set in the first address byte.
+    pub(crate) flag: u8,
+    /// The bytes of the transfer.
+    pub(crate) range: Range<usize>,
+}
+
+impl AddressingMode {
+    /// Splits a transfer of `len` bytes starting at `address` into segments.
+    ///
+    /// An empty transfer yields a single empty segment.
+    pub(crate) fn segments(self, address: u16, len: usize) -> Segments {
+        Segments {
+            mode: self,
+            address,
+            offset: 0,
+            len,
+            done: false,
+        }
+    }
+}
+
+/// An iterator over the [`Segment`]s of a transfer.
+pub(crate) struct Segments {
+    mode: AddressingMode,
+    address: u16,
+    offset: usize,
+    len: usize,
+    done: bool,
+}
+
+impl Iterator for Segments {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || (self.offset > 0 && self.offset >= self.len) {
+            return None;
+        }
+        let remaining = self.len - self.offset;
+        let (count, flag) = match self.mode {
+            AddressingMode::AutoIncrement | AddressingMode::Wrap { block: 0 } => (remaining, 0),
+            AddressingMode::Wrap { block } => {
+                let to_boundary = usize::from(block - self.address % block);
+                (remaining.min(to_boundary), 0)
+            }
+            AddressingMode::NoIncrement => (remaining.min(1), 0),
+            AddressingMode::IncrementFlag { flag } => {
+                (remaining, if remaining > 1 { flag } else { 0 })
+            }
+        };
+        let segment = Segment {
+            address: self.address,
+            flag,
+            range: self.offset..self.offset + count,
+        };
+        self.offset += count;
+        #[allow(clippy::cast_possible_truncation)]
+        let advance = count as u16;
+        self.address = self.address.wrapping_add(advance);
+        self.done = count == 0;
+        Some(segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(mode: AddressingMode, address: u16, len: usize) -> Vec<(u16, u8, Range<usize>)> {
+        mode.segments(address, len)
+            .map(|s| (s.address, s.flag, s.range))
+            .collect()
+    }
+
+    #[test]
+    fn auto_increment() {
+        let mode = AddressingMode::AutoIncrement;
+        assert_eq!(segments(mode, 0x28, 6), [(0x28, 0, 0..6)]);
+        assert_eq!(segments(mode, 0x28, 0), [(0x28, 0, 0..0)]);
+    }
+
+    #[test]
+    fn wrap() {
+        let mode = AddressingMode::Wrap { block: 8 };
+        assert_eq!(
+            segments(mode, 0x2E, 12),
+            [(0x2E, 0, 0..2), (0x30, 0, 2..10), (0x38, 0, 10..12)]
+        );
+        assert_eq!(segments(mode, 0x28, 8), [(0x28, 0, 0..8)]);
+        let mode = AddressingMode::Wrap { block: 0 };
+        assert_eq!(segments(mode, 0x2E, 12), [(0x2E, 0, 0..12)]);
+    }
+
+    #[test]
+    fn no_increment() {
+        let mode = AddressingMode::NoIncrement;
+        assert_eq!(
+            segments(mode, 0x20, 3),
+            [(0x20, 0, 0..1), (0x21, 0, 1..2), (0x22, 0, 2..3)]
+        );
+        assert_eq!(segments(mode, 0x20, 0), [(0x20, 0, 0..0)]);
+    }
+
+    #[test]
+    fn increment_flag() {
+        let mode = AddressingMode::IncrementFlag { flag: 0x80 };
+        assert_eq!(segments(mode, 0x28, 6), [(0x28, 0x80, 0..6)]);
+        assert_eq!(segments(mode, 0x28, 1), [(0x28, 0, 0..1)]);
+    }
+
+    #[cfg(feature = "i2c")]
+    #[test]
+    fn i2c_no_increment() {
+        use crate::access::RegisterAccess;
+        use crate::i2c::{DeviceAddress7, I2CDevice, RegisterAddress8};
+        use crate::mock::{MockDevice, MockI2c};
+
+        let mock = MockDevice::new::<RegisterAddress8>().with_auto_increment(false);
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock);
+        let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18))
+            .with_addressing(AddressingMode::NoIncrement);
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x12, 0x34]),
+            Ok(())
+        );
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x20), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x12, 0x34]);
+        assert_eq!(
+            device.read_raw_burst(RegisterAddress8::new(0x20), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x12, 0x12]);
+    }
+
+    #[cfg(feature = "spi")]
+    #[test]
+    fn spi_increment_flag() {
+        use crate::access::RegisterAccess;
+        use crate::mock::{MockDevice, MockSpi};
+        use crate::spi::{RegisterAddress8, SPIDevice};
+
+        let mock = MockDevice::new::<RegisterAddress8>().with_increment_flag(0x40);
+        let mut device = SPIDevice::new(MockSpi::new(mock))
+            .with_addressing(AddressingMode::IncrementFlag { flag: 0x40 });
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x12, 0x34]),
+            Ok(())
+        );
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x20), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x12, 0x34]);
+        let mock = device.release();
+        assert_eq!(
+            mock.device().registers().get(RegisterAddress8::new(0x20)),
+            [0x12, 0x34]
+        );
+        assert_eq!(mock.device().registers().byte(0x60), 0);
+    }
+}
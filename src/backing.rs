@@ -0,0 +1,140 @@
+//! Conversion of register backing types to and from their on-the-wire bytes.
+
+/// The order in which the bytes of a multi-byte register are transferred.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ByteOrder {
+    /// The most significant byte is transferred first.
+    #[default]
+    BigEndian,
+    /// The least significant byte is transferred first.
+    LittleEndian,
+}
+
+/// A backing type that can be transferred as a sequence of register bytes.
+///
+/// Implementations write or read exactly as many bytes as the provided buffer holds.
+/// This allows e.g. a 24-bit register ([`R3`](crate::sizes::R3)) to be backed by a `u32`,
+/// in which case only the three least significant bytes are transferred.
+#[allow(clippy::module_name_repetitions)]
+pub trait RegisterBacking: Sized {
+    /// Writes the value into the provided buffer using the specified byte order.
+    fn to_register_bytes(&self, bytes: &mut [u8], order: ByteOrder);
+
+    /// Reads the value from the provided buffer using the specified byte order.
+    #[must_use]
+    fn from_register_bytes(bytes: &[u8], order: ByteOrder) -> Self;
+}
+
+macro_rules! impl_register_backing {
+    ($type: ty) => {
+        impl RegisterBacking for $type {
+            fn to_register_bytes(&self, bytes: &mut [u8], order: ByteOrder) {
+                const SIZE: usize = core::mem::size_of::<$type>();
+                let n = bytes.len().min(SIZE);
+                match order {
+                    ByteOrder::BigEndian => {
+                        bytes[..n].copy_from_slice(&self.to_be_bytes()[SIZE - n..]);
+                    }
+                    ByteOrder::LittleEndian => {
+                        bytes[..n].copy_from_slice(&self.to_le_bytes()[..n]);
+                    }
+                }
+            }
+
+            fn from_register_bytes(bytes: &[u8], order: ByteOrder) -> Self {
+                const SIZE: usize = core::mem::size_of::<$type>();
+                let n = bytes.len().min(SIZE);
+                let mut value = [0_u8; SIZE];
+                match order {
+                    ByteOrder::BigEndian => {
+                        value[SIZE - n..].copy_from_slice(&bytes[..n]);
+                        <$type>::from_be_bytes(value)
+                    }
+                    ByteOrder::LittleEndian => {
+                        value[..n].copy_from_slice(&bytes[..n]);
+                        <$type>::from_le_bytes(value)
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_register_backing!(u8);
+impl_register_backing!(u16);
+impl_register_backing!(u32);
+impl_register_backing!(u64);
+impl_register_backing!(u128);
+
+/// Byte arrays are transferred as-is, regardless of the byte order.
+impl<const N: usize> RegisterBacking for [u8; N] {
+    fn to_register_bytes(&self, bytes: &mut [u8], _order: ByteOrder) {
+        let n = bytes.len().min(N);
+        bytes[..n].copy_from_slice(&self[..n]);
+    }
+
+    fn from_register_bytes(bytes: &[u8], _order: ByteOrder) -> Self {
+        let n = bytes.len().min(N);
+        let mut value = [0_u8; N];
+        value[..n].copy_from_slice(&bytes[..n]);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u16_big_endian() {
+        let mut bytes = [0_u8; 2];
+        0x1234_u16.to_register_bytes(&mut bytes, ByteOrder::BigEndian);
+        assert_eq!(bytes, [0x12, 0x34]);
+        assert_eq!(
+            u16::from_register_bytes(&bytes, ByteOrder::BigEndian),
+            0x1234
+        );
+    }
+
+    #[test]
+    fn u16_little_endian() {
+        let mut bytes = [0_u8; 2];
+        0x1234_u16.to_register_bytes(&mut bytes, ByteOrder::LittleEndian);
+        assert_eq!(bytes, [0x34, 0x12]);
+        assert_eq!(
+            u16::from_register_bytes(&bytes, ByteOrder::LittleEndian),
+            0x1234
+        );
+    }
+
+    #[test]
+    fn u32_as_24_bits() {
+        let mut bytes = [0_u8; 3];
+        0x0012_3456_u32.to_register_bytes(&mut bytes, ByteOrder::BigEndian);
+        assert_eq!(bytes, [0x12, 0x34, 0x56]);
+        assert_eq!(
+            u32::from_register_bytes(&bytes, ByteOrder::BigEndian),
+            0x0012_3456
+        );
+
+        0x0012_3456_u32.to_register_bytes(&mut bytes, ByteOrder::LittleEndian);
+        assert_eq!(bytes, [0x56, 0x34, 0x12]);
+        assert_eq!(
+            u32::from_register_bytes(&bytes, ByteOrder::LittleEndian),
+            0x0012_3456
+        );
+    }
+
+    #[test]
+    fn arrays_ignore_byte_order() {
+        let mut bytes = [0_u8; 3];
+        [1_u8, 2, 3].to_register_bytes(&mut bytes, ByteOrder::LittleEndian);
+        assert_eq!(bytes, [1, 2, 3]);
+        assert_eq!(
+            <[u8; 3]>::from_register_bytes(&bytes, ByteOrder::LittleEndian),
+            [1, 2, 3]
+        );
+    }
+}
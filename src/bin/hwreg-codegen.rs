@@ -0,0 +1,117 @@
+//! Generates register definitions for the `hardware-registers` crate.
+//!
+//! ```text
+//! hwreg-codegen svd <FILE> [--i2c <ADDRESS> | --i2c10 <ADDRESS>] [--spi] [-o <OUTPUT>]
+//! hwreg-codegen ipxact <FILE> [--i2c <ADDRESS> | --i2c10 <ADDRESS>] [--spi] [-o <OUTPUT>]
+//! hwreg-codegen rdl <FILE> [--i2c <ADDRESS> | --i2c10 <ADDRESS>] [--spi] [-o <OUTPUT>]
+//! hwreg-codegen device <FILE> [-o <OUTPUT>]
+//! ```
+
+use hardware_registers::codegen::{self, Addressing, Interface};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: hwreg-codegen <FORMAT> <FILE> [OPTIONS]
+
+Formats:
+  svd       CMSIS-SVD peripheral description
+  ipxact    IP-XACT (IEEE 1685) component
+  rdl       SystemRDL register description
+  device    TOML device description
+
+Options:
+  --i2c <ADDRESS>     Implement I2CRegister with the 7-bit default device address (not for device)
+  --i2c10 <ADDRESS>   Implement I2CRegister with the 10-bit default device address (not for device)
+  --spi               Implement SPIRegister (not for device)
+  -o <OUTPUT>         Write the generated code to a file instead of stdout
+";
+
+struct Arguments {
+    format: String,
+    input: String,
+    interfaces: Vec<Interface>,
+    output: Option<String>,
+}
+
+/// Parses a 7-bit or, with `ten_bit`, 10-bit I²C device address.
+fn parse_address(value: Option<String>, ten_bit: bool) -> Result<u16, String> {
+    let value = value.ok_or("missing device address")?;
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    let address = parsed.map_err(|_| format!("invalid device address {value}"))?;
+    let (max_address, bits) = if ten_bit { (0x3FF, 10) } else { (0x7F, 7) };
+    if address > max_address {
+        return Err(format!("I²C address {value} exceeds {bits} bits"));
+    }
+    Ok(address)
+}
+
+fn parse_arguments() -> Result<Arguments, String> {
+    let mut args = std::env::args().skip(1);
+    let format = args.next().ok_or("missing format")?;
+    let input = args.next().ok_or("missing input file")?;
+    let mut interfaces = Vec::new();
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--i2c" => {
+                interfaces.push(Interface::i2c(parse_address(args.next(), false)?));
+            }
+            "--i2c10" => {
+                interfaces.push(Interface::I2c {
+                    address: parse_address(args.next(), true)?,
+                    alternate_addresses: Vec::new(),
+                    ten_bit: true,
+                    addressing: Addressing::AutoIncrement,
+                });
+            }
+            "--spi" => interfaces.push(Interface::spi()),
+            "-o" => output = Some(args.next().ok_or("missing output file")?),
+            other => return Err(format!("unknown option {other}")),
+        }
+    }
+    Ok(Arguments {
+        format,
+        input,
+        interfaces,
+        output,
+    })
+}
+
+fn run(args: &Arguments) -> Result<(), String> {
+    let text = std::fs::read_to_string(&args.input)
+        .map_err(|e| format!("failed to read {}: {e}", args.input))?;
+    let devices = match args.format.as_str() {
+        "svd" => codegen::svd::parse(&text, &args.interfaces),
+        "ipxact" => codegen::ipxact::parse(&text, &args.interfaces),
+        "rdl" => codegen::systemrdl::parse(&text, &args.interfaces),
+        "device" => codegen::description::parse(&text).map(|device| vec![device]),
+        other => return Err(format!("unknown format {other}")),
+    }
+    .map_err(|e| format!("{}: {e}", args.input))?;
+    let code = codegen::generate(&devices).map_err(|e| e.to_string())?;
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, code).map_err(|e| format!("failed to write {path}: {e}"))
+        }
+        None => {
+            print!("{code}");
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_arguments()
+        .map_err(|e| format!("{e}\n\n{USAGE}"))
+        .and_then(|args| run(&args));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
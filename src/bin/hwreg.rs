@@ -0,0 +1,588 @@
+//! Reads, writes and dumps the registers of an I²C device by name.
+//!
+//! ```text
+//! hwreg [BACKEND] scan
+//! hwreg [BACKEND] -d <DEVICE> [--address <ADDRESS>] get <REGISTER>
+//! hwreg [BACKEND] -d <DEVICE> [--address <ADDRESS>] set <REGISTER>[.<FIELD>] <VALUE>
+//! hwreg [BACKEND] -d <DEVICE> [--address <ADDRESS>] dump
+//! ```
+//!
+//! Devices are given as TOML device descriptions (see `codegen::description`). Register values
+//! are converted from their transfer bytes using the device's byte order and printed along
+//! with their decoded fields, e.g. `CTRL_REG1 @ 0x20 = 0x47 { ODR: 4, LPen: 0, Xen: 1 }`.
+
+use embedded_hal::i2c::{Error as _, I2c};
+use hardware_registers::access::RegisterAccess;
+use hardware_registers::addressing::AddressingMode;
+use hardware_registers::backing::{ByteOrder, RegisterBacking};
+use hardware_registers::codegen::{self, AddressWidth, Addressing, Device, Interface, Register};
+use hardware_registers::i2c::{
+    self, DeviceAddress7, I2CDevice, Probe, RegisterAddress16, RegisterAddress8, ScanResult,
+};
+use hardware_registers::mock::{MockDevice, MockI2c};
+use hardware_registers::snapshot::{Snapshot, SnapshotEntry};
+use std::fmt::Write;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: hwreg [BACKEND] [-d <DEVICE>] [--address <ADDRESS>] <COMMAND>
+
+Commands:
+  scan                              List the 7-bit addresses of responding devices
+  get <REGISTER>                    Read and decode a register
+  set <REGISTER>[.<FIELD>] <VALUE>  Write a register, or read-modify-write a field
+  dump                              Read and decode all readable registers
+
+Backends:
+  --bus <PATH>        Use a Linux I2C bus device file, e.g. /dev/i2c-1
+  --simulate          Use a simulated device initialized with the register reset values
+  --state <SNAPSHOT>  Load and save the simulated register contents in a snapshot file
+
+Options:
+  -d <DEVICE>         TOML device description (required for get, set and dump)
+  --address <ADDRESS> 7-bit device address; defaults to the address of the description
+";
+
+/// The number of non-reserved 7-bit addresses, i.e. the most devices a scan can find.
+const SCAN_CAPACITY: usize = 0x70;
+
+#[derive(Debug, Default)]
+struct Arguments {
+    bus: Option<String>,
+    simulate: bool,
+    state: Option<String>,
+    device: Option<String>,
+    address: Option<u8>,
+    command: Vec<String>,
+}
+
+fn parse_arguments(args: impl IntoIterator<Item = String>) -> Result<Arguments, String> {
+    let mut args = args.into_iter();
+    let mut arguments = Arguments::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bus" => arguments.bus = Some(args.next().ok_or("missing bus device file")?),
+            "--simulate" => arguments.simulate = true,
+            "--state" => {
+                arguments.simulate = true;
+                arguments.state = Some(args.next().ok_or("missing snapshot file")?);
+            }
+            "-d" => arguments.device = Some(args.next().ok_or("missing device description")?),
+            "--address" => {
+                let value = args.next().ok_or("missing device address")?;
+                let address = codegen::parse_integer(&value)
+                    .and_then(|a| u8::try_from(a).ok())
+                    .filter(|a| *a <= 0x7F)
+                    .ok_or_else(|| format!("invalid 7-bit device address {value}"))?;
+                arguments.address = Some(address);
+            }
+            other if other.starts_with('-') => return Err(format!("unknown option {other}")),
+            _ => arguments.command.push(arg),
+        }
+    }
+    if arguments.command.is_empty() {
+        return Err("missing command".into());
+    }
+    Ok(arguments)
+}
+
+/// A register selected on the command line.
+struct Selection<'a> {
+    register: &'a Register,
+    field: Option<&'a codegen::Field>,
+}
+
+impl<'a> Selection<'a> {
+    fn find(device: &'a Device, name: &str) -> Result<Self, String> {
+        let (register_name, field_name) = match name.split_once('.') {
+            Some((register, field)) => (register, Some(field)),
+            None => (name, None),
+        };
+        let register = device
+            .registers
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(register_name))
+            .ok_or_else(|| format!("{} has no register {register_name}", device.name))?;
+        let field = match field_name {
+            Some(field_name) => Some(
+                register
+                    .fields
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case(field_name))
+                    .ok_or_else(|| format!("{} has no field {field_name}", register.name))?,
+            ),
+            None => None,
+        };
+        Ok(Self { register, field })
+    }
+}
+
+/// Register access to the described device, dispatching on its register address width.
+struct Target<'a, B> {
+    device: &'a Device,
+    access: I2CDevice<B, DeviceAddress7>,
+}
+
+impl<B> Target<'_, B>
+where
+    B: I2c,
+{
+    fn read(&mut self, register: &Register) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![0; register_bytes(register)];
+        let result = match self.device.address_width {
+            AddressWidth::Bits8 => self
+                .access
+                .read_raw(RegisterAddress8::new(narrow(register.address)?), &mut bytes),
+            AddressWidth::Bits16 => self.access.read_raw(
+                RegisterAddress16::new(narrow(register.address)?),
+                &mut bytes,
+            ),
+        };
+        result.map_err(|e| format!("failed to read {}: {}", register.name, e.kind()))?;
+        Ok(bytes)
+    }
+
+    fn write(&mut self, register: &Register, bytes: &[u8]) -> Result<(), String> {
+        let result = match self.device.address_width {
+            AddressWidth::Bits8 => self
+                .access
+                .write_raw(RegisterAddress8::new(narrow(register.address)?), bytes),
+            AddressWidth::Bits16 => self
+                .access
+                .write_raw(RegisterAddress16::new(narrow(register.address)?), bytes),
+        };
+        result.map_err(|e| format!("failed to write {}: {}", register.name, e.kind()))
+    }
+}
+
+fn narrow<T>(address: u32) -> Result<T, String>
+where
+    T: TryFrom<u32>,
+{
+    T::try_from(address).map_err(|_| format!("register address 0x{address:X} is out of range"))
+}
+
+fn register_bytes(register: &Register) -> usize {
+    (register.size_bits as usize + 7) / 8
+}
+
+/// Converts the transfer bytes of a register up to 128 bits wide into its value.
+fn value_of(register: &Register, byte_order: ByteOrder, bytes: &[u8]) -> Option<u128> {
+    (register.size_bits <= 128).then(|| u128::from_register_bytes(bytes, byte_order))
+}
+
+fn field_value(value: u128, field: &codegen::Field) -> u128 {
+    let mask = u128::MAX >> (128 - field.width);
+    (value >> field.offset) & mask
+}
+
+/// Formats a register like [`DecodedRegister`](hardware_registers::format::DecodedRegister).
+fn describe(device: &Device, register: &Register, bytes: &[u8]) -> String {
+    let width = match device.address_width {
+        AddressWidth::Bits8 => 2,
+        AddressWidth::Bits16 => 4,
+    };
+    let mut line = format!("{} @ 0x{:0width$X} = 0x", register.name, register.address);
+    match value_of(register, device.byte_order, bytes) {
+        Some(value) => {
+            let _ = write!(line, "{value:0digits$X}", digits = bytes.len() * 2);
+            for (i, field) in register.fields.iter().enumerate() {
+                let separator = if i == 0 { " { " } else { ", " };
+                let _ = write!(
+                    line,
+                    "{separator}{}: {}",
+                    field.name,
+                    field_value(value, field)
+                );
+            }
+            if !register.fields.is_empty() {
+                line.push_str(" }");
+            }
+        }
+        None => bytes.iter().for_each(|b| {
+            let _ = write!(line, "{b:02X}");
+        }),
+    }
+    line
+}
+
+fn get<B: I2c>(target: &mut Target<'_, B>, name: &str, out: &mut String) -> Result<(), String> {
+    let selection = Selection::find(target.device, name)?;
+    if !selection.register.access.is_readable() {
+        return Err(format!("{} is not readable", selection.register.name));
+    }
+    let bytes = target.read(selection.register)?;
+    let _ = writeln!(
+        out,
+        "{}",
+        describe(target.device, selection.register, &bytes)
+    );
+    Ok(())
+}
+
+fn set<B: I2c>(
+    target: &mut Target<'_, B>,
+    name: &str,
+    value: &str,
+    out: &mut String,
+) -> Result<(), String> {
+    let selection = Selection::find(target.device, name)?;
+    let register = selection.register;
+    if !register.access.is_writable() {
+        return Err(format!("{} is not writable", register.name));
+    }
+    if register.size_bits > 128 {
+        return Err(format!("{} is too wide to be set", register.name));
+    }
+    let value = codegen::parse_integer(value).ok_or_else(|| format!("invalid value {value}"))?;
+    let byte_order = target.device.byte_order;
+
+    let value = match selection.field {
+        Some(field) => {
+            if field.access.map_or(false, |a| !a.is_writable()) {
+                return Err(format!("{}.{} is not writable", register.name, field.name));
+            }
+            if value > u128::MAX >> (128 - field.width) {
+                return Err(format!(
+                    "{value} does not fit {}.{}",
+                    register.name, field.name
+                ));
+            }
+            let current = if register.access.is_readable() {
+                let bytes = target.read(register)?;
+                value_of(register, byte_order, &bytes).unwrap_or(0)
+            } else {
+                register.reset.unwrap_or(0)
+            };
+            let mask = (u128::MAX >> (128 - field.width)) << field.offset;
+            (current & !mask) | (value << field.offset)
+        }
+        None => {
+            if register.size_bits < 128 && value >> register.size_bits != 0 {
+                return Err(format!("{value} does not fit {}", register.name));
+            }
+            value
+        }
+    };
+
+    let mut bytes = vec![0; register_bytes(register)];
+    value.to_register_bytes(&mut bytes, byte_order);
+    target.write(register, &bytes)?;
+    let _ = writeln!(out, "{}", describe(target.device, register, &bytes));
+    Ok(())
+}
+
+fn dump<B: I2c>(target: &mut Target<'_, B>, out: &mut String) -> Result<(), String> {
+    let device = target.device;
+    for register in device.registers.iter().filter(|r| r.access.is_readable()) {
+        let bytes = target.read(register)?;
+        let _ = writeln!(out, "{}", describe(device, register, &bytes));
+    }
+    Ok(())
+}
+
+fn scan<B: I2c>(bus: &mut B, out: &mut String) -> Result<(), String> {
+    let found: ScanResult<DeviceAddress7, SCAN_CAPACITY> =
+        i2c::scan(bus, Probe::ReadByte).map_err(|e| format!("scan failed: {}", e.kind()))?;
+    for address in &found {
+        let _ = writeln!(out, "0x{:02X}", address.into_inner());
+    }
+    Ok(())
+}
+
+fn load_device(args: &Arguments) -> Result<Option<Device>, String> {
+    let path = match &args.device {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let text = std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    let device = codegen::description::parse(&text).map_err(|e| format!("{path}: {e}"))?;
+    check_fields(&device).map_err(|e| format!("{path}: {e}"))?;
+    Ok(Some(device))
+}
+
+/// Checks that every field can be extracted from a register value of up to 128 bits.
+fn check_fields(device: &Device) -> Result<(), String> {
+    for register in &device.registers {
+        for field in &register.fields {
+            let end = field.offset.checked_add(field.width);
+            if register.size_bits > 128 {
+                return Err(format!(
+                    "field {}.{} is in a register wider than 128 bits",
+                    register.name, field.name
+                ));
+            }
+            if field.width == 0 || end.map_or(true, |end| end > register.size_bits) {
+                return Err(format!(
+                    "field {}.{} exceeds the register size",
+                    register.name, field.name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn device_address(args: &Arguments, device: Option<&Device>) -> Result<u8, String> {
+    if let Some(address) = args.address {
+        return Ok(address);
+    }
+    let configured = device.and_then(|d| {
+        d.interfaces.iter().find_map(|interface| match interface {
+            Interface::I2c {
+                address,
+                ten_bit: false,
+                ..
+            } => u8::try_from(*address).ok(),
+            _ => None,
+        })
+    });
+    configured.ok_or_else(|| "no 7-bit I2C address; use --address".to_string())
+}
+
+/// Returns how the device advances the register address over I²C.
+fn addressing(device: Option<&Device>) -> Addressing {
+    device
+        .into_iter()
+        .flat_map(|d| &d.interfaces)
+        .find_map(|interface| match interface {
+            Interface::I2c { addressing, .. } => Some(*addressing),
+            Interface::Spi { .. } => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Runs the command on the provided bus.
+fn execute<B: I2c>(
+    bus: &mut B,
+    args: &Arguments,
+    device: Option<&Device>,
+    out: &mut String,
+) -> Result<(), String> {
+    let command: Vec<&str> = args.command.iter().map(String::as_str).collect();
+    if command == ["scan"] {
+        return scan(bus, out);
+    }
+
+    let device = device.ok_or("missing device description (-d)")?;
+    let address = DeviceAddress7::new(device_address(args, Some(device))?);
+    let mut target = Target {
+        device,
+        access: I2CDevice::new(bus, address).with_addressing(match addressing(Some(device)) {
+            Addressing::AutoIncrement => AddressingMode::AutoIncrement,
+            Addressing::Wrap { block } => AddressingMode::Wrap { block },
+            Addressing::NoIncrement => AddressingMode::NoIncrement,
+            Addressing::IncrementFlag { flag } => AddressingMode::IncrementFlag { flag },
+        }),
+    };
+    match command[..] {
+        ["get", register] => get(&mut target, register, out),
+        ["set", register, value] => set(&mut target, register, value, out),
+        ["dump"] => dump(&mut target, out),
+        _ => Err(format!("invalid command {}", command.join(" "))),
+    }
+}
+
+/// Builds a simulated bus with the described device attached, holding its reset values
+/// overlaid with the contents of the state snapshot.
+fn simulated_bus(
+    args: &Arguments,
+    device: Option<&Device>,
+    state: Option<&Snapshot>,
+) -> Result<(MockI2c, u8), String> {
+    let address = device_address(args, device)?;
+    let mut mock = match device.map(|d| d.address_width) {
+        Some(AddressWidth::Bits16) => MockDevice::new::<RegisterAddress16>(),
+        _ => MockDevice::new::<RegisterAddress8>(),
+    };
+    // Transfers are split at block boundaries, so a wrapping device behaves like one
+    // that increments without wrapping.
+    mock = match addressing(device) {
+        Addressing::AutoIncrement | Addressing::Wrap { .. } => mock,
+        Addressing::NoIncrement => mock.with_auto_increment(false),
+        Addressing::IncrementFlag { flag } => mock.with_increment_flag(flag),
+    };
+    for register in device.iter().flat_map(|d| &d.registers) {
+        if let (Some(reset), Some(device)) = (register.reset, device) {
+            let mut bytes = vec![0; register_bytes(register)];
+            reset.to_register_bytes(&mut bytes, device.byte_order);
+            mock.registers_mut()
+                .set(narrow::<u16>(register.address)?, &bytes);
+        }
+    }
+    for entry in state.iter().flat_map(|s| s.entries()) {
+        mock.registers_mut().set(entry.register, &entry.data);
+    }
+    let bus = MockI2c::new().with_device(DeviceAddress7::new(address), mock);
+    Ok((bus, address))
+}
+
+/// Captures the simulated register contents of all described registers.
+fn simulated_state(bus: &MockI2c, address: u8, device: &Device) -> Result<Snapshot, String> {
+    let mut snapshot = Snapshot::new();
+    if let Some(mock) = bus.device(DeviceAddress7::new(address)) {
+        for register in &device.registers {
+            let start = narrow::<u16>(register.address)?;
+            let data = (0..register_bytes(register))
+                .map(|i| mock.registers().byte(start.wrapping_add(i as u16)))
+                .collect();
+            snapshot.insert(SnapshotEntry {
+                register: start,
+                address_bytes: mock.address_bytes(),
+                data,
+            });
+        }
+    }
+    Ok(snapshot)
+}
+
+fn run_simulated(args: &Arguments, device: Option<&Device>) -> Result<String, String> {
+    let state = match &args.state {
+        Some(path) if std::path::Path::new(path).exists() => {
+            let text =
+                std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+            Some(text.parse().map_err(|e| format!("{path}: {e}"))?)
+        }
+        _ => None,
+    };
+    let (mut bus, address) = simulated_bus(args, device, state.as_ref())?;
+    let mut out = String::new();
+    execute(&mut bus, args, device, &mut out)?;
+    if let (Some(path), Some(device)) = (&args.state, device) {
+        let snapshot = simulated_state(&bus, address, device)?;
+        std::fs::write(path, snapshot.to_string())
+            .map_err(|e| format!("failed to write {path}: {e}"))?;
+    }
+    Ok(out)
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+fn run_linux(path: &str, args: &Arguments, device: Option<&Device>) -> Result<String, String> {
+    let mut bus = hardware_registers::linux::LinuxI2c::open(path)
+        .map_err(|e| format!("failed to open {path}: {e}"))?;
+    let mut out = String::new();
+    execute(&mut bus, args, device, &mut out)?;
+    Ok(out)
+}
+
+#[cfg(not(all(feature = "linux", target_os = "linux")))]
+fn run_linux(_path: &str, _args: &Arguments, _device: Option<&Device>) -> Result<String, String> {
+    Err("--bus requires a Linux build with the `linux` feature".into())
+}
+
+fn run(args: &Arguments) -> Result<String, String> {
+    let device = load_device(args)?;
+    match (&args.bus, args.simulate) {
+        (Some(_), true) => Err("--bus and --simulate are mutually exclusive".into()),
+        (Some(path), false) => run_linux(path, args, device.as_ref()),
+        (None, true) => run_simulated(args, device.as_ref()),
+        (None, false) => Err("missing backend; use --bus or --simulate".into()),
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_arguments(std::env::args().skip(1))
+        .map_err(|e| format!("{e}\n\n{USAGE}"))
+        .and_then(|args| run(&args));
+    match result {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIS3DH: &str = "tests/fixtures/description/lis3dh.toml";
+
+    fn run_with(args: &[&str]) -> Result<String, String> {
+        let args = parse_arguments(args.iter().map(ToString::to_string))?;
+        run(&args)
+    }
+
+    #[test]
+    fn get_decodes_fields() {
+        assert_eq!(
+            run_with(&["--simulate", "-d", LIS3DH, "get", "ctrl_reg1"]),
+            Ok("CTRL_REG1 @ 0x20 = 0x07 { ODR: 0, LPen: 0, Zen: 1, Yen: 1, Xen: 1 }\n".into())
+        );
+    }
+
+    #[test]
+    fn set_field_persists_in_state() {
+        let path = std::env::temp_dir().join(format!("hwreg-state-{}", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        let set = run_with(&["--state", &path, "-d", LIS3DH, "set", "CTRL_REG1.ODR", "5"]);
+        let get = run_with(&["--state", &path, "-d", LIS3DH, "dump"]);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            set,
+            Ok("CTRL_REG1 @ 0x20 = 0x57 { ODR: 5, LPen: 0, Zen: 1, Yen: 1, Xen: 1 }\n".into())
+        );
+        assert_eq!(
+            get,
+            Ok("WHO_AM_I @ 0x0F = 0x33\n\
+                CTRL_REG1 @ 0x20 = 0x57 { ODR: 5, LPen: 0, Zen: 1, Yen: 1, Xen: 1 }\n\
+                OUT_X @ 0x28 = 0x0000\n"
+                .into())
+        );
+    }
+
+    #[test]
+    fn reads_with_increment_flag() {
+        let path = std::env::temp_dir().join(format!("hwreg-flag-{}", std::process::id()));
+        let written = std::fs::write(&path, "28 3412\nA8 FFFF\n");
+        let path = path.to_string_lossy().into_owned();
+        let get = run_with(&["--state", &path, "-d", LIS3DH, "get", "OUT_X"]);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written.ok(), Some(()));
+        assert_eq!(get, Ok("OUT_X @ 0x28 = 0x1234\n".into()));
+    }
+
+    #[test]
+    fn rejects_invalid_writes() {
+        assert_eq!(
+            run_with(&["--simulate", "-d", LIS3DH, "set", "WHO_AM_I", "1"]),
+            Err("WHO_AM_I is not writable".into())
+        );
+        assert_eq!(
+            run_with(&["--simulate", "-d", LIS3DH, "set", "CTRL_REG1.ODR", "16"]),
+            Err("16 does not fit CTRL_REG1.ODR".into())
+        );
+    }
+
+    #[test]
+    fn rejects_fields_of_wide_registers() {
+        let path = std::env::temp_dir().join(format!("hwreg-wide-{}.toml", std::process::id()));
+        let description = "name = \"WIDE\"\n\
+            [i2c]\naddress = 0x50\n\
+            [[registers]]\nname = \"KEY\"\naddress = 0\nsize = \"R32\"\n\
+            [[registers.fields]]\nname = \"HIGH\"\nbits = \"255:200\"\n";
+        let written = std::fs::write(&path, description);
+        let path = path.to_string_lossy().into_owned();
+        let get = run_with(&["--simulate", "-d", &path, "get", "KEY.HIGH"]);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written.ok(), Some(()));
+        assert_eq!(
+            get,
+            Err(format!(
+                "{path}: field KEY.HIGH is in a register wider than 128 bits"
+            ))
+        );
+    }
+
+    #[test]
+    fn scan_lists_responding_devices() {
+        assert_eq!(
+            run_with(&["--simulate", "--address", "0x19", "scan"]),
+            Ok("0x19\n".into())
+        );
+    }
+}
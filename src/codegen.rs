@@ -0,0 +1,325 @@
+//! Generation of register definitions from register descriptions.
+//!
+//! Register descriptions are parsed into a [`Device`] model by one of the importers, such as
+//! [`svd`], [`ipxact`], [`systemrdl`] or [`description`], and then turned into Rust source code
+//! by [`generate`]. The generated code contains one module per device with a register type per
+//! register implementing [`HardwareRegister`](crate::HardwareRegister),
+//! [`ToBits`](crate::ToBits) and [`FromBits`](crate::FromBits), field accessors, the
+//! bus-specific register trait selected by the device's [`Interface`], and a
+//! [`RegisterMap`](crate::map::RegisterMap) of all registers.
+//!
+//! The `hwreg-codegen` binary exposes the generator on the command line.
+
+pub mod description;
+mod generate;
+pub mod ipxact;
+mod model;
+pub mod svd;
+pub mod systemrdl;
+mod xml;
+
+pub use crate::codegen::generate::generate;
+pub use crate::codegen::model::{AddressWidth, Addressing, Device, Field, Interface, Register};
+use std::fmt::{Display, Formatter};
+
+/// An error encountered while importing a register description or generating code.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Error {
+    /// The input could not be parsed.
+    Syntax(String),
+    /// The input is valid but cannot be represented by the generated code.
+    Unsupported(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(message) => write!(f, "syntax error: {message}"),
+            Self::Unsupported(message) => write!(f, "unsupported: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Rust keywords that cannot be used as identifiers without escaping.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Splits a register or field name into words at underscores, non-alphanumeric
+/// characters and lower-to-upper case transitions.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(core::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower {
+            words.push(core::mem::take(&mut current));
+        }
+        if !c.is_ascii_digit() {
+            previous_lower = c.is_ascii_lowercase();
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Converts a name such as `CTRL_REG1` into an `UpperCamelCase` type name such as `CtrlReg1`.
+#[must_use]
+pub fn to_upper_camel_case(name: &str) -> String {
+    let mut result = String::new();
+    for word in words(name) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.push(first.to_ascii_uppercase());
+            result.extend(chars.map(|c| c.to_ascii_lowercase()));
+        }
+    }
+    if result.starts_with(|c: char| c.is_ascii_digit()) || result.is_empty() {
+        result.insert(0, 'R');
+    }
+    result
+}
+
+/// Converts a name such as `LPen` or `ODR` into a `snake_case` identifier such as `lpen` or `odr`.
+///
+/// Rust keywords are suffixed with an underscore.
+#[must_use]
+pub fn to_snake_case(name: &str) -> String {
+    let mut result = words(name)
+        .iter()
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if result.starts_with(|c: char| c.is_ascii_digit()) || result.is_empty() {
+        result.insert(0, '_');
+    }
+    if KEYWORDS.contains(&result.as_str()) {
+        result.push('_');
+    }
+    result
+}
+
+/// Parses an integer in decimal, `0x` hexadecimal, `0b` binary, `#` binary or
+/// Verilog notation such as `8'hFF` or `'b101`.
+///
+/// Binary digits given as `x` (don't care, as used by CMSIS-SVD) are read as zero.
+#[must_use]
+pub fn parse_integer(text: &str) -> Option<u128> {
+    let text = text.trim().replace('_', "");
+    if let Some((width, value)) = text.split_once('\'') {
+        if !width.is_empty() {
+            width.parse::<u32>().ok()?;
+        }
+        let mut chars = value.chars();
+        let radix = match chars.next()?.to_ascii_lowercase() {
+            'h' => 16,
+            'd' => 10,
+            'o' => 8,
+            'b' => 2,
+            _ => return None,
+        };
+        u128::from_str_radix(chars.as_str(), radix).ok()
+    } else if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u128::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = text
+        .strip_prefix("0b")
+        .or_else(|| text.strip_prefix("0B"))
+        .or_else(|| text.strip_prefix('#'))
+    {
+        u128::from_str_radix(&bin.replace(['x', 'X'], "0"), 2).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compares generated code against a golden file relative to the crate root.
+    ///
+    /// Set the `BLESS` environment variable to update the golden file instead.
+    pub(crate) fn assert_golden(path: &str, actual: &str) {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
+        if std::env::var_os("BLESS").is_some() {
+            if let Err(error) = std::fs::write(&path, actual) {
+                panic!("failed to write {}: {error}", path.display());
+            }
+            return;
+        }
+        match std::fs::read_to_string(&path) {
+            Ok(expected) => assert!(
+                expected == actual,
+                "generated code differs from {}; rerun with BLESS=1 to update",
+                path.display()
+            ),
+            Err(error) => panic!("failed to read {}: {error}", path.display()),
+        }
+    }
+
+    /// Ensures the golden files compile against this crate.
+    #[cfg(feature = "spi")]
+    #[allow(dead_code)]
+    mod generated {
+        include!("../tests/fixtures/svd/example.rs");
+
+        #[cfg(feature = "i2c")]
+        include!("../tests/fixtures/description/lis3dh.rs");
+
+        #[cfg(feature = "i2c")]
+        include!("../tests/fixtures/systemrdl/example.rs");
+
+        #[cfg(feature = "i2c")]
+        include!("../tests/fixtures/ipxact/uart.rs");
+
+        include!("../tests/fixtures/ipxact/gpio.rs");
+    }
+
+    #[test]
+    #[cfg(feature = "spi")]
+    fn generated_code_is_usable() {
+        use crate::spi::SPIRegister;
+        use crate::HardwareRegister;
+        use generated::uart0;
+
+        let ctrl = uart0::Ctrl::default();
+        assert_eq!(ctrl.baud(), 1);
+        assert!(!ctrl.en());
+        assert_eq!(ctrl.with_en(true).with_mode(2), uart0::Ctrl(0x0001_0005));
+        assert_eq!(
+            <uart0::Ctrl as HardwareRegister<_>>::BYTE_ORDER,
+            crate::backing::ByteOrder::LittleEndian
+        );
+        assert_eq!(*uart0::FifoData1::REGISTER_ADDRESS, 0x11);
+        assert_eq!(
+            uart0::MAP.find("CTRL").and_then(|r| r.reset),
+            Some(&[0x00, 0x00, 0x01, 0x00][..])
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "i2c", feature = "spi"))]
+    fn generated_registers_are_accessible() {
+        use crate::i2c::{I2CDevice, I2CRegisterAccess, RegisterAddress8};
+        use crate::mock::{MockDevice, MockI2c};
+        use generated::lis3dh;
+
+        let mut device = MockDevice::new::<RegisterAddress8>().with_increment_flag(0x80);
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x0F), &[0x33]);
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x28), &[0x34, 0x12]);
+        let bus = MockI2c::new().with_device(lis3dh::DEVICE_ADDRESSES[1], device);
+        let mut device = I2CDevice::new(bus, lis3dh::DEVICE_ADDRESSES[1])
+            .with_addressing(lis3dh::I2C_ADDRESSING);
+
+        assert_eq!(
+            device.read_register::<lis3dh::WhoAmI, _, _>(),
+            Ok(lis3dh::WhoAmI::default())
+        );
+        assert_eq!(
+            device.read_register::<lis3dh::OutX, _, _>(),
+            Ok(lis3dh::OutX(0x1234))
+        );
+        assert_eq!(
+            device.modify_register(|r: lis3dh::CtrlReg1| r.with_odr(5).with_xen(true)),
+            Ok(lis3dh::CtrlReg1(0x51))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "i2c")]
+    fn generated_identity_is_probed() {
+        use crate::i2c::{DeviceAddress7, IdentityRegister, RegisterAddress8};
+        use crate::mock::{MockDevice, MockI2c};
+        use generated::lis3dh;
+
+        let mut device = MockDevice::new::<RegisterAddress8>();
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x0F), &[0x33]);
+        let mut bus = MockI2c::new().with_device(DeviceAddress7::new(0x19), device);
+        assert_eq!(
+            lis3dh::WhoAmI::probe(&mut bus),
+            Ok(Some(DeviceAddress7::new(0x19)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "i2c")]
+    fn generated_maps_are_exportable() {
+        use crate::export::WaveDrom;
+        use generated::lis3dh;
+
+        let ctrl = lis3dh::MAP.find("CTRL_REG1");
+        assert_eq!(
+            ctrl.map(|r| WaveDrom::new(r).to_string()).as_deref(),
+            Some(
+                r#"{"reg":[{"name":"Xen","bits":1,"attr":"RW"},{"name":"Yen","bits":1,"attr":"RW"},{"name":"Zen","bits":1,"attr":"RW"},{"name":"LPen","bits":1,"attr":"RW"},{"name":"ODR","bits":4,"attr":"RW"}],"config":{"bits":8}}"#
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "i2c")]
+    fn generated_registers_decode() {
+        use crate::format::DecodedRegister;
+        use crate::register_address::RegisterAddress8;
+        use generated::lis3dh;
+
+        let value = lis3dh::CtrlReg1(0x47);
+        let decoded = DecodedRegister::new(RegisterAddress8::new(0x20), &value);
+        assert_eq!(
+            format!("{decoded:?}"),
+            "CTRL_REG1 @ 0x20 = 0x47 { ODR: 4, LPen: 0, Zen: 1, Yen: 1, Xen: 1 }"
+        );
+    }
+
+    #[test]
+    fn upper_camel_case() {
+        assert_eq!(to_upper_camel_case("CTRL_REG1"), "CtrlReg1");
+        assert_eq!(to_upper_camel_case("WHO_AM_I"), "WhoAmI");
+        assert_eq!(to_upper_camel_case("fifoCtrl"), "FifoCtrl");
+        assert_eq!(to_upper_camel_case("0_START"), "R0Start");
+    }
+
+    #[test]
+    fn snake_case() {
+        assert_eq!(to_snake_case("ODR"), "odr");
+        assert_eq!(to_snake_case("LPen"), "lpen");
+        assert_eq!(to_snake_case("fifoCtrl"), "fifo_ctrl");
+        assert_eq!(to_snake_case("type"), "type_");
+        assert_eq!(to_snake_case("LIS3DH"), "lis3dh");
+        assert_eq!(to_snake_case("reg1Value"), "reg1_value");
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(parse_integer("0x1F"), Some(31));
+        assert_eq!(parse_integer("#1x0"), Some(4));
+        assert_eq!(parse_integer("0b101"), Some(5));
+        assert_eq!(parse_integer("42"), Some(42));
+        assert_eq!(parse_integer("8'hFF"), Some(255));
+        assert_eq!(parse_integer("'b1010"), Some(10));
+        assert_eq!(parse_integer("16'd1_000"), Some(1000));
+        assert_eq!(parse_integer("4'z0"), None);
+        assert_eq!(parse_integer("x"), None);
+    }
+}
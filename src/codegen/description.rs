@@ -0,0 +1,581 @@
+//! Import of declarative TOML device descriptions.
+//!
+//! Unlike CMSIS-SVD, the description format captures what is specific to I²C and SPI
+//! peripherals: the device address (and the addresses it can be strapped to), the SPI read
+//! flag, how the register address advances during multi-byte transfers, register sizes from
+//! [`R1`](crate::sizes::R1) to [`R64`](crate::sizes::R64), the byte order of multi-byte
+//! registers and the access policy of registers and fields.
+//!
+//! ```toml
+//! name = "LIS3DH"
+//! description = "3-axis accelerometer"
+//! byte-order = "little-endian"     # or "big-endian" (default)
+//! address-width = 8                # or 16; inferred from the addresses if omitted
+//!
+//! [i2c]
+//! address = 0x18
+//! alternate-addresses = [0x19]     # optional
+//! ten-bit = false                  # optional
+//! addressing = { increment-flag = 0x80 }
+//!
+//! [spi]
+//! read-flag = 0x80                 # optional
+//! addressing = "auto-increment"    # default; or "no-increment", { wrap = 8 },
+//!                                  # { increment-flag = 0x40 }
+//!
+//! [[registers]]
+//! name = "CTRL_REG1"
+//! address = 0x20
+//! size = "R1"                      # R1, R2, R3, R4, R8, R16, R32 or R64; defaults to R1
+//! access = "read-write"            # read-only, write-only or read-write (default)
+//! reset = 0x07                     # optional; an integer or a string such as "0x07"
+//! identity = [0x33]                # optional; values identifying the device, e.g. for WHO_AM_I
+//!
+//! [[registers.fields]]
+//! name = "ODR"
+//! bits = "7:4"                     # most and least significant bit, or `bit = 3`
+//! access = "read-write"            # optional; defaults to the register access
+//! ```
+//!
+//! Registers are generated in address order; the `i2c` and `spi` tables select which of
+//! [`I2CRegister`](crate::i2c::I2CRegister) and [`SPIRegister`](crate::spi::SPIRegister)
+//! the generated registers implement.
+
+use crate::backing::ByteOrder;
+use crate::codegen::model::{AddressWidth, Addressing, Device, Field, Interface, Register};
+use crate::codegen::xml::parse_access;
+use crate::codegen::{parse_integer, Error};
+use crate::map::Access;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct DeviceDescription {
+    name: String,
+    description: Option<String>,
+    byte_order: Option<String>,
+    address_width: Option<u32>,
+    i2c: Option<I2cDescription>,
+    spi: Option<SpiDescription>,
+    #[serde(default)]
+    registers: Vec<RegisterDescription>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct I2cDescription {
+    address: u16,
+    #[serde(default)]
+    alternate_addresses: Vec<u16>,
+    #[serde(default)]
+    ten_bit: bool,
+    addressing: Option<AddressingDescription>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct SpiDescription {
+    read_flag: Option<u8>,
+    addressing: Option<AddressingDescription>,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum AddressingDescription {
+    AutoIncrement,
+    Wrap(u16),
+    NoIncrement,
+    IncrementFlag(u8),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RegisterDescription {
+    name: String,
+    description: Option<String>,
+    address: u32,
+    size: Option<String>,
+    access: Option<String>,
+    reset: Option<Integer>,
+    #[serde(default)]
+    identity: Vec<Integer>,
+    #[serde(default)]
+    fields: Vec<FieldDescription>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct FieldDescription {
+    name: String,
+    description: Option<String>,
+    bit: Option<u32>,
+    bits: Option<String>,
+    access: Option<String>,
+}
+
+/// An integer given either as a TOML integer or, for values beyond 63 bits, as a string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Integer {
+    Number(u64),
+    Text(String),
+}
+
+/// Parses a TOML device description.
+///
+/// # Errors
+/// Returns [`Error::Syntax`] if the description is not valid TOML or contains invalid values,
+/// such as a field exceeding its register or an I²C address exceeding 7 bits (10 bits with
+/// `ten-bit`), and [`Error::Unsupported`] if a register address exceeds the address width.
+pub fn parse(text: &str) -> Result<Device, Error> {
+    let description: DeviceDescription =
+        toml::from_str(text).map_err(|e| Error::Syntax(e.to_string()))?;
+
+    let byte_order = match description.byte_order.as_deref() {
+        None | Some("big-endian") => ByteOrder::BigEndian,
+        Some("little-endian") => ByteOrder::LittleEndian,
+        Some(other) => return Err(Error::Syntax(format!("unknown byte order {other}"))),
+    };
+
+    let mut interfaces = Vec::new();
+    if let Some(i2c) = description.i2c {
+        let max_address = if i2c.ten_bit { 0x3FF } else { 0x7F };
+        if let Some(address) = core::iter::once(&i2c.address)
+            .chain(&i2c.alternate_addresses)
+            .find(|address| **address > max_address)
+        {
+            return Err(Error::Syntax(format!(
+                "I²C address 0x{address:X} exceeds {} bits",
+                if i2c.ten_bit { 10 } else { 7 }
+            )));
+        }
+        interfaces.push(Interface::I2c {
+            address: i2c.address,
+            alternate_addresses: i2c.alternate_addresses,
+            ten_bit: i2c.ten_bit,
+            addressing: addressing(i2c.addressing)?,
+        });
+    }
+    if let Some(spi) = description.spi {
+        let addressing = addressing(spi.addressing)?;
+        // The default read flag of `SPIDevice`.
+        let read_flag = spi.read_flag.unwrap_or(0x80);
+        if let Addressing::IncrementFlag { flag } = addressing {
+            if flag & read_flag != 0 {
+                return Err(Error::Syntax(format!(
+                    "SPI increment flag 0x{flag:02X} overlaps the read flag 0x{read_flag:02X}"
+                )));
+            }
+        }
+        interfaces.push(Interface::Spi {
+            read_flag: spi.read_flag,
+            addressing,
+        });
+    }
+
+    let mut registers = description
+        .registers
+        .into_iter()
+        .map(register)
+        .collect::<Result<Vec<_>, _>>()?;
+    registers.sort_by_key(|r| r.address);
+
+    let max_address = registers.iter().map(|r| r.address).max().unwrap_or(0);
+    let address_width = match description.address_width {
+        Some(8) => AddressWidth::Bits8,
+        Some(16) => AddressWidth::Bits16,
+        Some(other) => {
+            return Err(Error::Syntax(format!(
+                "address width must be 8 or 16, not {other}"
+            )))
+        }
+        None => AddressWidth::fitting(max_address).ok_or_else(|| {
+            Error::Unsupported(format!(
+                "register address 0x{max_address:X} exceeds 16 bits"
+            ))
+        })?,
+    };
+
+    Ok(Device {
+        name: description.name,
+        description: description.description,
+        base_address: None,
+        address_width,
+        interfaces,
+        byte_order,
+        registers,
+    })
+}
+
+fn addressing(description: Option<AddressingDescription>) -> Result<Addressing, Error> {
+    Ok(match description {
+        None | Some(AddressingDescription::AutoIncrement) => Addressing::AutoIncrement,
+        Some(AddressingDescription::NoIncrement) => Addressing::NoIncrement,
+        Some(AddressingDescription::Wrap(0)) => {
+            return Err(Error::Syntax(
+                "addressing wrap block must not be zero".into(),
+            ))
+        }
+        Some(AddressingDescription::Wrap(block)) => Addressing::Wrap { block },
+        Some(AddressingDescription::IncrementFlag(0)) => {
+            return Err(Error::Syntax(
+                "addressing increment flag must not be zero".into(),
+            ))
+        }
+        Some(AddressingDescription::IncrementFlag(flag)) => Addressing::IncrementFlag { flag },
+    })
+}
+
+fn register(description: RegisterDescription) -> Result<Register, Error> {
+    let size_bits = match description.size.as_deref().unwrap_or("R1") {
+        "R1" => 8,
+        "R2" => 16,
+        "R3" => 24,
+        "R4" => 32,
+        "R8" => 64,
+        "R16" => 128,
+        "R32" => 256,
+        "R64" => 512,
+        other => {
+            return Err(Error::Syntax(format!(
+                "register {} has an unknown size {other}",
+                description.name
+            )))
+        }
+    };
+    let access = match description.access.as_deref() {
+        Some(access) => parse_access(access)?,
+        None => Access::ReadWrite,
+    };
+    let reset = description
+        .reset
+        .map(|value| integer(value, &description.name, "reset"))
+        .transpose()?;
+    let identity = description
+        .identity
+        .into_iter()
+        .map(|value| integer(value, &description.name, "identity"))
+        .collect::<Result<Vec<_>, _>>()?;
+    let fields = description
+        .fields
+        .into_iter()
+        .map(|f| field(f, &description.name, size_bits))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Register {
+        name: description.name,
+        description: description.description,
+        address: description.address,
+        size_bits,
+        access,
+        reset,
+        identity,
+        fields,
+    })
+}
+
+fn integer(value: Integer, register: &str, what: &str) -> Result<u128, Error> {
+    match value {
+        Integer::Number(value) => Ok(value.into()),
+        Integer::Text(text) => parse_integer(&text).ok_or_else(|| {
+            Error::Syntax(format!(
+                "register {register} has an invalid {what} value {text}"
+            ))
+        }),
+    }
+}
+
+fn field(description: FieldDescription, register: &str, size_bits: u32) -> Result<Field, Error> {
+    let (offset, width) = match (description.bit, description.bits.as_deref()) {
+        (Some(bit), None) => (bit, 1),
+        (None, Some(bits)) => {
+            let range = bits.split_once(':').and_then(|(msb, lsb)| {
+                let msb: u32 = msb.trim().parse().ok()?;
+                let lsb: u32 = lsb.trim().parse().ok()?;
+                (msb >= lsb).then_some((lsb, msb - lsb + 1))
+            });
+            range.ok_or_else(|| {
+                Error::Syntax(format!(
+                    "field {} has an invalid bit range {bits}",
+                    description.name
+                ))
+            })?
+        }
+        _ => {
+            return Err(Error::Syntax(format!(
+                "field {} requires either `bit` or `bits`",
+                description.name
+            )))
+        }
+    };
+    if offset
+        .checked_add(width)
+        .map_or(true, |end| end > size_bits)
+    {
+        return Err(Error::Syntax(format!(
+            "field {register}.{} exceeds the register size",
+            description.name
+        )));
+    }
+    Ok(Field {
+        name: description.name,
+        description: description.description,
+        offset,
+        width,
+        access: description
+            .access
+            .as_deref()
+            .map(parse_access)
+            .transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::generate;
+    use crate::codegen::tests::assert_golden;
+
+    const LIS3DH: &str = include_str!("../../tests/fixtures/description/lis3dh.toml");
+
+    fn device() -> Device {
+        match parse(LIS3DH) {
+            Ok(device) => device,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    #[test]
+    fn interfaces() {
+        assert_eq!(
+            device().interfaces,
+            [
+                Interface::I2c {
+                    address: 0x18,
+                    alternate_addresses: vec![0x19],
+                    ten_bit: false,
+                    addressing: Addressing::IncrementFlag { flag: 0x80 },
+                },
+                Interface::Spi {
+                    read_flag: Some(0x80),
+                    addressing: Addressing::IncrementFlag { flag: 0x40 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn registers() {
+        let device = device();
+        assert_eq!(device.byte_order, ByteOrder::LittleEndian);
+        assert_eq!(device.address_width, AddressWidth::Bits8);
+        let registers: Vec<_> = device
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.address, r.size_bits, r.access, r.reset))
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                ("WHO_AM_I", 0x0F, 8, Access::ReadOnly, Some(0x33)),
+                ("CTRL_REG1", 0x20, 8, Access::ReadWrite, Some(0x07)),
+                ("OUT_X", 0x28, 16, Access::ReadOnly, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let device = device();
+        let fields: Vec<_> = device.registers[1]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.width))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("ODR", 4, 4),
+                ("LPen", 3, 1),
+                ("Zen", 2, 1),
+                ("Yen", 1, 1),
+                ("Xen", 0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn golden() {
+        let code = match generate(&[device()]) {
+            Ok(code) => code,
+            Err(error) => panic!("{error}"),
+        };
+        assert_golden("tests/fixtures/description/lis3dh.rs", &code);
+    }
+
+    #[test]
+    fn wide_registers() {
+        let device = parse(
+            r#"
+            name = "EEPROM"
+            address-width = 16
+
+            [[registers]]
+            name = "SERIAL"
+            address = 0x0800
+            size = "R16"
+            access = "read-only"
+            reset = "0x0102030405060708090A0B0C0D0E0F10"
+            "#,
+        );
+        let device = match device {
+            Ok(device) => device,
+            Err(error) => panic!("{error}"),
+        };
+        assert_eq!(device.address_width, AddressWidth::Bits16);
+        assert_eq!(device.registers[0].size_bits, 128);
+        assert_eq!(
+            device.registers[0].reset,
+            Some(0x0102_0304_0506_0708_090A_0B0C_0D0E_0F10)
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_values() {
+        let device = match parse(include_str!(
+            "../../tests/fixtures/description/oversized.toml"
+        )) {
+            Ok(device) => device,
+            Err(error) => panic!("{error}"),
+        };
+        for register in &device.registers {
+            let device = Device {
+                registers: vec![register.clone()],
+                ..device.clone()
+            };
+            assert!(matches!(generate(&[device]), Err(Error::Unsupported(_))));
+        }
+    }
+
+    #[test]
+    fn rejects_name_collisions() {
+        let registers = r#"
+            name = "X"
+            [[registers]]
+            name = "CTRL_REG"
+            address = 0
+            [[registers]]
+            name = "CtrlReg"
+            address = 1
+        "#;
+        let fields = r#"
+            name = "X"
+            [[registers]]
+            name = "CTRL"
+            address = 0
+            [[registers.fields]]
+            name = "ODR"
+            bit = 0
+            [[registers.fields]]
+            name = "odr"
+            bit = 1
+        "#;
+        for description in [registers, fields] {
+            let device = match parse(description) {
+                Ok(device) => device,
+                Err(error) => panic!("{error}"),
+            };
+            assert!(matches!(generate(&[device]), Err(Error::Unsupported(_))));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_descriptions() {
+        let missing_bits = r#"
+            name = "X"
+            [[registers]]
+            name = "A"
+            address = 0
+            [[registers.fields]]
+            name = "F"
+        "#;
+        assert!(matches!(parse(missing_bits), Err(Error::Syntax(_))));
+        let unknown_size = r#"
+            name = "X"
+            [[registers]]
+            name = "A"
+            address = 0
+            size = "R5"
+        "#;
+        assert!(matches!(parse(unknown_size), Err(Error::Syntax(_))));
+        assert!(matches!(parse("name = 1"), Err(Error::Syntax(_))));
+        let wide_field = r#"
+            name = "X"
+            [[registers]]
+            name = "A"
+            address = 0
+            [[registers.fields]]
+            name = "F"
+            bits = "8:4"
+        "#;
+        assert!(matches!(parse(wide_field), Err(Error::Syntax(_))));
+        let eight_bit_address = r#"
+            name = "X"
+            [i2c]
+            address = 0x80
+        "#;
+        assert!(matches!(parse(eight_bit_address), Err(Error::Syntax(_))));
+        let alternate_address = r#"
+            name = "X"
+            [i2c]
+            address = 0x18
+            alternate-addresses = [0x400]
+            ten-bit = true
+        "#;
+        assert!(matches!(parse(alternate_address), Err(Error::Syntax(_))));
+        assert!(parse("name = \"X\"\n[i2c]\naddress = 0x80\nten-bit = true").is_ok());
+    }
+
+    #[test]
+    fn addressing_modes() {
+        let addressing = |table: &str, value: &str| {
+            let text = format!("name = \"X\"\n[{table}]\naddress = 0x18\naddressing = {value}");
+            let text = text.replace("[spi]\naddress = 0x18", "[spi]");
+            parse(&text).map(|device| match device.interfaces[..] {
+                [Interface::I2c { addressing, .. } | Interface::Spi { addressing, .. }] => {
+                    addressing
+                }
+                _ => panic!("expected a single interface"),
+            })
+        };
+        assert_eq!(
+            addressing("i2c", "\"auto-increment\""),
+            Ok(Addressing::AutoIncrement)
+        );
+        assert_eq!(
+            addressing("i2c", "\"no-increment\""),
+            Ok(Addressing::NoIncrement)
+        );
+        assert_eq!(
+            addressing("spi", "{ wrap = 8 }"),
+            Ok(Addressing::Wrap { block: 8 })
+        );
+        assert_eq!(
+            addressing("i2c", "{ increment-flag = 0x80 }"),
+            Ok(Addressing::IncrementFlag { flag: 0x80 })
+        );
+        assert!(matches!(
+            addressing("i2c", "\"wrap\""),
+            Err(Error::Syntax(_))
+        ));
+        assert!(matches!(
+            addressing("i2c", "{ wrap = 0 }"),
+            Err(Error::Syntax(_))
+        ));
+        assert_eq!(
+            addressing("spi", "{ increment-flag = 0xC0 }"),
+            Err(Error::Syntax(
+                "SPI increment flag 0xC0 overlaps the read flag 0x80".into()
+            ))
+        );
+    }
+}
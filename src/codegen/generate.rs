@@ -0,0 +1,745 @@
+use crate::backing::{ByteOrder, RegisterBacking};
+use crate::codegen::model::{AddressWidth, Addressing, Device, Field, Interface, Register};
+use crate::codegen::{to_snake_case, to_upper_camel_case, Error};
+use crate::map::Access;
+use std::fmt::Write;
+
+/// Generates Rust source code for the provided devices, one module per device.
+///
+/// # Errors
+/// Returns [`Error::Unsupported`] if a register cannot be represented, e.g. because of
+/// an unsupported size or an address exceeding the device's address width.
+pub fn generate(devices: &[Device]) -> Result<String, Error> {
+    let mut out = String::new();
+    out.push_str("// This file was generated by hardware-registers. Do not edit.\n");
+    for device in devices {
+        out.push('\n');
+        generate_device(&mut out, device)?;
+    }
+    Ok(out)
+}
+
+/// The Rust representation of a register size.
+struct SizeInfo {
+    marker: &'static str,
+    backing: &'static str,
+    bytes: usize,
+}
+
+/// Whether `register` needs its byte order spelled out, i.e. is wider than a byte and not
+/// big-endian.
+fn overrides_byte_order(device: &Device, register: &Register) -> bool {
+    register.size_bits > 8 && device.byte_order == ByteOrder::LittleEndian
+}
+
+fn size_info(register: &Register) -> Result<SizeInfo, Error> {
+    let (marker, backing, bytes) = match register.size_bits {
+        8 => ("R1", "u8", 1),
+        16 => ("R2", "u16", 2),
+        24 => ("R3", "u32", 3),
+        32 => ("R4", "u32", 4),
+        64 => ("R8", "u64", 8),
+        128 => ("R16", "u128", 16),
+        256 => ("R32", "[u8; 32]", 32),
+        512 => ("R64", "[u8; 64]", 64),
+        bits => {
+            return Err(Error::Unsupported(format!(
+                "register {} has an unsupported size of {bits} bits",
+                register.name
+            )))
+        }
+    };
+    Ok(SizeInfo {
+        marker,
+        backing,
+        bytes,
+    })
+}
+
+/// The largest register, in bits, that is backed by an integer rather than a byte array.
+const MAX_INTEGER_BITS: u32 = 128;
+
+fn field_type(width: u32) -> &'static str {
+    match width {
+        1 => "bool",
+        2..=8 => "u8",
+        9..=16 => "u16",
+        17..=32 => "u32",
+        33..=64 => "u64",
+        _ => "u128",
+    }
+}
+
+fn device_address_type(ten_bit: bool) -> &'static str {
+    if ten_bit {
+        "DeviceAddress10"
+    } else {
+        "DeviceAddress7"
+    }
+}
+
+fn address_type(width: AddressWidth) -> &'static str {
+    match width {
+        AddressWidth::Bits8 => "RegisterAddress8",
+        AddressWidth::Bits16 => "RegisterAddress16",
+    }
+}
+
+fn access_variant(access: Access) -> &'static str {
+    match access {
+        Access::ReadOnly => "ReadOnly",
+        Access::WriteOnly => "WriteOnly",
+        Access::ReadWrite => "ReadWrite",
+    }
+}
+
+fn write_doc(out: &mut String, indent: &str, text: &str) {
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            let _ = writeln!(out, "{indent}///");
+        } else {
+            let _ = writeln!(out, "{indent}/// {line}");
+        }
+    }
+}
+
+/// Checks that all registers, fields, reset and identity values fit and returns the used
+/// size markers.
+fn size_markers(device: &Device) -> Result<Vec<&'static str>, Error> {
+    let mut sizes: Vec<&str> = Vec::new();
+    for register in &device.registers {
+        if register.address > device.address_width.max_address() {
+            return Err(Error::Unsupported(format!(
+                "register {} at 0x{:X} exceeds the register address width",
+                register.name, register.address
+            )));
+        }
+        let marker = size_info(register)?.marker;
+        if !sizes.contains(&marker) {
+            sizes.push(marker);
+        }
+        if register.size_bits > MAX_INTEGER_BITS
+            && (!register.fields.is_empty()
+                || register.reset.is_some()
+                || !register.identity.is_empty())
+        {
+            return Err(Error::Unsupported(format!(
+                "register {} is backed by a byte array and cannot have fields, a reset value or identity values",
+                register.name
+            )));
+        }
+        if let Some(value) = register
+            .reset
+            .iter()
+            .chain(&register.identity)
+            .find(|value| {
+                register.size_bits < MAX_INTEGER_BITS && **value >> register.size_bits != 0
+            })
+        {
+            return Err(Error::Unsupported(format!(
+                "value 0x{value:X} of register {} exceeds its {} bits",
+                register.name, register.size_bits
+            )));
+        }
+        if !register.identity.is_empty() && !register.access.is_readable() {
+            return Err(Error::Unsupported(format!(
+                "identity register {} is not readable",
+                register.name
+            )));
+        }
+        for field in &register.fields {
+            if field.width == 0 || field.offset + field.width > register.size_bits {
+                return Err(Error::Unsupported(format!(
+                    "field {}.{} exceeds the register size",
+                    register.name, field.name
+                )));
+            }
+        }
+    }
+    sizes.sort_unstable();
+    Ok(sizes)
+}
+
+/// Checks that no two registers map to the same type and no two fields of a register map to
+/// the same accessor.
+fn check_names(device: &Device) -> Result<(), Error> {
+    let mut types = Vec::new();
+    for register in &device.registers {
+        let name = to_upper_camel_case(&register.name);
+        if types.contains(&name) {
+            return Err(Error::Unsupported(format!(
+                "register {} duplicates the name of another register",
+                register.name
+            )));
+        }
+        types.push(name);
+        let mut accessors = Vec::new();
+        for field in &register.fields {
+            let name = to_snake_case(&field.name);
+            let name = name.trim_end_matches('_').to_string();
+            if accessors.contains(&name) {
+                return Err(Error::Unsupported(format!(
+                    "field {} of register {} duplicates the name of another field",
+                    field.name, register.name
+                )));
+            }
+            accessors.push(name);
+        }
+    }
+    Ok(())
+}
+
+fn generate_device(out: &mut String, device: &Device) -> Result<(), Error> {
+    let module = to_snake_case(&device.name);
+    check_names(device)?;
+    let sizes = size_markers(device)?;
+
+    let doc = match &device.description {
+        Some(description) => description.clone(),
+        None => format!("The `{}` device.", device.name),
+    };
+    write_doc(out, "", &doc);
+    let _ = writeln!(out, "pub mod {module} {{");
+    out.push_str(
+        "    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]\n\n",
+    );
+    write_imports(out, device, &sizes);
+
+    if let Some(base) = device.base_address {
+        out.push('\n');
+        let _ = writeln!(out, "    /// The base address of the peripheral.");
+        let _ = writeln!(out, "    pub const BASE_ADDRESS: u64 = 0x{base:X};");
+    }
+    write_interface_constants(out, device);
+
+    for register in &device.registers {
+        out.push('\n');
+        generate_register(out, device, register)?;
+    }
+
+    out.push('\n');
+    write_register_map(out, device)?;
+    out.push_str("}\n");
+    Ok(())
+}
+
+fn write_imports(out: &mut String, device: &Device, sizes: &[&str]) {
+    let address = address_type(device.address_width);
+    if device.registers.iter().any(|r| !r.fields.is_empty()) {
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::format::{{DecodeRegister, FieldValue, FieldVisitor}};"
+        );
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::map::{{Access, FieldInfo, RegisterInfo, RegisterMap}};"
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::format::{{DecodeRegister, FieldVisitor}};"
+        );
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::map::{{Access, RegisterInfo, RegisterMap}};"
+        );
+    }
+    let _ = writeln!(
+        out,
+        "    use hardware_registers::register_address::{address};"
+    );
+    let _ = writeln!(
+        out,
+        "    use hardware_registers::sizes::{{{}}};",
+        sizes.join(", ")
+    );
+    if device
+        .registers
+        .iter()
+        .any(|register| overrides_byte_order(device, register))
+    {
+        let _ = writeln!(out, "    use hardware_registers::backing::ByteOrder;");
+    }
+    if device.interfaces.iter().any(|interface| match interface {
+        Interface::I2c { addressing, .. } | Interface::Spi { addressing, .. } => {
+            *addressing != Addressing::AutoIncrement
+        }
+    }) {
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::addressing::AddressingMode;"
+        );
+    }
+    for interface in &device.interfaces {
+        match interface {
+            Interface::I2c { ten_bit, .. } => {
+                let identity = if device.registers.iter().any(|r| !r.identity.is_empty()) {
+                    ", IdentityRegister"
+                } else {
+                    ""
+                };
+                let _ = writeln!(
+                    out,
+                    "    use hardware_registers::i2c::{{{}, I2CRegister{identity}}};",
+                    device_address_type(*ten_bit)
+                );
+            }
+            Interface::Spi { .. } => {
+                let _ = writeln!(out, "    use hardware_registers::spi::SPIRegister;");
+            }
+        }
+    }
+    if device.registers.iter().any(|r| r.access.is_writable()) {
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::{{FromBits, HardwareRegister, ToBits, WritableHardwareRegister}};"
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "    use hardware_registers::{{FromBits, HardwareRegister, ToBits}};"
+        );
+    }
+}
+
+/// Writes the device addresses and SPI framing of the device's interfaces.
+fn write_interface_constants(out: &mut String, device: &Device) {
+    for interface in &device.interfaces {
+        match interface {
+            Interface::I2c {
+                address,
+                alternate_addresses,
+                ten_bit,
+                addressing,
+            } => {
+                let ty = device_address_type(*ten_bit);
+                out.push('\n');
+                let _ = writeln!(out, "    /// The factory-default I²C device address.");
+                let _ = writeln!(
+                    out,
+                    "    pub const DEFAULT_DEVICE_ADDRESS: {ty} = {ty}::new(0x{address:02X});"
+                );
+                if !alternate_addresses.is_empty() {
+                    let addresses: Vec<String> = core::iter::once(address)
+                        .chain(alternate_addresses)
+                        .map(|a| format!("{ty}::new(0x{a:02X})"))
+                        .collect();
+                    out.push('\n');
+                    let _ = writeln!(
+                        out,
+                        "    /// All I²C device addresses the device can be configured to."
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    pub const DEVICE_ADDRESSES: &[{ty}] = &[{}];",
+                        addresses.join(", ")
+                    );
+                }
+                write_addressing(out, "I2C", "I2CDevice", *addressing);
+            }
+            Interface::Spi {
+                read_flag,
+                addressing,
+            } => {
+                if let Some(flag) = read_flag {
+                    out.push('\n');
+                    let _ = writeln!(
+                        out,
+                        "    /// The flag marking SPI reads, for use with `SPIDevice::with_read_flag`."
+                    );
+                    let _ = writeln!(out, "    pub const SPI_READ_FLAG: u8 = 0x{flag:02X};");
+                }
+                write_addressing(out, "SPI", "SPIDevice", *addressing);
+            }
+        }
+    }
+}
+
+/// Writes the addressing mode of an interface unless the device auto-increments.
+fn write_addressing(out: &mut String, bus: &str, device: &str, addressing: Addressing) {
+    let mode = match addressing {
+        Addressing::AutoIncrement => return,
+        Addressing::Wrap { block } => format!("Wrap {{ block: {block} }}"),
+        Addressing::NoIncrement => "NoIncrement".to_string(),
+        Addressing::IncrementFlag { flag } => format!("IncrementFlag {{ flag: 0x{flag:02X} }}"),
+    };
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "    /// How the register address advances, for use with `{device}::with_addressing`."
+    );
+    let _ = writeln!(
+        out,
+        "    pub const {bus}_ADDRESSING: AddressingMode = AddressingMode::{mode};"
+    );
+}
+
+fn write_register_map(out: &mut String, device: &Device) -> Result<(), Error> {
+    let address = address_type(device.address_width);
+    let _ = writeln!(out, "    /// The registers of the device.");
+    let _ = writeln!(
+        out,
+        "    pub const REGISTERS: &[RegisterInfo<{address}>] = &["
+    );
+    for register in &device.registers {
+        let size = size_info(register)?;
+        let _ = write!(
+            out,
+            "        RegisterInfo::new({:?}, {address}::new(0x{:02X}), {}, Access::{})",
+            register.name,
+            register.address,
+            size.bytes,
+            access_variant(register.access)
+        );
+        if let Some(reset) = register.reset {
+            let mut bytes = [0_u8; 16];
+            reset.to_register_bytes(&mut bytes[..size.bytes], device.byte_order);
+            let bytes: Vec<String> = bytes[..size.bytes]
+                .iter()
+                .map(|b| format!("0x{b:02X}"))
+                .collect();
+            let _ = write!(out, "\n            .with_reset(&[{}])", bytes.join(", "));
+        }
+        if overrides_byte_order(device, register) {
+            out.push_str("\n            .with_byte_order(ByteOrder::LittleEndian)");
+        }
+        write_field_infos(out, register);
+        out.push_str(",\n");
+    }
+    out.push_str("    ];\n\n");
+    let _ = writeln!(out, "    /// The register map of the device.");
+    let _ = writeln!(
+        out,
+        "    pub const MAP: RegisterMap<{address}> = RegisterMap::new({:?}, REGISTERS);",
+        device.name
+    );
+    Ok(())
+}
+
+fn write_field_infos(out: &mut String, register: &Register) {
+    if register.fields.is_empty() {
+        return;
+    }
+    out.push_str("\n            .with_fields(&[\n");
+    for field in &register.fields {
+        let _ = writeln!(
+            out,
+            "                FieldInfo::new({:?}, {}, {}, Access::{}),",
+            field.name,
+            field.offset,
+            field.width,
+            access_variant(field.access.unwrap_or(register.access))
+        );
+    }
+    out.push_str("            ])");
+}
+
+fn generate_register(out: &mut String, device: &Device, register: &Register) -> Result<(), Error> {
+    let size = size_info(register)?;
+    let name = to_upper_camel_case(&register.name);
+    let backing = size.backing;
+
+    let doc = match &register.description {
+        Some(description) => format!("`{}`: {description}", register.name),
+        None => format!("The `{}` register.", register.name),
+    };
+    write_doc(out, "    ", &doc);
+    out.push_str("    #[derive(Debug, Copy, Clone, Eq, PartialEq)]\n");
+    let _ = writeln!(out, "    pub struct {name}(pub {backing});");
+
+    if !register.fields.is_empty() {
+        out.push('\n');
+        let _ = writeln!(out, "    impl {name} {{");
+        let mut first = true;
+        for field in &register.fields {
+            if !first {
+                out.push('\n');
+            }
+            first = false;
+            generate_field(out, register, field, backing);
+        }
+        out.push_str("    }\n");
+    }
+
+    if let Some(reset) = register.reset {
+        out.push('\n');
+        let _ = writeln!(out, "    impl Default for {name} {{");
+        out.push_str("        fn default() -> Self {\n");
+        let _ = writeln!(out, "            Self(0x{reset:02X})");
+        out.push_str("        }\n");
+        out.push_str("    }\n");
+    }
+
+    out.push('\n');
+    if overrides_byte_order(device, register) {
+        let _ = writeln!(
+            out,
+            "    impl HardwareRegister<{}> for {name} {{",
+            size.marker
+        );
+        out.push_str("        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;\n");
+        out.push_str("    }\n");
+    } else {
+        let _ = writeln!(
+            out,
+            "    impl HardwareRegister<{}> for {name} {{}}",
+            size.marker
+        );
+    }
+    if register.access.is_writable() {
+        let _ = writeln!(
+            out,
+            "    impl WritableHardwareRegister<{}> for {name} {{}}",
+            size.marker
+        );
+    }
+
+    out.push('\n');
+    write_bit_conversions(out, &name, backing);
+    out.push('\n');
+    write_decode(out, register, &name, backing);
+    write_bus_register(out, device, register, &name)
+}
+
+fn write_bit_conversions(out: &mut String, name: &str, backing: &str) {
+    let _ = writeln!(out, "    impl ToBits for {name} {{");
+    let _ = writeln!(out, "        type Target = {backing};\n");
+    out.push_str("        fn to_bits(&self) -> Self::Target {\n");
+    out.push_str("            self.0\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+    let _ = writeln!(out, "    impl FromBits<{backing}> for {name} {{");
+    let _ = writeln!(
+        out,
+        "        fn from_bits_ref(value: &{backing}) -> Self {{"
+    );
+    out.push_str("            Self(*value)\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+}
+
+fn write_decode(out: &mut String, register: &Register, name: &str, backing: &str) {
+    let _ = writeln!(out, "    impl DecodeRegister for {name} {{");
+    let _ = writeln!(
+        out,
+        "        const NAME: &'static str = {:?};\n",
+        register.name
+    );
+    if register.fields.is_empty() {
+        out.push_str("        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}\n");
+    } else {
+        out.push_str("        fn decode(&self, visitor: &mut dyn FieldVisitor) {\n");
+        for field in &register.fields {
+            let value = field_value(register, field, backing);
+            let value = match field_type(field.width) {
+                "bool" => format!("FieldValue::Bit({value})"),
+                "u128" => format!("FieldValue::Unsigned({value})"),
+                _ => format!("FieldValue::Unsigned(u128::from({value}))"),
+            };
+            let _ = writeln!(out, "            visitor.field({:?}, {value});", field.name);
+        }
+        out.push_str("        }\n");
+    }
+    out.push_str("    }\n");
+}
+
+fn write_bus_register(
+    out: &mut String,
+    device: &Device,
+    register: &Register,
+    name: &str,
+) -> Result<(), Error> {
+    let size = size_info(register)?;
+    let backing = size.backing;
+    let address = address_type(device.address_width);
+    let reset = register
+        .reset
+        .map_or_else(|| "None".to_string(), |r| format!("Some(0x{r:02X})"));
+    for interface in &device.interfaces {
+        out.push('\n');
+        match interface {
+            Interface::I2c { ten_bit, .. } => {
+                let dev_type = device_address_type(*ten_bit);
+                let _ = writeln!(
+                    out,
+                    "    impl I2CRegister<{dev_type}, {address}, {}> for {name} {{",
+                    size.marker
+                );
+                let _ = writeln!(
+                    out,
+                    "        const DEFAULT_DEVICE_ADDRESS: {dev_type} = DEFAULT_DEVICE_ADDRESS;"
+                );
+            }
+            Interface::Spi { .. } => {
+                let _ = writeln!(
+                    out,
+                    "    impl SPIRegister<{address}, {}> for {name} {{",
+                    size.marker
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "        const REGISTER_ADDRESS: {address} = {address}::new(0x{:02X});",
+            register.address
+        );
+        let _ = writeln!(
+            out,
+            "        const RESET_VALUE: Option<{backing}> = {reset};"
+        );
+        let _ = writeln!(out, "        type Backing = {backing};");
+        out.push_str("    }\n");
+        if let Interface::I2c {
+            alternate_addresses,
+            ten_bit,
+            ..
+        } = interface
+        {
+            write_identity(
+                out,
+                device,
+                register,
+                name,
+                &size,
+                alternate_addresses,
+                *ten_bit,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Writes the [`IdentityRegister`](crate::i2c::IdentityRegister) implementation of an
+/// identity register.
+fn write_identity(
+    out: &mut String,
+    device: &Device,
+    register: &Register,
+    name: &str,
+    size: &SizeInfo,
+    alternate_addresses: &[u16],
+    ten_bit: bool,
+) {
+    if register.identity.is_empty() {
+        return;
+    }
+    let dev_type = device_address_type(ten_bit);
+    let address = address_type(device.address_width);
+    let values: Vec<String> = register
+        .identity
+        .iter()
+        .map(|v| format!("0x{v:02X}"))
+        .collect();
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "    impl IdentityRegister<{dev_type}, {address}, {}> for {name} {{",
+        size.marker
+    );
+    let _ = writeln!(
+        out,
+        "        const EXPECTED: &'static [{}] = &[{}];",
+        size.backing,
+        values.join(", ")
+    );
+    if !alternate_addresses.is_empty() {
+        let _ = writeln!(
+            out,
+            "        const ALTERNATE_DEVICE_ADDRESSES: &'static [{dev_type}] = DEVICE_ADDRESSES;"
+        );
+    }
+    out.push_str("    }\n");
+}
+
+/// Gets the expression extracting the value of a field from `self.0`.
+fn field_value(register: &Register, field: &Field, backing: &str) -> String {
+    let ty = field_type(field.width);
+    let offset = field.offset;
+    let mask = if field.width >= 128 {
+        u128::MAX
+    } else {
+        (1_u128 << field.width) - 1
+    };
+    let full = offset == 0 && field.width == register.size_bits && ty == backing;
+    let shifted = if offset == 0 {
+        "self.0".to_string()
+    } else {
+        format!("(self.0 >> {offset})")
+    };
+    if ty == "bool" {
+        format!("{shifted} & 0x1 != 0")
+    } else if full {
+        "self.0".to_string()
+    } else if ty == backing {
+        format!("{shifted} & 0x{mask:X}")
+    } else {
+        format!("({shifted} & 0x{mask:X}) as {ty}")
+    }
+}
+
+fn generate_field(out: &mut String, register: &Register, field: &Field, backing: &str) {
+    let name = to_snake_case(&field.name);
+    let ty = field_type(field.width);
+    let offset = field.offset;
+    let mask = if field.width >= 128 {
+        u128::MAX
+    } else {
+        (1_u128 << field.width) - 1
+    };
+    let access = field.access.unwrap_or(register.access);
+    let full = offset == 0 && field.width == register.size_bits && ty == backing;
+    let bits = if field.width == 1 {
+        format!("bit {offset}")
+    } else {
+        format!("bits {}..={}", offset + field.width - 1, offset)
+    };
+    let doc = match &field.description {
+        Some(description) => format!("`{}`: {description} ({bits})", field.name),
+        None => format!("The `{}` field ({bits}).", field.name),
+    };
+
+    if access.is_readable() {
+        write_doc(out, "        ", &doc);
+        out.push_str("        #[must_use]\n");
+        let _ = writeln!(out, "        pub const fn {name}(self) -> {ty} {{");
+        let _ = writeln!(out, "            {}", field_value(register, field, backing));
+        out.push_str("        }\n");
+    }
+
+    if access.is_writable() {
+        if access.is_readable() {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "        /// Sets the `{}` field ({bits}).", field.name);
+        out.push_str("        #[must_use]\n");
+        let _ = writeln!(
+            out,
+            "        pub const fn with_{}(self, value: {ty}) -> Self {{",
+            name.trim_end_matches('_')
+        );
+        let clear = format!("self.0 & !{}", shift_left(&format!("0x{mask:X}"), offset));
+        let value = if ty == "bool" {
+            format!("if value {{ 0x{:X} }} else {{ 0 }}", 1_u128 << offset)
+        } else if ty == backing {
+            shift_left(&format!("(value & 0x{mask:X})"), offset)
+        } else {
+            shift_left(&format!("((value as {backing}) & 0x{mask:X})"), offset)
+        };
+        if full {
+            let _ = writeln!(out, "            Self(value)");
+        } else {
+            let _ = writeln!(out, "            Self(({clear}) | {value})");
+        }
+        out.push_str("        }\n");
+    }
+}
+
+/// Renders `expr << offset`, omitting the shift for a zero offset.
+fn shift_left(expr: &str, offset: u32) -> String {
+    if offset == 0 {
+        expr.to_string()
+    } else {
+        format!("({expr} << {offset})")
+    }
+}
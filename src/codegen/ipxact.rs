@@ -0,0 +1,434 @@
+//! Import of IP-XACT (IEEE 1685) component descriptions.
+//!
+//! Both the 2009 (`spirit:`) and the 2014 (`ipxact:`) schema are accepted; elements are
+//! matched by their local name. Every memory map of the component becomes one [`Device`]
+//! named after the component, or after the component and the memory map if the component
+//! has several. Address blocks are flattened into their memory map, register files into
+//! their address block with the register file name as a prefix, and `dim` arrays are
+//! expanded into individual registers.
+//!
+//! Register sizes are taken from the register's `size`, falling back to the address block's
+//! `width`. Reset values are taken from the register's `reset` (IP-XACT 2009) or assembled
+//! from the fields' `resets` (IP-XACT 2014). The byte order is taken from the `endianness`
+//! of the bus interface referencing the memory map and defaults to little endian.
+//!
+//! ```
+//! use hardware_registers::codegen::{generate, ipxact, Interface};
+//!
+//! let xml = r#"
+//! <ipxact:component xmlns:ipxact="http://www.accellera.org/XMLSchema/IPXACT/1685-2014">
+//!   <ipxact:name>timer</ipxact:name>
+//!   <ipxact:memoryMaps>
+//!     <ipxact:memoryMap>
+//!       <ipxact:name>registers</ipxact:name>
+//!       <ipxact:addressBlock>
+//!         <ipxact:name>block</ipxact:name>
+//!         <ipxact:baseAddress>0</ipxact:baseAddress>
+//!         <ipxact:range>16</ipxact:range>
+//!         <ipxact:width>16</ipxact:width>
+//!         <ipxact:register>
+//!           <ipxact:name>COUNT</ipxact:name>
+//!           <ipxact:addressOffset>0x2</ipxact:addressOffset>
+//!           <ipxact:size>16</ipxact:size>
+//!         </ipxact:register>
+//!       </ipxact:addressBlock>
+//!     </ipxact:memoryMap>
+//!   </ipxact:memoryMaps>
+//! </ipxact:component>"#;
+//!
+//! let devices = ipxact::parse(xml, &[Interface::spi()]).unwrap();
+//! let code = generate(&devices).unwrap();
+//! assert!(code.contains("impl HardwareRegister<R2> for Count"));
+//! ```
+
+use crate::backing::ByteOrder;
+use crate::codegen::model::{AddressWidth, Device, Field, Interface, Register};
+use crate::codegen::xml::{
+    child, child_text, integer, normalize, parse_access, required_text, u32_value,
+};
+use crate::codegen::Error;
+use crate::map::Access;
+use roxmltree::{Document, Node};
+
+/// Register properties that are inherited from the address block and register file levels.
+#[derive(Debug, Copy, Clone)]
+struct Properties {
+    size: u32,
+    access: Access,
+}
+
+impl Properties {
+    fn inherit(self, node: Node<'_, '_>, size_element: &str) -> Result<Self, Error> {
+        Ok(Self {
+            size: match child_text(node, size_element) {
+                Some(size) => u32_value(size, size_element)?,
+                None => self.size,
+            },
+            access: match child_text(node, "access") {
+                Some(access) => parse_access(access)?,
+                None => self.access,
+            },
+        })
+    }
+}
+
+/// Parses an IP-XACT component into one [`Device`] per memory map.
+///
+/// The provided interfaces select the bus-specific register traits of the generated code.
+///
+/// # Errors
+/// Returns [`Error::Syntax`] if the file is not valid XML or lacks required elements, and
+/// [`Error::Unsupported`] if a register address exceeds 16 bits.
+pub fn parse(xml: &str, interfaces: &[Interface]) -> Result<Vec<Device>, Error> {
+    let document = Document::parse(xml).map_err(|e| Error::Syntax(e.to_string()))?;
+    let root = document.root_element();
+    if !root.has_tag_name("component") {
+        return Err(Error::Syntax("expected a <component> root element".into()));
+    }
+    let component = required_text(root, "name")?;
+    let description = child_text(root, "description").map(normalize);
+
+    let maps: Vec<Node<'_, '_>> = child(root, "memoryMaps")
+        .into_iter()
+        .flat_map(|maps| maps.children())
+        .filter(|n| n.has_tag_name("memoryMap"))
+        .collect();
+
+    let mut devices = Vec::new();
+    for map in &maps {
+        let name = if maps.len() == 1 {
+            component.to_string()
+        } else {
+            format!("{component}_{}", required_text(*map, "name")?)
+        };
+        let byte_order = match endianness(root, required_text(*map, "name")?) {
+            Some("big") => ByteOrder::BigEndian,
+            Some("little") | None => ByteOrder::LittleEndian,
+            Some(other) => return Err(Error::Syntax(format!("unknown endianness {other}"))),
+        };
+
+        let mut registers = Vec::new();
+        for block in map.children().filter(|n| n.has_tag_name("addressBlock")) {
+            let base = u32_value(required_text(block, "baseAddress")?, "baseAddress")?;
+            let properties = Properties {
+                size: 32,
+                access: Access::ReadWrite,
+            }
+            .inherit(block, "width")?;
+            collect_registers(block, "", base, properties, &mut registers)?;
+        }
+        registers.sort_by_key(|r| r.address);
+
+        let max_address = registers.iter().map(|r| r.address).max().unwrap_or(0);
+        let address_width = AddressWidth::fitting(max_address).ok_or_else(|| {
+            Error::Unsupported(format!("memory map {name} has addresses beyond 16 bits"))
+        })?;
+        devices.push(Device {
+            name,
+            description: child_text(*map, "description")
+                .map(normalize)
+                .or_else(|| description.clone()),
+            base_address: None,
+            address_width,
+            interfaces: interfaces.to_vec(),
+            byte_order,
+            registers,
+        });
+    }
+    Ok(devices)
+}
+
+/// Finds the `endianness` of the slave bus interface that references the named memory map.
+fn endianness<'a>(root: Node<'a, '_>, map: &str) -> Option<&'a str> {
+    child(root, "busInterfaces")?
+        .children()
+        .filter(|n| n.has_tag_name("busInterface"))
+        .find(|interface| {
+            child(*interface, "slave")
+                .and_then(|slave| child(slave, "memoryMapRef"))
+                .and_then(|reference| reference.attributes().find(|a| a.name() == "memoryMapRef"))
+                .map_or(false, |a| a.value() == map)
+        })
+        .and_then(|interface| child_text(interface, "endianness"))
+}
+
+/// Collects the registers of an `<addressBlock>` or `<registerFile>` element.
+fn collect_registers(
+    node: Node<'_, '_>,
+    prefix: &str,
+    offset: u32,
+    properties: Properties,
+    registers: &mut Vec<Register>,
+) -> Result<(), Error> {
+    for element in node.children().filter(Node::is_element) {
+        let is_file = element.has_tag_name("registerFile");
+        if !is_file && !element.has_tag_name("register") {
+            continue;
+        }
+        let properties = if is_file {
+            properties
+        } else {
+            properties.inherit(element, "size")?
+        };
+        let name = format!("{prefix}{}", required_text(element, "name")?);
+        let base = offset
+            .checked_add(u32_value(
+                required_text(element, "addressOffset")?,
+                "addressOffset",
+            )?)
+            .ok_or_else(|| exceeds_addresses(&name))?;
+        let stride = if is_file {
+            u32_value(required_text(element, "range")?, "range")?
+        } else {
+            properties.size / 8
+        };
+
+        let dim = child_text(element, "dim")
+            .map(|dim| u32_value(dim, "dim"))
+            .transpose()?;
+        let elements = match dim {
+            Some(count) => (0..count)
+                .map(|i| {
+                    let name = format!("{name}{i}");
+                    let address = stride
+                        .checked_mul(i)
+                        .and_then(|offset| base.checked_add(offset))
+                        .ok_or_else(|| exceeds_addresses(&name))?;
+                    Ok((name, address))
+                })
+                .collect::<Result<_, Error>>()?,
+            None => vec![(name, base)],
+        };
+
+        for (name, address) in elements {
+            if is_file {
+                collect_registers(element, &format!("{name}_"), address, properties, registers)?;
+            } else {
+                registers.push(register(element, name, address, properties)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn exceeds_addresses(name: &str) -> Error {
+    Error::Unsupported(format!("register {name} exceeds 32-bit addresses"))
+}
+
+fn register(
+    element: Node<'_, '_>,
+    name: String,
+    address: u32,
+    properties: Properties,
+) -> Result<Register, Error> {
+    let mut fields = Vec::new();
+    let mut field_reset: Option<u128> = None;
+    for field in element.children().filter(|n| n.has_tag_name("field")) {
+        let field_name = required_text(field, "name")?;
+        let offset = u32_value(required_text(field, "bitOffset")?, "bitOffset")?;
+        let width = u32_value(required_text(field, "bitWidth")?, "bitWidth")?;
+        if offset.checked_add(width).map_or(true, |end| end > 128) {
+            return Err(Error::Syntax(format!(
+                "field {name}.{field_name} exceeds 128 bits"
+            )));
+        }
+        let reset = child(field, "resets")
+            .and_then(|resets| child(resets, "reset"))
+            .and_then(|reset| child_text(reset, "value"));
+        if let Some(value) = reset {
+            let value = u128::from(integer(value, "value")?);
+            if value.checked_shr(width).map_or(false, |rest| rest != 0) {
+                return Err(Error::Syntax(format!(
+                    "reset value 0x{value:X} exceeds the {width}-bit field {name}.{field_name}"
+                )));
+            }
+            field_reset = Some(field_reset.unwrap_or(0) | (value << offset));
+        }
+        fields.push(Field {
+            name: field_name.to_string(),
+            description: child_text(field, "description").map(normalize),
+            offset,
+            width,
+            access: child_text(field, "access").map(parse_access).transpose()?,
+        });
+    }
+
+    let reset = match child(element, "reset").and_then(|reset| child_text(reset, "value")) {
+        Some(value) => Some(u128::from(integer(value, "value")?)),
+        None => field_reset,
+    };
+    Ok(Register {
+        name,
+        description: child_text(element, "description").map(normalize),
+        address,
+        size_bits: properties.size,
+        access: properties.access,
+        reset,
+        identity: Vec::new(),
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::generate;
+    use crate::codegen::tests::assert_golden;
+
+    const UART: &str = include_str!("../../tests/fixtures/ipxact/uart.xml");
+    const GPIO: &str = include_str!("../../tests/fixtures/ipxact/gpio.xml");
+
+    fn parse_fixture(xml: &str, interfaces: &[Interface]) -> Vec<Device> {
+        match parse(xml, interfaces) {
+            Ok(devices) => devices,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    #[test]
+    fn registers_2014() {
+        let devices = parse_fixture(UART, &[]);
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "uart");
+        assert_eq!(devices[0].byte_order, ByteOrder::LittleEndian);
+        let registers: Vec<_> = devices[0]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.address, r.size_bits, r.access, r.reset))
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                ("DATA", 0x00, 8, Access::ReadWrite, None),
+                ("STATUS", 0x01, 8, Access::ReadOnly, Some(0x02)),
+                ("BAUD", 0x02, 16, Access::ReadWrite, Some(0x0100)),
+                ("CTRL", 0x04, 32, Access::ReadWrite, Some(0x0000_0001)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fields_2014() {
+        let devices = parse_fixture(UART, &[]);
+        let fields: Vec<_> = devices[0].registers[3]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.width, f.access))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("EN", 0, 1, None),
+                ("PARITY", 1, 2, None),
+                ("BUSY", 31, 1, Some(Access::ReadOnly)),
+            ]
+        );
+    }
+
+    #[test]
+    fn registers_2009() {
+        let devices = parse_fixture(GPIO, &[]);
+        let names: Vec<_> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["gpio_control", "gpio_pins"]);
+        assert_eq!(devices[0].byte_order, ByteOrder::BigEndian);
+        let registers: Vec<_> = devices[1]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.address, r.size_bits, r.reset))
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                ("port0_DIR", 0x10, 16, Some(0xFFFF)),
+                ("port0_OUT", 0x12, 16, Some(0)),
+                ("port1_DIR", 0x14, 16, Some(0xFFFF)),
+                ("port1_OUT", 0x16, 16, Some(0)),
+                ("IN0", 0x20, 8, None),
+                ("IN1", 0x21, 8, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn golden() {
+        let uart = parse_fixture(UART, &[Interface::i2c(0x2A)]);
+        match generate(&uart) {
+            Ok(code) => assert_golden("tests/fixtures/ipxact/uart.rs", &code),
+            Err(error) => panic!("{error}"),
+        }
+        let gpio = parse_fixture(GPIO, &[Interface::spi()]);
+        match generate(&gpio) {
+            Ok(code) => assert_golden("tests/fixtures/ipxact/gpio.rs", &code),
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    #[test]
+    fn rejects_address_overflow() {
+        let file = "<component><name>x</name><memoryMaps><memoryMap><name>m</name>\
+                    <addressBlock><baseAddress>0xFFFFFFFF</baseAddress>\
+                    <registerFile><name>F</name><addressOffset>0x10</addressOffset>\
+                    <range>4</range></registerFile>\
+                    </addressBlock></memoryMap></memoryMaps></component>";
+        assert_eq!(
+            parse(file, &[]),
+            Err(Error::Unsupported(
+                "register F exceeds 32-bit addresses".into()
+            ))
+        );
+        let dim = "<component><name>x</name><memoryMaps><memoryMap><name>m</name>\
+                   <addressBlock><baseAddress>0</baseAddress>\
+                   <register><name>R</name><addressOffset>0xFFFFFFFE</addressOffset>\
+                   <dim>2</dim><size>32</size></register>\
+                   </addressBlock></memoryMap></memoryMaps></component>";
+        assert_eq!(
+            parse(dim, &[]),
+            Err(Error::Unsupported(
+                "register R1 exceeds 32-bit addresses".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_fields() {
+        let register = |field: &str| {
+            format!(
+                "<component><name>x</name><memoryMaps><memoryMap><name>m</name>\
+                 <addressBlock><baseAddress>0</baseAddress>\
+                 <register><name>R</name><addressOffset>0</addressOffset><size>32</size>\
+                 <field><name>A</name><bitOffset>0</bitOffset><bitWidth>4</bitWidth></field>\
+                 {field}</register></addressBlock></memoryMap></memoryMaps></component>"
+            )
+        };
+        let reset = register(
+            "<field><name>B</name><bitOffset>4</bitOffset><bitWidth>4</bitWidth>\
+             <resets><reset><value>0x1F</value></reset></resets></field>",
+        );
+        assert_eq!(
+            parse(&reset, &[]),
+            Err(Error::Syntax(
+                "reset value 0x1F exceeds the 4-bit field R.B".into()
+            ))
+        );
+        let offset = register(
+            "<field><name>B</name><bitOffset>128</bitOffset><bitWidth>1</bitWidth></field>",
+        );
+        assert_eq!(
+            parse(&offset, &[]),
+            Err(Error::Syntax("field R.B exceeds 128 bits".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_elements() {
+        assert!(matches!(
+            parse("<component><name>x</name></component>", &[]),
+            Ok(devices) if devices.is_empty()
+        ));
+        assert!(matches!(parse("<device/>", &[]), Err(Error::Syntax(_))));
+        let xml = "<component><name>x</name><memoryMaps><memoryMap><name>m</name>\
+                   <addressBlock><register><name>R</name></register></addressBlock>\
+                   </memoryMap></memoryMaps></component>";
+        assert!(matches!(parse(xml, &[]), Err(Error::Syntax(_))));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::backing::ByteOrder;
+use crate::map::Access;
+
+/// The width of a device's register addresses.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AddressWidth {
+    /// Registers are addressed by a [`RegisterAddress8`](crate::register_address::RegisterAddress8).
+    Bits8,
+    /// Registers are addressed by a [`RegisterAddress16`](crate::register_address::RegisterAddress16).
+    Bits16,
+}
+
+impl AddressWidth {
+    /// Selects the narrowest width that can represent the specified address.
+    #[must_use]
+    pub fn fitting(address: u32) -> Option<Self> {
+        if address <= 0xFF {
+            Some(Self::Bits8)
+        } else if address <= 0xFFFF {
+            Some(Self::Bits16)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the largest address representable with this width.
+    #[must_use]
+    pub fn max_address(self) -> u32 {
+        match self {
+            Self::Bits8 => 0xFF,
+            Self::Bits16 => 0xFFFF,
+        }
+    }
+}
+
+/// How the device advances the register address during multi-byte transfers.
+///
+/// Mirrors [`AddressingMode`](crate::addressing::AddressingMode).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Addressing {
+    /// The address is incremented after every byte.
+    #[default]
+    AutoIncrement,
+    /// The address wraps around at the end of each aligned block of `block` registers.
+    Wrap {
+        /// The number of registers in a block.
+        block: u16,
+    },
+    /// The address is not incremented.
+    NoIncrement,
+    /// The address is only incremented if the `flag` bits are set in the first address byte.
+    IncrementFlag {
+        /// The bits to set in the first address byte.
+        flag: u8,
+    },
+}
+
+/// A bus interface the generated registers are accessed through.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Interface {
+    /// Registers implement [`I2CRegister`](crate::i2c::I2CRegister).
+    I2c {
+        /// The factory-default device address.
+        address: u16,
+        /// Further addresses the device can be strapped to, e.g. through an address pin.
+        alternate_addresses: Vec<u16>,
+        /// Whether the device uses 10-bit addressing.
+        ten_bit: bool,
+        /// How the device advances the register address.
+        addressing: Addressing,
+    },
+    /// Registers implement [`SPIRegister`](crate::spi::SPIRegister).
+    Spi {
+        /// The bit set in the first address byte to mark a read, if it differs from
+        /// [`SPIDevice::DEFAULT_READ_FLAG`](crate::spi::SPIDevice::DEFAULT_READ_FLAG).
+        read_flag: Option<u8>,
+        /// How the device advances the register address.
+        addressing: Addressing,
+    },
+}
+
+impl Interface {
+    /// An I²C interface with a single 7-bit device address.
+    #[must_use]
+    pub fn i2c(address: u16) -> Self {
+        Self::I2c {
+            address,
+            alternate_addresses: Vec::new(),
+            ten_bit: false,
+            addressing: Addressing::AutoIncrement,
+        }
+    }
+
+    /// An SPI interface using the default read flag.
+    #[must_use]
+    pub fn spi() -> Self {
+        Self::Spi {
+            read_flag: None,
+            addressing: Addressing::AutoIncrement,
+        }
+    }
+}
+
+/// A device (or peripheral) description to generate register types for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Device {
+    /// The device name.
+    pub name: String,
+    /// An optional description.
+    pub description: Option<String>,
+    /// The base address of a memory-mapped peripheral, if any.
+    pub base_address: Option<u64>,
+    /// The width of the register addresses.
+    pub address_width: AddressWidth,
+    /// The bus interfaces; no bus-specific traits are generated if empty.
+    pub interfaces: Vec<Interface>,
+    /// The byte order of multi-byte registers.
+    pub byte_order: ByteOrder,
+    /// The registers, in address order.
+    pub registers: Vec<Register>,
+}
+
+/// A register description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Register {
+    /// The register name.
+    pub name: String,
+    /// An optional description.
+    pub description: Option<String>,
+    /// The register address (or address offset of a memory-mapped peripheral).
+    pub address: u32,
+    /// The register size in bits; one of 8, 16, 24, 32, 64, 128, 256 or 512.
+    ///
+    /// Registers of 256 and 512 bits are backed by byte arrays and cannot have fields
+    /// or reset values.
+    pub size_bits: u32,
+    /// The access policy.
+    pub access: Access,
+    /// The power-on reset value.
+    pub reset: Option<u128>,
+    /// The values identifying the device, if this is an identity register such as `WHO_AM_I`.
+    pub identity: Vec<u128>,
+    /// The bit fields, in any order.
+    pub fields: Vec<Field>,
+}
+
+/// A bit field within a register.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// The field name.
+    pub name: String,
+    /// An optional description.
+    pub description: Option<String>,
+    /// The offset of the least significant bit.
+    pub offset: u32,
+    /// The width in bits.
+    pub width: u32,
+    /// The access policy, if different from the register's.
+    pub access: Option<Access>,
+}
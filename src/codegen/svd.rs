@@ -0,0 +1,464 @@
+//! Import of CMSIS-SVD peripheral descriptions.
+//!
+//! Every peripheral of the SVD file becomes one [`Device`]. Register offsets are used as
+//! register addresses; the peripheral's base address is retained in [`Device::base_address`].
+//! Clusters are flattened into their parent peripheral with the cluster name as a prefix,
+//! and `dim` arrays are expanded into individual registers.
+//!
+//! ```
+//! use hardware_registers::codegen::{generate, svd, Interface};
+//!
+//! let xml = r#"
+//! <device>
+//!   <name>EXAMPLE</name>
+//!   <peripherals>
+//!     <peripheral>
+//!       <name>TIMER</name>
+//!       <baseAddress>0x40000000</baseAddress>
+//!       <registers>
+//!         <register>
+//!           <name>CTRL</name>
+//!           <addressOffset>0x00</addressOffset>
+//!           <size>8</size>
+//!           <resetValue>0x01</resetValue>
+//!         </register>
+//!       </registers>
+//!     </peripheral>
+//!   </peripherals>
+//! </device>"#;
+//!
+//! let devices = svd::parse(xml, &[Interface::spi()]).unwrap();
+//! let code = generate(&devices).unwrap();
+//! assert!(code.contains("pub struct Ctrl(pub u8);"));
+//! ```
+
+use crate::backing::ByteOrder;
+use crate::codegen::model::{AddressWidth, Device, Field, Interface, Register};
+use crate::codegen::xml::{
+    child, child_text, integer, normalize, parse_access, required_text, u32_value,
+};
+use crate::codegen::Error;
+use crate::map::Access;
+use roxmltree::{Document, Node};
+
+/// Register properties that are inherited from the device, peripheral and cluster levels.
+#[derive(Debug, Copy, Clone)]
+struct Properties {
+    size: u32,
+    access: Access,
+    reset: Option<u128>,
+    reset_mask: u128,
+}
+
+impl Properties {
+    fn inherit(self, node: Node<'_, '_>) -> Result<Self, Error> {
+        Ok(Self {
+            size: match child_text(node, "size") {
+                Some(size) => integer(size, "size")?.try_into().map_err(|_| {
+                    Error::Unsupported(format!("register size {size} is too large"))
+                })?,
+                None => self.size,
+            },
+            access: match child_text(node, "access") {
+                Some(access) => parse_access(access)?,
+                None => self.access,
+            },
+            reset: match child_text(node, "resetValue") {
+                Some(reset) => Some(integer(reset, "resetValue")?.into()),
+                None => self.reset,
+            },
+            reset_mask: match child_text(node, "resetMask") {
+                Some(mask) => integer(mask, "resetMask")?.into(),
+                None => self.reset_mask,
+            },
+        })
+    }
+
+    /// Returns the reset value of a register with these properties.
+    ///
+    /// The `resetMask` is applied; an inherited reset value is also truncated to the
+    /// register size, whereas an explicit one is kept for the generator to reject.
+    fn register_reset(self, inherited: bool) -> Option<u128> {
+        let mask = match self.size {
+            0..=127 if inherited => self.reset_mask & ((1 << self.size) - 1),
+            _ => self.reset_mask,
+        };
+        self.reset.map(|reset| reset & mask)
+    }
+}
+
+/// Parses a CMSIS-SVD file into one [`Device`] per peripheral.
+///
+/// The provided interfaces select the bus-specific register traits of the generated code.
+///
+/// # Errors
+/// Returns [`Error::Syntax`] if the file is not valid XML or lacks required elements, and
+/// [`Error::Unsupported`] if a register offset exceeds 16 bits.
+pub fn parse(xml: &str, interfaces: &[Interface]) -> Result<Vec<Device>, Error> {
+    let document = Document::parse(xml).map_err(|e| Error::Syntax(e.to_string()))?;
+    let root = document.root_element();
+    if !root.has_tag_name("device") {
+        return Err(Error::Syntax("expected a <device> root element".into()));
+    }
+
+    let byte_order = match child(root, "cpu").and_then(|cpu| child_text(cpu, "endian")) {
+        Some("big") => ByteOrder::BigEndian,
+        _ => ByteOrder::LittleEndian,
+    };
+    let defaults = Properties {
+        size: 32,
+        access: Access::ReadWrite,
+        reset: None,
+        reset_mask: u128::MAX,
+    }
+    .inherit(root)?;
+
+    let mut devices: Vec<Device> = Vec::new();
+    let peripherals = child(root, "peripherals")
+        .into_iter()
+        .flat_map(|p| p.children());
+    for peripheral in peripherals.filter(|n| n.has_tag_name("peripheral")) {
+        let name = required_text(peripheral, "name")?.to_string();
+        let base = child_text(peripheral, "baseAddress")
+            .map(|b| integer(b, "baseAddress"))
+            .transpose()?;
+
+        let mut registers = match peripheral.attribute("derivedFrom") {
+            Some(parent) => devices
+                .iter()
+                .find(|d| d.name == parent)
+                .map(|d| d.registers.clone())
+                .ok_or_else(|| Error::Syntax(format!("unknown peripheral {parent}")))?,
+            None => Vec::new(),
+        };
+        if let Some(node) = child(peripheral, "registers") {
+            let properties = defaults.inherit(peripheral)?;
+            collect_registers(node, "", 0, properties, &mut registers)?;
+        }
+        registers.sort_by_key(|r| r.address);
+
+        let max_address = registers.iter().map(|r| r.address).max().unwrap_or(0);
+        let address_width = AddressWidth::fitting(max_address).ok_or_else(|| {
+            Error::Unsupported(format!(
+                "peripheral {name} has register offsets beyond 16 bits"
+            ))
+        })?;
+
+        devices.push(Device {
+            description: child_text(peripheral, "description").map(normalize),
+            name,
+            base_address: base,
+            address_width,
+            interfaces: interfaces.to_vec(),
+            byte_order,
+            registers,
+        });
+    }
+    Ok(devices)
+}
+
+/// Collects the registers of a `<registers>` or `<cluster>` element.
+fn collect_registers(
+    node: Node<'_, '_>,
+    prefix: &str,
+    offset: u32,
+    properties: Properties,
+    registers: &mut Vec<Register>,
+) -> Result<(), Error> {
+    for element in node.children().filter(Node::is_element) {
+        let is_cluster = element.has_tag_name("cluster");
+        if !is_cluster && !element.has_tag_name("register") {
+            continue;
+        }
+        let properties = properties.inherit(element)?;
+        let name = required_text(element, "name")?;
+        let base = offset
+            .checked_add(u32_value(
+                required_text(element, "addressOffset")?,
+                "addressOffset",
+            )?)
+            .ok_or_else(|| exceeds_addresses(&format!("{prefix}{name}")))?;
+        for (name, address) in expand_dim(element, name, base)? {
+            let name = format!("{prefix}{name}");
+            if is_cluster {
+                collect_registers(element, &format!("{name}_"), address, properties, registers)?;
+                continue;
+            }
+            let description = child_text(element, "description").map(normalize);
+            let fields = match child(element, "fields") {
+                Some(fields) => parse_fields(fields)?,
+                None => Vec::new(),
+            };
+            registers.push(Register {
+                name,
+                description,
+                address,
+                size_bits: properties.size,
+                access: properties.access,
+                reset: properties.register_reset(child(element, "resetValue").is_none()),
+                identity: Vec::new(),
+                fields,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Expands a `dim` array into names and addresses, or returns the single element.
+fn expand_dim(node: Node<'_, '_>, name: &str, address: u32) -> Result<Vec<(String, u32)>, Error> {
+    let dim = match child_text(node, "dim") {
+        Some(dim) => u32_value(dim, "dim")?,
+        None => return Ok(vec![(name.to_string(), address)]),
+    };
+    let increment = u32_value(required_text(node, "dimIncrement")?, "dimIncrement")?;
+    let indices: Vec<String> = match child_text(node, "dimIndex") {
+        Some(list) if list.contains('-') && !list.contains(',') => {
+            let (start, end) = list.split_once('-').unwrap_or_default();
+            let (start, end) = (u32_value(start, "dimIndex")?, u32_value(end, "dimIndex")?);
+            (start..=end).map(|i| i.to_string()).collect()
+        }
+        Some(list) => list.split(',').map(|s| s.trim().to_string()).collect(),
+        None => (0..dim).map(|i| i.to_string()).collect(),
+    };
+    if u32::try_from(indices.len()) != Ok(dim) {
+        return Err(Error::Syntax(format!(
+            "dimIndex of {} lists {} indices for a dim of {dim}",
+            name.replace("[%s]", "").replace("%s", ""),
+            indices.len()
+        )));
+    }
+    indices
+        .iter()
+        .zip(0..)
+        .map(|(index, i)| {
+            let name = name.replace("[%s]", index).replace("%s", index);
+            let address = increment
+                .checked_mul(i)
+                .and_then(|offset| address.checked_add(offset))
+                .ok_or_else(|| exceeds_addresses(&name))?;
+            Ok((name, address))
+        })
+        .collect()
+}
+
+fn exceeds_addresses(name: &str) -> Error {
+    Error::Unsupported(format!("register {name} exceeds 32-bit addresses"))
+}
+
+fn parse_fields(node: Node<'_, '_>) -> Result<Vec<Field>, Error> {
+    let mut fields = Vec::new();
+    for field in node.children().filter(|n| n.has_tag_name("field")) {
+        let name = required_text(field, "name")?;
+        let span = |msb: u32, lsb: u32| match msb.checked_sub(lsb) {
+            Some(span) => Ok((lsb, span + 1)),
+            None => Err(Error::Syntax(format!(
+                "field {name} has msb {msb} below lsb {lsb}"
+            ))),
+        };
+        let (offset, width) = if let Some(offset) = child_text(field, "bitOffset") {
+            let width = child_text(field, "bitWidth").unwrap_or("1");
+            (
+                u32_value(offset, "bitOffset")?,
+                u32_value(width, "bitWidth")?,
+            )
+        } else if let Some(lsb) = child_text(field, "lsb") {
+            let lsb = u32_value(lsb, "lsb")?;
+            let msb = u32_value(required_text(field, "msb")?, "msb")?;
+            span(msb, lsb)?
+        } else if let Some(range) = child_text(field, "bitRange") {
+            let range = range.trim().trim_start_matches('[').trim_end_matches(']');
+            let (msb, lsb) = range
+                .split_once(':')
+                .ok_or_else(|| Error::Syntax(format!("invalid bitRange {range}")))?;
+            let (msb, lsb) = (u32_value(msb, "bitRange")?, u32_value(lsb, "bitRange")?);
+            span(msb, lsb)?
+        } else {
+            return Err(Error::Syntax("field without bit position".into()));
+        };
+        fields.push(Field {
+            name: name.to_string(),
+            description: child_text(field, "description").map(normalize),
+            offset,
+            width,
+            access: child_text(field, "access").map(parse_access).transpose()?,
+        });
+    }
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::generate;
+    use crate::codegen::tests::assert_golden;
+
+    const SVD: &str = include_str!("../../tests/fixtures/svd/example.svd");
+
+    fn devices() -> Vec<Device> {
+        match parse(SVD, &[Interface::spi()]) {
+            Ok(devices) => devices,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    #[test]
+    fn peripherals() {
+        let devices = devices();
+        let names: Vec<_> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["UART0", "UART1"]);
+        assert_eq!(devices[1].base_address, Some(0x4000_2000));
+        assert_eq!(devices[0].registers, devices[1].registers);
+        assert_eq!(devices[0].byte_order, ByteOrder::LittleEndian);
+    }
+
+    #[test]
+    fn registers() {
+        let devices = devices();
+        let registers: Vec<_> = devices[0]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.address, r.size_bits, r.access, r.reset))
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                ("CTRL", 0x00, 32, Access::ReadWrite, Some(0x0001_0000)),
+                ("STATUS", 0x04, 8, Access::ReadOnly, Some(0x00)),
+                ("FIFO_DATA0", 0x10, 8, Access::ReadWrite, None),
+                ("FIFO_DATA1", 0x11, 8, Access::ReadWrite, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let devices = devices();
+        let fields: Vec<_> = devices[0].registers[0]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.width))
+            .collect();
+        assert_eq!(fields, [("EN", 0, 1), ("MODE", 1, 2), ("BAUD", 16, 16)]);
+    }
+
+    #[test]
+    fn golden() {
+        let code = match generate(&devices()) {
+            Ok(code) => code,
+            Err(error) => panic!("{error}"),
+        };
+        assert_golden("tests/fixtures/svd/example.rs", &code);
+    }
+
+    #[test]
+    fn reset_values() {
+        let devices = match parse(include_str!("../../tests/fixtures/svd/reset.svd"), &[]) {
+            Ok(devices) => devices,
+            Err(error) => panic!("{error}"),
+        };
+        let resets: Vec<_> = devices[0]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.reset))
+            .collect();
+        assert_eq!(
+            resets,
+            [
+                ("WIDE", Some(0x00FF_FFFF)),
+                ("NARROW", Some(0xFF)),
+                ("MASKED", Some(0x34)),
+            ]
+        );
+        assert!(generate(&devices[..1]).is_ok());
+        assert_eq!(devices[1].registers[0].reset, Some(0x1FF));
+        assert!(matches!(
+            generate(&devices[1..]),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_address_overflow() {
+        let cluster = r"
+            <device>
+              <peripherals>
+                <peripheral>
+                  <name>P</name>
+                  <registers>
+                    <cluster>
+                      <name>C</name>
+                      <addressOffset>0xFFFFFFFF</addressOffset>
+                      <register>
+                        <name>R</name>
+                        <addressOffset>0x10</addressOffset>
+                      </register>
+                    </cluster>
+                  </registers>
+                </peripheral>
+              </peripherals>
+            </device>";
+        assert_eq!(
+            parse(cluster, &[]),
+            Err(Error::Unsupported(
+                "register C_R exceeds 32-bit addresses".into()
+            ))
+        );
+        let dim = r"
+            <device>
+              <peripherals>
+                <peripheral>
+                  <name>P</name>
+                  <registers>
+                    <register>
+                      <dim>2</dim>
+                      <dimIncrement>0x80000000</dimIncrement>
+                      <name>R%s</name>
+                      <addressOffset>0x80000000</addressOffset>
+                    </register>
+                  </registers>
+                </peripheral>
+              </peripherals>
+            </device>";
+        assert_eq!(
+            parse(dim, &[]),
+            Err(Error::Unsupported(
+                "register R1 exceeds 32-bit addresses".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_positions() {
+        let svd = |register: &str| {
+            format!(
+                "<device><peripherals><peripheral><name>P</name><registers>\
+                 {register}</registers></peripheral></peripherals></device>"
+            )
+        };
+        let inverted = svd("<register><name>R</name><addressOffset>0</addressOffset>\
+             <fields><field><name>F</name><bitRange>[3:4]</bitRange></field></fields>\
+             </register>");
+        assert_eq!(
+            parse(&inverted, &[]),
+            Err(Error::Syntax("field F has msb 3 below lsb 4".into()))
+        );
+        let inverted = svd("<register><name>R</name><addressOffset>0</addressOffset>\
+             <fields><field><name>F</name><lsb>2</lsb><msb>1</msb></field></fields>\
+             </register>");
+        assert!(matches!(parse(&inverted, &[]), Err(Error::Syntax(_))));
+        let indices = svd("<register><dim>3</dim><dimIncrement>4</dimIncrement>\
+             <dimIndex>A,B</dimIndex><name>R%s</name><addressOffset>0</addressOffset>\
+             </register>");
+        assert_eq!(
+            parse(&indices, &[]),
+            Err(Error::Syntax(
+                "dimIndex of R lists 2 indices for a dim of 3".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_xml() {
+        assert!(matches!(parse("<device>", &[]), Err(Error::Syntax(_))));
+    }
+}
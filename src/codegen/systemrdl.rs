@@ -0,0 +1,861 @@
+//! Import of SystemRDL register descriptions.
+//!
+//! A practical subset of SystemRDL 2.0 is supported:
+//!
+//! - `addrmap`, `regfile`, `reg` and `field` components, both as anonymous definitions
+//!   instantiated in place and as named definitions instantiated by type name,
+//! - instance addresses (`@`), array instances (`[N]`) with strides (`+=`) and alignment (`%=`),
+//! - field positions given as `[msb:lsb]` or widths given as `[N]`, and field resets (`= value`),
+//! - the `name`, `desc`, `regwidth`, `fieldwidth`, `sw`, `hw`, `reset`, `littleendian` and
+//!   `bigendian` properties, including `default` property assignments.
+//!
+//! Every `addrmap` that is not instantiated by another one becomes a [`Device`]. Register
+//! files and nested address maps are flattened into it with their instance name as a prefix.
+//! The register access follows the software (`sw`) access of its fields; fields with
+//! `sw = na` are omitted. The hardware (`hw`) access is validated but only concerns the
+//! FPGA side. Other properties are accepted and ignored.
+//!
+//! ```
+//! use hardware_registers::codegen::{generate, systemrdl, Interface};
+//!
+//! let rdl = r#"
+//!     addrmap timer {
+//!         reg {
+//!             regwidth = 8;
+//!             field { sw = rw; hw = r; } EN[0:0] = 1;
+//!             field { sw = r; hw = w; } RUNNING[1:1] = 0;
+//!         } CTRL @ 0x00;
+//!     };
+//! "#;
+//!
+//! let devices = systemrdl::parse(rdl, &[Interface::spi()]).unwrap();
+//! let code = generate(&devices).unwrap();
+//! assert!(code.contains("pub struct Ctrl(pub u8);"));
+//! ```
+
+use crate::backing::ByteOrder;
+use crate::codegen::model::{AddressWidth, Device, Field, Interface, Register};
+use crate::codegen::{parse_integer, Error};
+use crate::map::Access;
+use std::collections::{HashMap, HashSet};
+
+/// A lexical token.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(u128),
+    Str(String),
+    Punct(&'static str),
+}
+
+const PUNCTUATION: &[&str] = &[
+    "+=", "%=", "->", "{", "}", "[", "]", ";", ":", "=", "@", ",", ".",
+];
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, Error> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '\n' {
+            line += 1;
+            rest = &rest[1..];
+        } else if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let end = comment
+                .find("*/")
+                .ok_or_else(|| syntax(line, "unterminated comment"))?;
+            line += comment[..end].matches('\n').count();
+            rest = &comment[end + 2..];
+        } else if c == '"' {
+            let (value, length) = string_literal(&rest[1..], line)?;
+            let lines = value.matches('\n').count();
+            tokens.push((Token::Str(value), line));
+            line += lines;
+            rest = &rest[1 + length..];
+        } else if c.is_ascii_digit() || c == '\'' {
+            let length = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '\''))
+                .unwrap_or(rest.len());
+            let number = parse_integer(&rest[..length])
+                .ok_or_else(|| syntax(line, &format!("invalid number {}", &rest[..length])))?;
+            tokens.push((Token::Number(number), line));
+            rest = &rest[length..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let length = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push((Token::Ident(rest[..length].to_string()), line));
+            rest = &rest[length..];
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+            tokens.push((Token::Punct(punct), line));
+            rest = &rest[punct.len()..];
+        } else {
+            return Err(syntax(line, &format!("unexpected character {c:?}")));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads a string literal after its opening quote, returning the value and consumed length.
+fn string_literal(text: &str, line: usize) -> Result<(String, usize), Error> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, index + 1)),
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    Err(syntax(line, "unterminated string"))
+}
+
+fn syntax(line: usize, message: &str) -> Error {
+    Error::Syntax(format!("line {line}: {message}"))
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Kind {
+    AddrMap,
+    RegFile,
+    Reg,
+    Field,
+}
+
+impl Kind {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "addrmap" => Some(Self::AddrMap),
+            "regfile" => Some(Self::RegFile),
+            "reg" => Some(Self::Reg),
+            "field" => Some(Self::Field),
+            _ => None,
+        }
+    }
+
+    fn may_contain(self, child: Self) -> bool {
+        match self {
+            Self::AddrMap => child != Self::Field,
+            Self::RegFile => matches!(child, Self::RegFile | Self::Reg),
+            Self::Reg => child == Self::Field,
+            Self::Field => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(u128),
+    Str(String),
+    Ident(String),
+    Bool(bool),
+}
+
+#[derive(Debug)]
+struct Component {
+    kind: Kind,
+    properties: HashMap<String, Value>,
+    instances: Vec<Instance>,
+}
+
+impl Component {
+    fn number(&self, name: &str) -> Option<u128> {
+        match self.properties.get(name) {
+            Some(Value::Number(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn text(&self, name: &str) -> Option<&str> {
+        match self.properties.get(name) {
+            Some(Value::Str(value) | Value::Ident(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        matches!(self.properties.get(name), Some(Value::Bool(true)))
+    }
+
+    /// The `desc` property, falling back to the `name` property.
+    fn description(&self) -> Option<String> {
+        self.text("desc")
+            .or_else(|| self.text("name"))
+            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// Either an array size or, for fields, a width or bit range.
+#[derive(Debug, Copy, Clone)]
+enum Dim {
+    Count(u128),
+    Bits { msb: u128, lsb: u128 },
+}
+
+#[derive(Debug)]
+struct Instance {
+    component: usize,
+    name: String,
+    dim: Option<Dim>,
+    address: Option<u128>,
+    stride: Option<u128>,
+    align: Option<u128>,
+    reset: Option<u128>,
+    line: usize,
+}
+
+/// The named definitions and `default` properties of one lexical scope.
+#[derive(Debug, Default)]
+struct Scope {
+    types: HashMap<String, usize>,
+    defaults: HashMap<String, Value>,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    components: Vec<Component>,
+    scopes: Vec<Scope>,
+    roots: Vec<(String, usize)>,
+    instantiated: HashSet<usize>,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn error(&self, message: &str) -> Error {
+        syntax(self.line(), message)
+    }
+
+    fn accept(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), Error> {
+        if self.accept(punct) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{punct}`")))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        if let Some(Token::Ident(ident)) = self.next() {
+            Ok(ident)
+        } else {
+            self.position -= 1;
+            Err(self.error("expected an identifier"))
+        }
+    }
+
+    fn number(&mut self) -> Result<u128, Error> {
+        if let Some(Token::Number(number)) = self.next() {
+            Ok(number)
+        } else {
+            self.position -= 1;
+            Err(self.error("expected a number"))
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.types.get(name).copied())
+    }
+
+    /// Parses the statements of the root scope or of a component body.
+    fn statements(&mut self, parent: Option<usize>) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                None if parent.is_none() => return Ok(()),
+                None => return Err(self.error("unexpected end of input")),
+                Some(Token::Punct("}")) if parent.is_some() => return Ok(()),
+                Some(Token::Ident(_)) => self.statement(parent)?,
+                Some(_) => return Err(self.error("expected a component or property")),
+            }
+        }
+    }
+
+    fn statement(&mut self, parent: Option<usize>) -> Result<(), Error> {
+        let keyword = self.ident()?;
+        if let Some(kind) = Kind::from_keyword(&keyword) {
+            return self.definition(parent, kind);
+        }
+        match keyword.as_str() {
+            "external" | "internal" => self.statement(parent),
+            "default" => {
+                let (name, value) = self.assignment()?;
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.defaults.insert(name, value);
+                }
+                Ok(())
+            }
+            "enum" | "signal" | "mem" | "property" | "constraint" => Err(Error::Unsupported(
+                format!("line {}: `{keyword}` is not supported", self.line()),
+            )),
+            _ => match (parent, self.peek()) {
+                (Some(parent), Some(Token::Punct("=" | ";"))) => {
+                    self.position -= 1;
+                    let (name, value) = self.assignment()?;
+                    self.components[parent].properties.insert(name, value);
+                    Ok(())
+                }
+                (Some(parent), Some(Token::Ident(_))) => {
+                    let component = self
+                        .lookup(&keyword)
+                        .ok_or_else(|| self.error(&format!("unknown component type {keyword}")))?;
+                    self.instances(parent, component)
+                }
+                (_, Some(Token::Punct("->" | "."))) => Err(Error::Unsupported(format!(
+                    "line {}: property references are not supported",
+                    self.line()
+                ))),
+                _ => Err(self.error(&format!("unexpected `{keyword}`"))),
+            },
+        }
+    }
+
+    /// Parses `name = value;` or `name;`.
+    fn assignment(&mut self) -> Result<(String, Value), Error> {
+        let name = self.ident()?;
+        if self.accept(";") {
+            return Ok((name, Value::Bool(true)));
+        }
+        self.expect("=")?;
+        let value = match self.next() {
+            Some(Token::Number(number)) => Value::Number(number),
+            Some(Token::Str(text)) => Value::Str(text),
+            Some(Token::Ident(ident)) if ident == "true" => Value::Bool(true),
+            Some(Token::Ident(ident)) if ident == "false" => Value::Bool(false),
+            Some(Token::Ident(ident)) => Value::Ident(ident),
+            _ => {
+                self.position -= 1;
+                return Err(self.error(&format!("expected a value for {name}")));
+            }
+        };
+        if matches!(self.peek(), Some(Token::Punct("->" | "."))) {
+            return Err(Error::Unsupported(format!(
+                "line {}: property references are not supported",
+                self.line()
+            )));
+        }
+        self.expect(";")?;
+        Ok((name, value))
+    }
+
+    fn definition(&mut self, parent: Option<usize>, kind: Kind) -> Result<(), Error> {
+        match parent {
+            Some(parent) if !self.components[parent].kind.may_contain(kind) => {
+                return Err(self.error(&format!("{kind:?} cannot be nested here")));
+            }
+            _ => {}
+        }
+        let type_name = match self.peek() {
+            Some(Token::Ident(_)) => Some(self.ident()?),
+            _ => None,
+        };
+        self.expect("{")?;
+
+        let mut properties = HashMap::new();
+        for scope in &self.scopes {
+            properties.extend(scope.defaults.clone());
+        }
+        let index = self.components.len();
+        self.components.push(Component {
+            kind,
+            properties,
+            instances: Vec::new(),
+        });
+        self.scopes.push(Scope::default());
+        self.statements(Some(index))?;
+        self.scopes.pop();
+        self.expect("}")?;
+
+        if let Some(name) = type_name {
+            if parent.is_none() && kind == Kind::AddrMap {
+                self.roots.push((name.clone(), index));
+            }
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.types.insert(name, index);
+            }
+        }
+        match parent {
+            Some(parent) if !self.accept(";") => self.instances(parent, index),
+            _ => self.expect(";"),
+        }
+    }
+
+    /// Parses a comma-separated list of instances followed by `;`.
+    fn instances(&mut self, parent: usize, component: usize) -> Result<(), Error> {
+        if !self.components[parent]
+            .kind
+            .may_contain(self.components[component].kind)
+        {
+            return Err(self.error("component cannot be instantiated here"));
+        }
+        self.instantiated.insert(component);
+        loop {
+            let line = self.line();
+            let name = self.ident()?;
+            let dim = if self.accept("[") {
+                let first = self.number()?;
+                let dim = if self.accept(":") {
+                    Dim::Bits {
+                        msb: first,
+                        lsb: self.number()?,
+                    }
+                } else {
+                    Dim::Count(first)
+                };
+                self.expect("]")?;
+                Some(dim)
+            } else {
+                None
+            };
+            let mut instance = Instance {
+                component,
+                name,
+                dim,
+                address: None,
+                stride: None,
+                align: None,
+                reset: None,
+                line,
+            };
+            loop {
+                if self.accept("=") {
+                    instance.reset = Some(self.number()?);
+                } else if self.accept("@") {
+                    instance.address = Some(self.number()?);
+                } else if self.accept("+=") {
+                    instance.stride = Some(self.number()?);
+                } else if self.accept("%=") {
+                    instance.align = Some(self.number()?);
+                } else {
+                    break;
+                }
+            }
+            self.components[parent].instances.push(instance);
+            if !self.accept(",") {
+                return self.expect(";");
+            }
+        }
+    }
+}
+
+/// Parses SystemRDL source into one [`Device`] per top-level address map.
+///
+/// The provided interfaces select the bus-specific register traits of the generated code.
+///
+/// # Errors
+/// Returns [`Error::Syntax`] if the source cannot be parsed, and [`Error::Unsupported`] if it
+/// uses SystemRDL features outside the supported subset or registers that cannot be represented.
+pub fn parse(text: &str, interfaces: &[Interface]) -> Result<Vec<Device>, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        position: 0,
+        components: Vec::new(),
+        scopes: vec![Scope::default()],
+        roots: Vec::new(),
+        instantiated: HashSet::new(),
+    };
+    parser.statements(None)?;
+
+    let mut devices = Vec::new();
+    for (name, root) in parser.roots {
+        if parser.instantiated.contains(&root) {
+            continue;
+        }
+        let component = &parser.components[root];
+        let mut registers = Vec::new();
+        collect(&parser.components, root, "", 0, &mut registers)?;
+        registers.sort_by_key(|r| r.address);
+
+        let max_address = registers.iter().map(|r| r.address).max().unwrap_or(0);
+        let address_width = AddressWidth::fitting(max_address).ok_or_else(|| {
+            Error::Unsupported(format!(
+                "address map {name} exceeds 16-bit register addresses"
+            ))
+        })?;
+        devices.push(Device {
+            description: component.description(),
+            name,
+            base_address: None,
+            address_width,
+            interfaces: interfaces.to_vec(),
+            byte_order: if component.flag("littleendian") {
+                ByteOrder::LittleEndian
+            } else {
+                ByteOrder::BigEndian
+            },
+            registers,
+        });
+    }
+    Ok(devices)
+}
+
+/// Collects the registers of an address map or register file placed at `base`,
+/// returning the size of the component in bytes.
+fn collect(
+    components: &[Component],
+    index: usize,
+    prefix: &str,
+    base: u128,
+    registers: &mut Vec<Register>,
+) -> Result<u128, Error> {
+    let mut next = 0;
+    let mut end = 0;
+    for instance in &components[index].instances {
+        let child = &components[instance.component];
+        let size = match child.kind {
+            Kind::Reg => u128::from(register_width(child, instance)? / 8),
+            _ => collect(components, instance.component, "", 0, &mut Vec::new())?,
+        };
+        let count = match instance.dim {
+            None => 1,
+            Some(Dim::Count(count)) => count,
+            Some(Dim::Bits { .. }) => {
+                return Err(syntax(instance.line, "only fields have bit ranges"));
+            }
+        };
+        let alignment = instance
+            .align
+            .unwrap_or(if child.kind == Kind::Reg { size } else { 1 });
+        let address = match instance.address {
+            Some(address) => address,
+            None => align_up(next, alignment)
+                .ok_or_else(|| exceeds_addresses(&format!("{prefix}{}", instance.name)))?,
+        };
+        let stride = instance.stride.unwrap_or(size);
+
+        for element in 0..count {
+            let name = match instance.dim {
+                Some(_) => format!("{prefix}{}{element}", instance.name),
+                None => format!("{prefix}{}", instance.name),
+            };
+            let absolute = element
+                .checked_mul(stride)
+                .and_then(|offset| offset.checked_add(address))
+                .and_then(|offset| offset.checked_add(base))
+                .ok_or_else(|| exceeds_addresses(&name))?;
+            if child.kind == Kind::Reg {
+                let address = u32::try_from(absolute).map_err(|_| exceeds_addresses(&name))?;
+                registers.push(register(components, instance, name, address)?);
+            } else {
+                collect(
+                    components,
+                    instance.component,
+                    &format!("{name}_"),
+                    absolute,
+                    registers,
+                )?;
+            }
+        }
+        next = count
+            .saturating_sub(1)
+            .checked_mul(stride)
+            .and_then(|offset| offset.checked_add(address))
+            .and_then(|offset| offset.checked_add(size))
+            .ok_or_else(|| exceeds_addresses(&format!("{prefix}{}", instance.name)))?;
+        end = end.max(next);
+    }
+    Ok(end)
+}
+
+fn align_up(value: u128, alignment: u128) -> Option<u128> {
+    if alignment <= 1 {
+        Some(value)
+    } else {
+        Some(value.checked_add(alignment - 1)? / alignment * alignment)
+    }
+}
+
+fn exceeds_addresses(name: &str) -> Error {
+    Error::Unsupported(format!("register {name} exceeds 32-bit addresses"))
+}
+
+fn register_width(component: &Component, instance: &Instance) -> Result<u32, Error> {
+    match component.number("regwidth").unwrap_or(32) {
+        width @ (8 | 16 | 32 | 64) => Ok(u32::try_from(width).unwrap_or(32)),
+        width => Err(Error::Unsupported(format!(
+            "line {}: register {} has an unsupported width of {width} bits",
+            instance.line, instance.name
+        ))),
+    }
+}
+
+fn register(
+    components: &[Component],
+    instance: &Instance,
+    name: String,
+    address: u32,
+) -> Result<Register, Error> {
+    let component = &components[instance.component];
+    let size_bits = register_width(component, instance)?;
+    let mut fields = Vec::new();
+    let mut reset: Option<u128> = None;
+    let (mut readable, mut writable) = (false, false);
+    let mut next_lsb = 0;
+
+    for field in &component.instances {
+        let definition = &components[field.component];
+        let (offset, width) = match field.dim {
+            Some(Dim::Bits { msb, lsb }) if msb >= lsb => match (msb - lsb).checked_add(1) {
+                Some(width) => (lsb, width),
+                None => return Err(syntax(field.line, "field position out of range")),
+            },
+            Some(Dim::Bits { .. }) => {
+                return Err(syntax(field.line, "msb must not be less than lsb"));
+            }
+            Some(Dim::Count(width)) => (next_lsb, width),
+            None => (next_lsb, definition.number("fieldwidth").unwrap_or(1)),
+        };
+        next_lsb = offset
+            .checked_add(width)
+            .ok_or_else(|| syntax(field.line, "field position out of range"))?;
+        let (offset, width) = match (u32::try_from(offset), u32::try_from(width)) {
+            (Ok(offset), Ok(width)) => (offset, width),
+            _ => return Err(syntax(field.line, "field position out of range")),
+        };
+
+        if let Some(hw) = definition.text("hw") {
+            if !matches!(hw, "r" | "w" | "rw" | "wr" | "na") {
+                return Err(syntax(field.line, &format!("unknown hw access {hw}")));
+            }
+        }
+        let access = match definition.text("sw").unwrap_or("rw") {
+            "r" => Access::ReadOnly,
+            "w" | "w1" => Access::WriteOnly,
+            "rw" | "wr" | "rw1" => Access::ReadWrite,
+            "na" => continue,
+            other => return Err(syntax(field.line, &format!("unknown sw access {other}"))),
+        };
+        readable |= access.is_readable();
+        writable |= access.is_writable();
+
+        if let Some(value) = field.reset.or_else(|| definition.number("reset")) {
+            if value.checked_shr(width).map_or(false, |rest| rest != 0) {
+                return Err(syntax(
+                    field.line,
+                    &format!("reset value 0x{value:X} exceeds the {width}-bit field"),
+                ));
+            }
+            if offset < 128 {
+                reset = Some(reset.unwrap_or(0) | (value << offset));
+            }
+        }
+        fields.push(Field {
+            name: field.name.clone(),
+            description: definition.description(),
+            offset,
+            width,
+            access: Some(access),
+        });
+    }
+
+    let access = match (readable, writable) {
+        (true, true) => Access::ReadWrite,
+        (true, false) => Access::ReadOnly,
+        (false, true) => Access::WriteOnly,
+        (false, false) => {
+            return Err(syntax(
+                instance.line,
+                &format!("register {name} has no software-accessible fields"),
+            ))
+        }
+    };
+    if fields.iter().all(|f| f.access == Some(access)) {
+        for field in &mut fields {
+            field.access = None;
+        }
+    }
+    Ok(Register {
+        name,
+        description: component.description(),
+        address,
+        size_bits,
+        access,
+        reset,
+        identity: Vec::new(),
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codegen::generate;
+    use crate::codegen::tests::assert_golden;
+
+    const RDL: &str = include_str!("../../tests/fixtures/systemrdl/example.rdl");
+
+    fn devices() -> Vec<Device> {
+        match parse(RDL, &[Interface::i2c(0x40)]) {
+            Ok(devices) => devices,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    #[test]
+    fn top_level_address_maps() {
+        let devices = devices();
+        let names: Vec<_> = devices.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["fpga_ctrl"]);
+        assert_eq!(devices[0].byte_order, ByteOrder::LittleEndian);
+        assert_eq!(devices[0].address_width, AddressWidth::Bits16);
+    }
+
+    #[test]
+    fn registers() {
+        let devices = devices();
+        let registers: Vec<_> = devices[0]
+            .registers
+            .iter()
+            .map(|r| (r.name.as_str(), r.address, r.size_bits, r.access, r.reset))
+            .collect();
+        assert_eq!(
+            registers,
+            [
+                ("ID", 0x000, 32, Access::ReadOnly, Some(0x0A5A_0102)),
+                ("CTRL", 0x004, 16, Access::ReadWrite, Some(0x0001)),
+                ("STATUS", 0x006, 16, Access::ReadOnly, Some(0x0000)),
+                ("dma0_SRC", 0x100, 32, Access::ReadWrite, None),
+                ("dma0_LEN", 0x104, 16, Access::ReadWrite, Some(0x0000)),
+                ("dma1_SRC", 0x108, 32, Access::ReadWrite, None),
+                ("dma1_LEN", 0x10C, 16, Access::ReadWrite, Some(0x0000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let devices = devices();
+        let fields: Vec<_> = devices[0].registers[1]
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset, f.width, f.access))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("EN", 0, 1, Some(Access::ReadWrite)),
+                ("MODE", 1, 2, Some(Access::ReadWrite)),
+                ("SOFT_RESET", 15, 1, Some(Access::WriteOnly)),
+            ]
+        );
+    }
+
+    #[test]
+    fn golden() {
+        let code = match generate(&devices()) {
+            Ok(code) => code,
+            Err(error) => panic!("{error}"),
+        };
+        assert_golden("tests/fixtures/systemrdl/example.rs", &code);
+    }
+
+    #[test]
+    fn nested_address_maps_are_flattened() {
+        let rdl = "
+            addrmap block { reg { field {} VALUE[7:0]; } DATA @ 0x4; };
+            addrmap top { block sub @ 0x10; };
+        ";
+        let devices = match parse(rdl, &[]) {
+            Ok(devices) => devices,
+            Err(error) => panic!("{error}"),
+        };
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "top");
+        assert_eq!(devices[0].registers[0].name, "sub_DATA");
+        assert_eq!(devices[0].registers[0].address, 0x14);
+    }
+
+    #[test]
+    fn rejects_unsupported_input() {
+        assert!(matches!(
+            parse("addrmap a { enum e { X = 0; }; };", &[]),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse("addrmap a { reg { field {} F; } R; R.F->reset = 1; };", &[]),
+            Err(Error::Unsupported(_))
+        ));
+        assert!(matches!(
+            parse("addrmap a { reg { field {} F; } R @ 0x0 }", &[]),
+            Err(Error::Syntax(message)) if message.starts_with("line 1:")
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_field_reset() {
+        let rdl = "addrmap a {
+            reg {
+                field {} F[3:0] = 0x1F;
+            } R @ 0x0;
+        };";
+        assert_eq!(
+            parse(rdl, &[]),
+            Err(Error::Syntax(
+                "line 3: reset value 0x1F exceeds the 4-bit field".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_overflowing_positions() {
+        let rdl = "addrmap a {
+            reg {
+                field {} F[0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF:0];
+            } R @ 0x0;
+        };";
+        assert_eq!(
+            parse(rdl, &[]),
+            Err(Error::Syntax("line 3: field position out of range".into()))
+        );
+
+        let rdl = "addrmap a {
+            regfile {
+                reg { field {} F; } R @ 0x4;
+            } x @ 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF;
+            reg { field {} G; } S @ 0x10;
+        };";
+        assert!(matches!(parse(rdl, &[]), Err(Error::Unsupported(_))));
+
+        let rdl = "addrmap a {
+            reg { field {} F; } R[2] @ 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF;
+        };";
+        assert!(matches!(parse(rdl, &[]), Err(Error::Unsupported(_))));
+    }
+}
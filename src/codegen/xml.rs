@@ -0,0 +1,51 @@
+//! Helpers shared by the XML-based importers.
+//!
+//! [`parse_access`] is also used by the TOML description format.
+
+use crate::codegen::{parse_integer, Error};
+use crate::map::Access;
+use roxmltree::Node;
+
+/// Parses the access values shared by CMSIS-SVD, IP-XACT and the TOML description format.
+pub(crate) fn parse_access(access: &str) -> Result<Access, Error> {
+    match access.trim() {
+        "read-only" => Ok(Access::ReadOnly),
+        "write-only" | "writeOnce" => Ok(Access::WriteOnly),
+        "read-write" | "read-writeOnce" => Ok(Access::ReadWrite),
+        other => Err(Error::Syntax(format!("unknown access {other}"))),
+    }
+}
+
+pub(crate) fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(name))
+}
+
+pub(crate) fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|n| n.text()).map(str::trim)
+}
+
+pub(crate) fn required_text<'a>(node: Node<'a, '_>, name: &str) -> Result<&'a str, Error> {
+    child_text(node, name).ok_or_else(|| {
+        Error::Syntax(format!(
+            "<{}> lacks the required <{name}> element",
+            node.tag_name().name()
+        ))
+    })
+}
+
+pub(crate) fn integer(text: &str, element: &str) -> Result<u64, Error> {
+    parse_integer(text)
+        .and_then(|value| u64::try_from(value).ok())
+        .ok_or_else(|| Error::Syntax(format!("invalid <{element}> value {text}")))
+}
+
+pub(crate) fn u32_value(text: &str, element: &str) -> Result<u32, Error> {
+    integer(text, element)?
+        .try_into()
+        .map_err(|_| Error::Unsupported(format!("<{element}> value {text} is too large")))
+}
+
+/// Collapses the whitespace of multi-line descriptions.
+pub(crate) fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
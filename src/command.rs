@@ -0,0 +1,505 @@
+//! Command-style devices that are controlled by command codes rather than registers.
+//!
+//! Devices such as the Sensirion `SHT3x` or `SCD4x` sensors and many SPI flash chips are
+//! controlled by writing a command code, optionally followed by arguments, and reading a
+//! response after the command has executed. A [`Command`] describes the code, its argument
+//! bytes and its execution time; a [`Query`] additionally describes the response, which is
+//! decoded with [`FromBits`] like a register.
+//!
+//! Arguments and responses may be protected by a [`Crc8`] checksum after every word, as
+//! Sensirion sensors do. Checksums are appended to the arguments and verified and stripped
+//! from responses.
+//!
+//! [`CommandAccess`] is implemented by [`I2CDevice`](crate::i2c::I2CDevice) and
+//! [`SPIDevice`](crate::spi::SPIDevice).
+
+use crate::access::encode_address;
+use crate::backing::{ByteOrder, RegisterBacking};
+use crate::error::Error;
+use crate::register_address::RegisterAddress;
+use crate::sizes::{RegisterSizeInformation, MAX_BYTES};
+use crate::FromBits;
+use embedded_hal::delay::DelayNs;
+
+/// The largest transfer, in bytes, including the command code and checksums.
+const MAX_TRANSFER_BYTES: usize = 2 + 2 * MAX_BYTES;
+
+/// A CRC-8 checksum following every word of arguments and responses.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Crc8 {
+    polynomial: u8,
+    init: u8,
+    word_bytes: usize,
+}
+
+impl Crc8 {
+    /// The checksum of Sensirion sensors: polynomial `0x31`, initial value `0xFF`, after
+    /// every two bytes.
+    pub const SENSIRION: Self = Self::new(0x31, 0xFF, 2);
+
+    /// Constructs a checksum of the specified polynomial and initial value, following
+    /// every `word_bytes` bytes.
+    ///
+    /// # Panics
+    /// Panics if `word_bytes` is zero.
+    #[must_use]
+    pub const fn new(polynomial: u8, init: u8, word_bytes: usize) -> Self {
+        assert!(word_bytes > 0, "a checksum must cover at least one byte");
+        Self {
+            polynomial,
+            init,
+            word_bytes,
+        }
+    }
+
+    /// Returns the number of bytes covered by each checksum.
+    #[must_use]
+    pub const fn word_bytes(&self) -> usize {
+        self.word_bytes
+    }
+
+    /// Calculates the checksum of the provided bytes.
+    #[must_use]
+    pub fn calculate(&self, data: &[u8]) -> u8 {
+        let mut crc = self.init;
+        for byte in data {
+            crc ^= byte;
+            for _ in 0..8 {
+                crc = if crc & 0x80 == 0 {
+                    crc << 1
+                } else {
+                    (crc << 1) ^ self.polynomial
+                };
+            }
+        }
+        crc
+    }
+}
+
+/// A command, identified by its code and optionally followed by argument bytes.
+pub trait Command<C>
+where
+    C: RegisterAddress,
+{
+    /// The command code, transferred like a register address.
+    const CODE: C;
+
+    /// The time the device takes to execute the command, in microseconds.
+    const EXECUTION_TIME_US: u32 = 0;
+
+    /// The checksum following every word of arguments and response.
+    const CRC: Option<Crc8> = None;
+
+    /// Writes the argument bytes, without checksums, and returns their number.
+    ///
+    /// The buffer holds [`MAX_BYTES`] bytes. Defaults to no arguments.
+    fn arguments(&self, arguments: &mut [u8]) -> usize {
+        let _ = arguments;
+        0
+    }
+}
+
+/// A command that is answered with a response of `Size` bytes, excluding checksums.
+pub trait Query<C, Size>: Command<C>
+where
+    C: RegisterAddress,
+    Size: RegisterSizeInformation,
+{
+    /// The order in which the response bytes are transferred.
+    const BYTE_ORDER: ByteOrder = ByteOrder::BigEndian;
+
+    /// The backing type of the response, e.g. `u32` for a 4-byte response.
+    type Backing: RegisterBacking;
+
+    /// The response type.
+    type Response: FromBits<Self::Backing>;
+}
+
+/// Byte-level access to a command-style device, with typed [`Command`] execution.
+pub trait CommandAccess {
+    /// The error type of the underlying bus.
+    type Error;
+
+    /// Writes a command code and its arguments.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn write_command(&mut self, command: &[u8]) -> Result<(), Self::Error>;
+
+    /// Reads the response to a previously written command.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn read_response(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Writes a command code and its arguments and reads the response in a single
+    /// transaction.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn transfer_command(&mut self, command: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Executes a command and waits for its execution time.
+    ///
+    /// # Errors
+    /// Returns [`Error::Bus`] if the bus transaction fails.
+    fn execute<Cmd, C, D>(&mut self, command: &Cmd, delay: &mut D) -> Result<(), Error<Self::Error>>
+    where
+        Cmd: Command<C>,
+        C: RegisterAddress,
+        D: DelayNs,
+    {
+        let mut buffer = [0_u8; MAX_TRANSFER_BYTES];
+        let len = encode_command(command, &mut buffer);
+        self.write_command(&buffer[..len]).map_err(Error::Bus)?;
+        if Cmd::EXECUTION_TIME_US > 0 {
+            delay.delay_us(Cmd::EXECUTION_TIME_US);
+        }
+        Ok(())
+    }
+
+    /// Executes a command, waits for its execution time and reads and decodes the response.
+    ///
+    /// Commands without an execution time are transferred in a single transaction.
+    ///
+    /// # Errors
+    /// Returns [`Error::Bus`] if a bus transaction fails and [`Error::ChecksumMismatch`] if
+    /// a checksum of the response does not match.
+    fn query<Cmd, C, Size, D>(
+        &mut self,
+        command: &Cmd,
+        delay: &mut D,
+    ) -> Result<Cmd::Response, Error<Self::Error>>
+    where
+        Cmd: Query<C, Size>,
+        C: RegisterAddress,
+        Size: RegisterSizeInformation,
+        D: DelayNs,
+    {
+        let mut request = [0_u8; MAX_TRANSFER_BYTES];
+        let len = encode_command(command, &mut request);
+        let mut response = [0_u8; MAX_TRANSFER_BYTES];
+        let response = &mut response[..wire_len(Size::BYTES, Cmd::CRC)];
+        if Cmd::EXECUTION_TIME_US > 0 {
+            self.write_command(&request[..len]).map_err(Error::Bus)?;
+            delay.delay_us(Cmd::EXECUTION_TIME_US);
+            self.read_response(response).map_err(Error::Bus)?;
+        } else {
+            self.transfer_command(&request[..len], response)
+                .map_err(Error::Bus)?;
+        }
+        let mut data = [0_u8; MAX_BYTES];
+        let data = &mut data[..Size::BYTES];
+        strip_checksums(response, Cmd::CRC, data)?;
+        Ok(Cmd::Response::from_bits(Cmd::Backing::from_register_bytes(
+            data,
+            Cmd::BYTE_ORDER,
+        )))
+    }
+}
+
+/// Returns the number of bytes of `len` data bytes with their checksums.
+fn wire_len(len: usize, crc: Option<Crc8>) -> usize {
+    match crc {
+        Some(crc) => len + (len + crc.word_bytes - 1) / crc.word_bytes,
+        None => len,
+    }
+}
+
+/// Writes the command code and arguments with their checksums, returning the length.
+fn encode_command<Cmd, C>(command: &Cmd, buffer: &mut [u8]) -> usize
+where
+    Cmd: Command<C> + ?Sized,
+    C: RegisterAddress,
+{
+    let (code, mut len) = encode_address(&Cmd::CODE);
+    buffer[..len].copy_from_slice(&code[..len]);
+    let mut arguments = [0_u8; MAX_BYTES];
+    let count = command.arguments(&mut arguments).min(MAX_BYTES);
+    if let Some(crc) = Cmd::CRC {
+        for word in arguments[..count].chunks(crc.word_bytes) {
+            buffer[len..len + word.len()].copy_from_slice(word);
+            len += word.len();
+            buffer[len] = crc.calculate(word);
+            len += 1;
+        }
+    } else {
+        buffer[len..len + count].copy_from_slice(&arguments[..count]);
+        len += count;
+    }
+    len
+}
+
+/// Verifies and removes the checksums of a response.
+fn strip_checksums<E>(response: &[u8], crc: Option<Crc8>, data: &mut [u8]) -> Result<(), Error<E>> {
+    match crc {
+        Some(crc) => {
+            let words = response.chunks(crc.word_bytes + 1);
+            for (word, data) in words.zip(data.chunks_mut(crc.word_bytes)) {
+                let (bytes, checksum) = word.split_at(word.len() - 1);
+                if crc.calculate(bytes) != checksum[0] {
+                    return Err(Error::ChecksumMismatch);
+                }
+                data.copy_from_slice(bytes);
+            }
+        }
+        None => data.copy_from_slice(response),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::RegisterAddress16;
+    use crate::sizes::R4;
+    use crate::test_support::Clock;
+
+    /// A device answering every command with a fixed response, recording the last command.
+    #[derive(Default)]
+    struct Device {
+        command: Vec<u8>,
+        response: Vec<u8>,
+        transactions: usize,
+    }
+
+    impl CommandAccess for Device {
+        type Error = ();
+
+        fn write_command(&mut self, command: &[u8]) -> Result<(), Self::Error> {
+            self.command = command.to_vec();
+            self.transactions += 1;
+            Ok(())
+        }
+
+        fn read_response(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+            buffer.copy_from_slice(&self.response);
+            self.transactions += 1;
+            Ok(())
+        }
+
+        fn transfer_command(
+            &mut self,
+            command: &[u8],
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.command = command.to_vec();
+            buffer.copy_from_slice(&self.response);
+            self.transactions += 1;
+            Ok(())
+        }
+    }
+
+    /// Sets the ambient pressure for compensation, in hPa.
+    struct SetAmbientPressure(u16);
+
+    impl Command<RegisterAddress16> for SetAmbientPressure {
+        const CODE: RegisterAddress16 = RegisterAddress16::new(0xE000);
+        const CRC: Option<Crc8> = Some(Crc8::SENSIRION);
+
+        fn arguments(&self, arguments: &mut [u8]) -> usize {
+            arguments[..2].copy_from_slice(&self.0.to_be_bytes());
+            2
+        }
+    }
+
+    /// A single-shot temperature and humidity measurement.
+    struct MeasureSingleShot;
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Measurement {
+        temperature: u16,
+        humidity: u16,
+    }
+
+    impl FromBits<u32> for Measurement {
+        #[allow(clippy::cast_possible_truncation)]
+        fn from_bits_ref(value: &u32) -> Self {
+            Self {
+                temperature: (value >> 16) as u16,
+                humidity: *value as u16,
+            }
+        }
+    }
+
+    impl Command<RegisterAddress16> for MeasureSingleShot {
+        const CODE: RegisterAddress16 = RegisterAddress16::new(0x2400);
+        const EXECUTION_TIME_US: u32 = 15_000;
+        const CRC: Option<Crc8> = Some(Crc8::SENSIRION);
+    }
+
+    impl Query<RegisterAddress16, R4> for MeasureSingleShot {
+        type Backing = u32;
+        type Response = Measurement;
+    }
+
+    #[test]
+    fn sensirion_crc() {
+        assert_eq!(Crc8::SENSIRION.calculate(&[0xBE, 0xEF]), 0x92);
+    }
+
+    #[test]
+    fn execute_with_arguments() {
+        let mut device = Device::default();
+        let mut clock = Clock::default();
+        assert_eq!(
+            device.execute(&SetAmbientPressure(0xBEEF), &mut clock),
+            Ok(())
+        );
+        assert_eq!(device.command, [0xE0, 0x00, 0xBE, 0xEF, 0x92]);
+        assert_eq!(clock.elapsed_ns, 0);
+    }
+
+    #[test]
+    fn query_verifies_checksums() {
+        let mut device = Device {
+            response: vec![0xBE, 0xEF, 0x92, 0x12, 0x34, 0x37],
+            ..Device::default()
+        };
+        let mut clock = Clock::default();
+        let measurement = device.query(&MeasureSingleShot, &mut clock);
+        assert_eq!(
+            measurement,
+            Ok(Measurement {
+                temperature: 0xBEEF,
+                humidity: 0x1234,
+            })
+        );
+        assert_eq!(device.command, [0x24, 0x00]);
+        assert_eq!(device.transactions, 2);
+        assert_eq!(clock.elapsed_ns, 15_000_000);
+
+        device.response[5] ^= 0x01;
+        let measurement = device.query(&MeasureSingleShot, &mut clock);
+        assert_eq!(measurement, Err(Error::ChecksumMismatch));
+    }
+
+    #[cfg(feature = "i2c")]
+    mod i2c {
+        use super::*;
+        use crate::i2c::{DeviceAddress7, I2CDevice};
+        use crate::mock::{MockDevice, MockI2c};
+
+        fn device(mock: MockDevice) -> I2CDevice<MockI2c> {
+            let bus = MockI2c::new().with_device(DeviceAddress7::new(0x62), mock);
+            I2CDevice::new(bus, DeviceAddress7::new(0x62))
+        }
+
+        #[test]
+        fn execute_writes_code_and_arguments() {
+            let mut device = device(MockDevice::new::<RegisterAddress16>());
+            assert_eq!(
+                device.execute(&SetAmbientPressure(0x1234), &mut Clock::default()),
+                Ok(())
+            );
+            let bus = device.release();
+            let written = bus
+                .device(DeviceAddress7::new(0x62))
+                .map(|m| m.registers().get(RegisterAddress16::new(0xE000)));
+            assert_eq!(written, Some([0x12, 0x34, 0x37]));
+        }
+
+        #[test]
+        fn query_reads_response_after_execution() {
+            let mut mock = MockDevice::new::<RegisterAddress16>();
+            mock.registers_mut().set(
+                RegisterAddress16::new(0x2400),
+                &[0xBE, 0xEF, 0x92, 0x12, 0x34, 0x37],
+            );
+            let mut clock = Clock::default();
+            assert_eq!(
+                device(mock).query(&MeasureSingleShot, &mut clock),
+                Ok(Measurement {
+                    temperature: 0xBEEF,
+                    humidity: 0x1234,
+                })
+            );
+            assert_eq!(clock.elapsed_ns, 15_000_000);
+
+            let mut mock = MockDevice::new::<RegisterAddress16>();
+            mock.registers_mut().set(
+                RegisterAddress16::new(0x2400),
+                &[0xBE, 0xEF, 0x92, 0x12, 0x34, 0x36],
+            );
+            assert_eq!(
+                device(mock).query(&MeasureSingleShot, &mut clock),
+                Err(Error::ChecksumMismatch)
+            );
+        }
+    }
+
+    #[cfg(feature = "spi")]
+    mod spi {
+        use super::*;
+        use crate::mock::{MockDevice, MockSpi};
+        use crate::register_address::RegisterAddress8;
+        use crate::sizes::R2;
+        use crate::spi::SPIDevice;
+        use crate::test_support::test_register;
+
+        /// Sets an alarm threshold; the code does not carry the read flag.
+        struct SetThreshold(u16);
+
+        impl Command<RegisterAddress8> for SetThreshold {
+            const CODE: RegisterAddress8 = RegisterAddress8::new(0x10);
+            const CRC: Option<Crc8> = Some(Crc8::SENSIRION);
+
+            fn arguments(&self, arguments: &mut [u8]) -> usize {
+                arguments[..2].copy_from_slice(&self.0.to_be_bytes());
+                2
+            }
+        }
+
+        /// Reads the status word; the code carries the read flag.
+        struct ReadStatus;
+
+        impl Command<RegisterAddress8> for ReadStatus {
+            const CODE: RegisterAddress8 = RegisterAddress8::new(0x85);
+            const CRC: Option<Crc8> = Some(Crc8::SENSIRION);
+        }
+
+        test_register!(Status(u16): R2);
+
+        impl Query<RegisterAddress8, R2> for ReadStatus {
+            type Backing = u16;
+            type Response = Status;
+        }
+
+        #[test]
+        fn execute_writes_code_and_arguments() {
+            let mut device = SPIDevice::new(MockSpi::new(MockDevice::new::<RegisterAddress8>()));
+            assert_eq!(
+                device.execute(&SetThreshold(0xBEEF), &mut Clock::default()),
+                Ok(())
+            );
+            let written = device
+                .release()
+                .device()
+                .registers()
+                .get(RegisterAddress8::new(0x10));
+            assert_eq!(written, [0xBE, 0xEF, 0x92]);
+        }
+
+        #[test]
+        fn query_transfers_command_and_response() {
+            let mut mock = MockDevice::new::<RegisterAddress8>();
+            mock.registers_mut()
+                .set(RegisterAddress8::new(0x05), &[0x12, 0x34, 0x37]);
+            let mut device = SPIDevice::new(MockSpi::new(mock));
+            assert_eq!(
+                device.query(&ReadStatus, &mut Clock::default()),
+                Ok(Status(0x1234))
+            );
+
+            let mut mock = MockDevice::new::<RegisterAddress8>();
+            mock.registers_mut()
+                .set(RegisterAddress8::new(0x05), &[0x12, 0x34, 0x00]);
+            let mut device = SPIDevice::new(MockSpi::new(mock));
+            assert_eq!(
+                device.query(&ReadStatus, &mut Clock::default()),
+                Err(Error::ChecksumMismatch)
+            );
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Errors of register operations.
+//!
+//! [`Error`] is generic over the error of the underlying bus, which is kept as
+//! [`Error::Bus`]. Bus-independent failures, such as an out-of-range address or a timeout
+//! while polling a register, have dedicated variants.
+//!
+//! With the `i2c` or `spi` feature enabled, [`Error`] implements the corresponding
+//! embedded-hal `Error` trait, so that it can be classified like any bus error.
+
+use core::fmt::{Debug, Display, Formatter};
+
+/// The part of an I²C transaction that was not acknowledged.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NoAcknowledgeSource {
+    /// The device did not acknowledge its address.
+    Address,
+    /// The device did not acknowledge a data byte.
+    Data,
+    /// The bus does not report which part was not acknowledged.
+    Unknown,
+}
+
+/// An error of a register operation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The bus transaction failed.
+    Bus(E),
+    /// The device did not acknowledge the transaction.
+    NoAcknowledge(NoAcknowledgeSource),
+    /// The register content is not a valid value of the register type.
+    Decode,
+    /// The access policy of the register does not permit the operation, e.g. writing a
+    /// read-only register.
+    AccessViolation,
+    /// The address does not fit the address type or the device's address space.
+    AddressOutOfRange,
+    /// The packet error code (PEC) or CRC of the transferred data does not match.
+    ChecksumMismatch,
+    /// The operation did not complete in time.
+    Timeout,
+}
+
+impl<E> Error<E> {
+    /// Converts the bus error using the provided function.
+    pub fn map_bus<F, M>(self, f: M) -> Error<F>
+    where
+        M: FnOnce(E) -> F,
+    {
+        match self {
+            Self::Bus(error) => Error::Bus(f(error)),
+            Self::NoAcknowledge(source) => Error::NoAcknowledge(source),
+            Self::Decode => Error::Decode,
+            Self::AccessViolation => Error::AccessViolation,
+            Self::AddressOutOfRange => Error::AddressOutOfRange,
+            Self::ChecksumMismatch => Error::ChecksumMismatch,
+            Self::Timeout => Error::Timeout,
+        }
+    }
+}
+
+impl<E> Display for Error<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(error) => write!(f, "bus error: {error:?}"),
+            Self::NoAcknowledge(NoAcknowledgeSource::Address) => {
+                f.write_str("the device did not acknowledge its address")
+            }
+            Self::NoAcknowledge(NoAcknowledgeSource::Data) => {
+                f.write_str("the device did not acknowledge the data")
+            }
+            Self::NoAcknowledge(NoAcknowledgeSource::Unknown) => {
+                f.write_str("the device did not acknowledge")
+            }
+            Self::Decode => f.write_str("the register content is invalid"),
+            Self::AccessViolation => f.write_str("the register does not permit the operation"),
+            Self::AddressOutOfRange => f.write_str("the address is out of range"),
+            Self::ChecksumMismatch => f.write_str("checksum mismatch"),
+            Self::Timeout => f.write_str("timed out"),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<E> std::error::Error for Error<E> where E: Debug {}
+
+#[cfg(feature = "i2c")]
+impl<E> embedded_hal::i2c::Error for Error<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource as Source};
+        match self {
+            Self::Bus(error) => error.kind(),
+            Self::NoAcknowledge(NoAcknowledgeSource::Address) => {
+                ErrorKind::NoAcknowledge(Source::Address)
+            }
+            Self::NoAcknowledge(NoAcknowledgeSource::Data) => {
+                ErrorKind::NoAcknowledge(Source::Data)
+            }
+            Self::NoAcknowledge(NoAcknowledgeSource::Unknown) => {
+                ErrorKind::NoAcknowledge(Source::Unknown)
+            }
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<E> embedded_hal::spi::Error for Error<E>
+where
+    E: embedded_hal::spi::Error,
+{
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            Self::Bus(error) => error.kind(),
+            _ => embedded_hal::spi::ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Error::Bus(42).to_string(), "bus error: 42");
+        assert_eq!(
+            Error::<()>::NoAcknowledge(NoAcknowledgeSource::Data).to_string(),
+            "the device did not acknowledge the data"
+        );
+        assert_eq!(Error::<()>::Timeout.to_string(), "timed out");
+    }
+
+    #[test]
+    fn map_bus() {
+        assert_eq!(Error::Bus(1).map_bus(|e| e + 1), Error::Bus(2));
+        assert_eq!(Error::<u8>::Decode.map_bus(u16::from), Error::Decode);
+    }
+
+    #[test]
+    #[cfg(feature = "i2c")]
+    fn i2c_error_kind() {
+        use embedded_hal::i2c::{Error as _, ErrorKind, NoAcknowledgeSource as Source};
+
+        assert_eq!(
+            Error::<ErrorKind>::NoAcknowledge(NoAcknowledgeSource::Address).kind(),
+            ErrorKind::NoAcknowledge(Source::Address)
+        );
+        assert_eq!(
+            Error::Bus(ErrorKind::ArbitrationLoss).kind(),
+            ErrorKind::ArbitrationLoss
+        );
+        assert_eq!(Error::<ErrorKind>::Timeout.kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    #[cfg(feature = "spi")]
+    fn spi_error_kind() {
+        use embedded_hal::spi::{Error as _, ErrorKind};
+
+        assert_eq!(
+            Error::Bus(ErrorKind::ModeFault).kind(),
+            ErrorKind::ModeFault
+        );
+        assert_eq!(
+            Error::<ErrorKind>::ChecksumMismatch.kind(),
+            ErrorKind::Other
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! Documentation exports of register maps.
+//!
+//! [`Markdown`] and [`Html`] render a [`RegisterMap`] as a summary table of all registers,
+//! followed by a table of the bit fields of every register that describes them. [`WaveDrom`]
+//! renders a single register as a [WaveDrom](https://wavedrom.com/) `reg` diagram in JSON.
+//! All adapters implement [`Display`], so no allocation is required.
+//!
+//! ```
+//! use hardware_registers::export::{Markdown, WaveDrom};
+//! use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+//! use hardware_registers::register_address::RegisterAddress8;
+//! use hardware_registers::sizes::{RegisterSizeInformation, R1};
+//!
+//! const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+//!     RegisterInfo::new("CTRL_REG1", RegisterAddress8::new(0x20), R1::BYTES, Access::ReadWrite)
+//!         .with_reset(&[0x07])
+//!         .with_fields(&[
+//!             FieldInfo::new("ODR", 4, 4, Access::ReadWrite),
+//!             FieldInfo::new("Xen", 0, 1, Access::ReadWrite),
+//!         ]),
+//! ];
+//!
+//! const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("LIS3DH", REGISTERS);
+//! let markdown = Markdown::new(&MAP).to_string();
+//! assert!(markdown.contains("| CTRL_REG1 | 0x20 | 8 | RW | 0x07 |"));
+//! assert!(markdown.contains("| 7:4 | ODR | RW | 0x0 |"));
+//! assert_eq!(
+//!     WaveDrom::new(&REGISTERS[0]).to_string(),
+//!     r#"{"reg":[{"name":"Xen","bits":1,"attr":"RW"},{"bits":3},{"name":"ODR","bits":4,"attr":"RW"}],"config":{"bits":8}}"#
+//! );
+//! ```
+
+use crate::backing::ByteOrder;
+use crate::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+use crate::register_address::RegisterAddress;
+use core::fmt::{Display, Formatter, Result, Write};
+
+/// Renders a register map as Markdown tables.
+#[derive(Debug, Copy, Clone)]
+pub struct Markdown<'a, A: 'static> {
+    map: &'a RegisterMap<A>,
+}
+
+impl<'a, A> Markdown<'a, A> {
+    /// Constructs a new Markdown adapter for the register map.
+    #[must_use]
+    pub const fn new(map: &'a RegisterMap<A>) -> Self {
+        Self { map }
+    }
+}
+
+impl<A> Display for Markdown<'_, A>
+where
+    A: RegisterAddress,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_document(f, self.map, Style::Markdown)
+    }
+}
+
+/// Renders a register map as HTML tables.
+#[derive(Debug, Copy, Clone)]
+pub struct Html<'a, A: 'static> {
+    map: &'a RegisterMap<A>,
+}
+
+impl<'a, A> Html<'a, A> {
+    /// Constructs a new HTML adapter for the register map.
+    #[must_use]
+    pub const fn new(map: &'a RegisterMap<A>) -> Self {
+        Self { map }
+    }
+}
+
+impl<A> Display for Html<'_, A>
+where
+    A: RegisterAddress,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write_document(f, self.map, Style::Html)
+    }
+}
+
+/// Renders a register as a WaveDrom `reg` diagram.
+///
+/// Fields are listed from the least significant bit upwards, as WaveDrom expects; bits not
+/// covered by a field are emitted as unnamed gaps. A register without fields is drawn as a
+/// single field carrying the register's name.
+#[derive(Debug, Copy, Clone)]
+pub struct WaveDrom<'a, A> {
+    register: &'a RegisterInfo<A>,
+}
+
+impl<'a, A> WaveDrom<'a, A> {
+    /// Constructs a new WaveDrom adapter for the register.
+    #[must_use]
+    pub const fn new(register: &'a RegisterInfo<A>) -> Self {
+        Self { register }
+    }
+}
+
+impl<A> Display for WaveDrom<'_, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let register = self.register;
+        f.write_str("{\"reg\":[")?;
+        if register.fields.is_empty() {
+            write_wavedrom_field(f, register.name, register.bits(), register.access)?;
+        } else {
+            for (i, segment) in Segments::new(register).enumerate() {
+                if i > 0 {
+                    f.write_char(',')?;
+                }
+                match segment {
+                    Segment::Field(field) => {
+                        write_wavedrom_field(f, field.name, field.width, field.access)?;
+                    }
+                    Segment::Reserved { width, .. } => write!(f, "{{\"bits\":{width}}}")?,
+                }
+            }
+        }
+        write!(f, "],\"config\":{{\"bits\":{}}}}}", register.bits())
+    }
+}
+
+fn write_wavedrom_field(f: &mut Formatter<'_>, name: &str, bits: usize, access: Access) -> Result {
+    write!(
+        f,
+        "{{\"name\":\"{}\",\"bits\":{bits},\"attr\":\"{}\"}}",
+        Text(Style::Json, name),
+        abbreviation(access)
+    )
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum Style {
+    Markdown,
+    Html,
+    Json,
+}
+
+fn write_document<A>(f: &mut Formatter<'_>, map: &RegisterMap<A>, style: Style) -> Result
+where
+    A: RegisterAddress,
+{
+    heading(f, style, 1, map.name)?;
+    table_start(
+        f,
+        style,
+        &["Register", "Address", "Size", "Access", "Reset"],
+    )?;
+    for register in map.registers {
+        table_row(
+            f,
+            style,
+            &[
+                &Text(style, register.name),
+                &format_args!(
+                    "0x{:0width$X}",
+                    register.address.value(),
+                    width = A::ADDR_BYTES * 2
+                ),
+                &register.bits(),
+                &abbreviation(register.access),
+                &RegisterReset(register),
+            ],
+        )?;
+    }
+    table_end(f, style)?;
+
+    for register in map.registers.iter().filter(|r| !r.fields.is_empty()) {
+        f.write_char('\n')?;
+        heading(f, style, 2, register.name)?;
+        table_start(f, style, &["Bits", "Field", "Access", "Reset"])?;
+        for segment in Segments::new(register).rev() {
+            match segment {
+                Segment::Field(field) => table_row(
+                    f,
+                    style,
+                    &[
+                        &BitRange(field.offset, field.width),
+                        &Text(style, field.name),
+                        &abbreviation(field.access),
+                        &FieldReset(register, field),
+                    ],
+                )?,
+                Segment::Reserved { offset, width } => {
+                    table_row(f, style, &[&BitRange(offset, width), &"reserved", &"", &""])?;
+                }
+            }
+        }
+        table_end(f, style)?;
+    }
+    Ok(())
+}
+
+fn heading(f: &mut Formatter<'_>, style: Style, level: usize, text: &str) -> Result {
+    if style == Style::Html {
+        writeln!(f, "<h{level}>{}</h{level}>", Text(style, text))
+    } else {
+        writeln!(f, "{:#<level$} {}\n", "", Text(style, text))
+    }
+}
+
+fn table_start(f: &mut Formatter<'_>, style: Style, headers: &[&str]) -> Result {
+    if style == Style::Html {
+        f.write_str("<table>\n<thead><tr>")?;
+        for header in headers {
+            write!(f, "<th>{header}</th>")?;
+        }
+        f.write_str("</tr></thead>\n<tbody>\n")
+    } else {
+        for header in headers {
+            write!(f, "| {header} ")?;
+        }
+        f.write_str("|\n")?;
+        for header in headers {
+            write!(f, "|{:-<width$}", "", width = header.len() + 2)?;
+        }
+        f.write_str("|\n")
+    }
+}
+
+fn table_row(f: &mut Formatter<'_>, style: Style, cells: &[&dyn Display]) -> Result {
+    if style == Style::Html {
+        f.write_str("<tr>")?;
+        for cell in cells {
+            write!(f, "<td>{cell}</td>")?;
+        }
+        f.write_str("</tr>\n")
+    } else {
+        for cell in cells {
+            write!(f, "| {cell} ")?;
+        }
+        f.write_str("|\n")
+    }
+}
+
+fn table_end(f: &mut Formatter<'_>, style: Style) -> Result {
+    if style == Style::Html {
+        f.write_str("</tbody>\n</table>\n")
+    } else {
+        Ok(())
+    }
+}
+
+const fn abbreviation(access: Access) -> &'static str {
+    match access {
+        Access::ReadOnly => "R",
+        Access::WriteOnly => "W",
+        Access::ReadWrite => "RW",
+    }
+}
+
+/// Text escaped for the output format.
+struct Text<'a>(Style, &'a str);
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for c in self.1.chars() {
+            match (self.0, c) {
+                (Style::Markdown, '|' | '\\' | '*' | '`') | (Style::Json, '"' | '\\') => {
+                    write!(f, "\\{c}")?;
+                }
+                (Style::Html, '<') => f.write_str("&lt;")?,
+                (Style::Html, '>') => f.write_str("&gt;")?,
+                (Style::Html, '&') => f.write_str("&amp;")?,
+                (Style::Html, '"') => f.write_str("&quot;")?,
+                (Style::Json, c) if c.is_control() => write!(f, "\\u{:04x}", u32::from(c))?,
+                (_, c) => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A bit range in `msb:lsb` notation, or a single bit index.
+struct BitRange(usize, usize);
+
+impl Display for BitRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let BitRange(offset, width) = *self;
+        if width == 1 {
+            write!(f, "{offset}")
+        } else {
+            write!(f, "{}:{offset}", offset + width - 1)
+        }
+    }
+}
+
+/// The reset value of a register, most significant byte first.
+struct RegisterReset<'a, A>(&'a RegisterInfo<A>);
+
+impl<A> Display for RegisterReset<'_, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let reset = match self.0.reset {
+            Some(reset) => reset,
+            None => return Ok(()),
+        };
+        f.write_str("0x")?;
+        match self.0.byte_order {
+            ByteOrder::BigEndian => reset.iter().try_for_each(|b| write!(f, "{b:02X}")),
+            ByteOrder::LittleEndian => reset.iter().rev().try_for_each(|b| write!(f, "{b:02X}")),
+        }
+    }
+}
+
+/// The reset value of a field, as a bit for single-bit fields and in hex otherwise.
+struct FieldReset<'a, A>(&'a RegisterInfo<A>, &'a FieldInfo);
+
+impl<A> Display for FieldReset<'_, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.0.field_reset(self.1) {
+            Some(value) if self.1.width == 1 => write!(f, "{value}"),
+            Some(value) => write!(f, "0x{value:X}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A run of bits within a register.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Segment<'a> {
+    Field(&'a FieldInfo),
+    Reserved { offset: usize, width: usize },
+}
+
+/// Iterates the fields of a register and the gaps between them, from the least significant
+/// bit upwards or, reversed, from the most significant bit downwards.
+///
+/// Fields overlapping an already emitted field are skipped.
+struct Segments<'a> {
+    fields: &'a [FieldInfo],
+    low: usize,
+    high: usize,
+}
+
+impl<'a> Segments<'a> {
+    fn new<A>(register: &RegisterInfo<A>) -> Self {
+        Self {
+            fields: register.fields,
+            low: 0,
+            high: register.bits(),
+        }
+    }
+
+    fn remaining(&self) -> impl Iterator<Item = &'a FieldInfo> {
+        let (low, high) = (self.low, self.high);
+        self.fields
+            .iter()
+            .filter(move |f| f.width > 0 && f.offset >= low && f.offset + f.width <= high)
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.low >= self.high {
+            return None;
+        }
+        let low = self.low;
+        match self.remaining().min_by_key(|f| f.offset) {
+            Some(field) if field.offset == low => {
+                self.low += field.width;
+                Some(Segment::Field(field))
+            }
+            next => {
+                self.low = next.map_or(self.high, |f| f.offset);
+                Some(Segment::Reserved {
+                    offset: low,
+                    width: self.low - low,
+                })
+            }
+        }
+    }
+}
+
+impl DoubleEndedIterator for Segments<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.low >= self.high {
+            return None;
+        }
+        let high = self.high;
+        match self.remaining().max_by_key(|f| f.offset + f.width) {
+            Some(field) if field.offset + field.width == high => {
+                self.high = field.offset;
+                Some(Segment::Field(field))
+            }
+            next => {
+                self.high = next.map_or(self.low, |f| f.offset + f.width);
+                Some(Segment::Reserved {
+                    offset: self.high,
+                    width: high - self.high,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::RegisterAddress8;
+
+    const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("WHO_AM_I", RegisterAddress8::new(0x0F), 1, Access::ReadOnly)
+            .with_reset(&[0x33]),
+        RegisterInfo::new("CTRL", RegisterAddress8::new(0x20), 2, Access::ReadWrite)
+            .with_reset(&[0x07, 0x80])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("EN", 0, 1, Access::ReadWrite),
+                FieldInfo::new("MODE", 1, 2, Access::ReadWrite),
+                FieldInfo::new("A|B", 12, 4, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("OUT", RegisterAddress8::new(0x28), 2, Access::ReadOnly),
+    ];
+
+    const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("TEST", REGISTERS);
+
+    #[test]
+    fn markdown() {
+        test_format::assert_display_fmt!(
+            Markdown::new(&MAP),
+            "# TEST\n\n\
+             | Register | Address | Size | Access | Reset |\n\
+             |----------|---------|------|--------|-------|\n\
+             | WHO_AM_I | 0x0F | 8 | R | 0x33 |\n\
+             | CTRL | 0x20 | 16 | RW | 0x8007 |\n\
+             | OUT | 0x28 | 16 | R |  |\n\
+             \n\
+             ## CTRL\n\n\
+             | Bits | Field | Access | Reset |\n\
+             |------|-------|--------|-------|\n\
+             | 15:12 | A\\|B | R | 0x8 |\n\
+             | 11:3 | reserved |  |  |\n\
+             | 2:1 | MODE | RW | 0x3 |\n\
+             | 0 | EN | RW | 1 |\n"
+        );
+    }
+
+    #[test]
+    fn html() {
+        const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new(
+            "<X>",
+            &[
+                RegisterInfo::new("WHO_AM_I", RegisterAddress8::new(0x0F), 1, Access::ReadOnly)
+                    .with_reset(&[0x33]),
+            ],
+        );
+        test_format::assert_display_fmt!(
+            Html::new(&MAP),
+            "<h1>&lt;X&gt;</h1>\n\
+             <table>\n\
+             <thead><tr><th>Register</th><th>Address</th><th>Size</th><th>Access</th><th>Reset</th></tr></thead>\n\
+             <tbody>\n\
+             <tr><td>WHO_AM_I</td><td>0x0F</td><td>8</td><td>R</td><td>0x33</td></tr>\n\
+             </tbody>\n\
+             </table>\n"
+        );
+    }
+
+    #[test]
+    fn wavedrom() {
+        test_format::assert_display_fmt!(
+            WaveDrom::new(&REGISTERS[1]),
+            r#"{"reg":[{"name":"EN","bits":1,"attr":"RW"},{"name":"MODE","bits":2,"attr":"RW"},{"bits":9},{"name":"A|B","bits":4,"attr":"R"}],"config":{"bits":16}}"#
+        );
+        test_format::assert_display_fmt!(
+            WaveDrom::new(&REGISTERS[2]),
+            r#"{"reg":[{"name":"OUT","bits":16,"attr":"R"}],"config":{"bits":16}}"#
+        );
+    }
+
+    #[test]
+    fn segments_skip_overlapping_fields() {
+        const FIELDS: &[FieldInfo] = &[
+            FieldInfo::new("A", 0, 4, Access::ReadWrite),
+            FieldInfo::new("B", 2, 4, Access::ReadWrite),
+        ];
+        let register = RegisterInfo::new("R", 0_u8, 1, Access::ReadWrite).with_fields(FIELDS);
+        let segments: Vec<_> = Segments::new(&register).collect();
+        assert_eq!(
+            segments,
+            [
+                Segment::Field(&FIELDS[0]),
+                Segment::Reserved {
+                    offset: 4,
+                    width: 4
+                }
+            ]
+        );
+    }
+}
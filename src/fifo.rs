@@ -0,0 +1,191 @@
+//! Streaming reads from FIFO data registers.
+//!
+//! A sensor FIFO is drained by reading its data register repeatedly; the device does not
+//! advance the register address while the FIFO is read, so a single burst transfers several
+//! frames. The `read_fifo` methods of [`I2CRegisterAccess`](crate::i2c::I2CRegisterAccess)
+//! and [`SPIRegisterAccess`](crate::spi::SPIRegisterAccess) fill a caller-provided slice of
+//! frames, each decoded from the register content with [`FromBits`]. The frames are
+//! transferred in bursts of up to [`MAX_BYTES`] bytes.
+//!
+//! `read_fifo_counted` first reads a register reporting the FIFO level, see [`FifoCount`],
+//! and then reads as many frames as are available and fit into the slice.
+
+use crate::backing::{ByteOrder, RegisterBacking};
+use crate::sizes::MAX_BYTES;
+use crate::FromBits;
+
+/// The unit of a [`FifoCount`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CountUnit {
+    /// The count is the number of frames, e.g. samples.
+    Frames,
+    /// The count is the number of bytes; incomplete frames are left in the FIFO.
+    Bytes,
+}
+
+/// A register reporting the number of entries in a FIFO.
+pub trait FifoCount {
+    /// The unit of the count.
+    const UNIT: CountUnit = CountUnit::Frames;
+
+    /// Returns the number of entries in the FIFO, in units of [`UNIT`](Self::UNIT).
+    fn count(&self) -> usize;
+
+    /// Returns the number of complete frames of `frame_bytes` bytes in the FIFO.
+    #[must_use]
+    fn frames(&self, frame_bytes: usize) -> usize {
+        match Self::UNIT {
+            CountUnit::Frames => self.count(),
+            CountUnit::Bytes => self.count() / frame_bytes.max(1),
+        }
+    }
+}
+
+/// Fills `frames` with frames of `frame_bytes` bytes, each burst read by `read`.
+pub(crate) fn read_frames<F, B, E, T>(
+    frames: &mut [F],
+    frame_bytes: usize,
+    order: ByteOrder,
+    mut read: T,
+) -> Result<(), E>
+where
+    F: FromBits<B>,
+    B: RegisterBacking,
+    T: FnMut(&mut [u8]) -> Result<(), E>,
+{
+    let mut buffer = [0_u8; MAX_BYTES];
+    let frame_bytes = frame_bytes.clamp(1, MAX_BYTES);
+    for chunk in frames.chunks_mut(MAX_BYTES / frame_bytes) {
+        let bytes = &mut buffer[..chunk.len() * frame_bytes];
+        read(bytes)?;
+        for (frame, bytes) in chunk.iter_mut().zip(bytes.chunks_exact(frame_bytes)) {
+            *frame = F::from_bits(B::from_register_bytes(bytes, order));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Level(u8);
+
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    struct Frame(u32);
+
+    impl FromBits<u32> for Frame {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl FifoCount for Level {
+        const UNIT: CountUnit = CountUnit::Bytes;
+
+        fn count(&self) -> usize {
+            self.0.into()
+        }
+    }
+
+    #[test]
+    fn frames_from_bytes() {
+        assert_eq!(Level(13).frames(6), 2);
+        assert_eq!(Level(13).frames(0), 13);
+    }
+
+    #[test]
+    fn reads_in_bursts() {
+        let mut frames = [Frame::default(); 40];
+        let mut bursts = 0;
+        let mut next = 0_u8;
+        let result: Result<(), ()> = read_frames(&mut frames, 3, ByteOrder::BigEndian, |bytes| {
+            bursts += 1;
+            for byte in bytes {
+                *byte = next;
+                next = next.wrapping_add(1);
+            }
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(bursts, 2);
+        assert_eq!(frames[0], Frame(0x00_0102));
+        assert_eq!(frames[21], Frame(0x3F_4041));
+    }
+
+    #[cfg(feature = "i2c")]
+    mod i2c {
+        use crate::fifo::{CountUnit, FifoCount};
+        use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegister, I2CRegisterAccess};
+        use crate::mock::{MockDevice, MockI2c};
+        use crate::register_address::RegisterAddress8;
+        use crate::sizes::{R1, R2};
+        use crate::test_support::test_register;
+
+        test_register!(
+            /// A 16-bit sample from the FIFO data register.
+            Sample(u16): R2
+        );
+
+        impl I2CRegister<DeviceAddress7, RegisterAddress8, R2> for Sample {
+            const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+            const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x74);
+            type Backing = u16;
+        }
+
+        test_register!(
+            /// The number of bytes in the FIFO.
+            FifoLevel(u8): R1
+        );
+
+        impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for FifoLevel {
+            const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+            const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x72);
+            type Backing = u8;
+        }
+
+        impl FifoCount for FifoLevel {
+            const UNIT: CountUnit = CountUnit::Bytes;
+
+            fn count(&self) -> usize {
+                self.0.into()
+            }
+        }
+
+        /// Constructs a device whose FIFO holds the bytes `1..=level`.
+        fn device(level: u8) -> I2CDevice<MockI2c> {
+            let mut mock = MockDevice::new::<RegisterAddress8>()
+                .with_auto_increment(false)
+                .on_read(RegisterAddress8::new(0x74), |registers| {
+                    let next = registers.byte(0x74) + 1;
+                    registers.set_byte(0x74, next);
+                    registers.set_byte(0x72, registers.byte(0x72) - 1);
+                });
+            mock.registers_mut().set_byte(0x72, level);
+            mock.registers_mut().set_byte(0x74, 1);
+            let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock);
+            I2CDevice::new(bus, DeviceAddress7::new(0x18))
+        }
+
+        #[test]
+        fn read_fifo() {
+            let mut samples = [Sample::default(); 3];
+            assert_eq!(device(8).read_fifo(&mut samples), Ok(()));
+            assert_eq!(samples, [Sample(0x0102), Sample(0x0304), Sample(0x0506)]);
+        }
+
+        #[test]
+        fn read_fifo_counted() {
+            let mut samples = [Sample::default(); 8];
+            let mut device = device(7);
+            let read = device.read_fifo_counted::<FifoLevel, _, _, _, _, _>(&mut samples);
+            assert_eq!(read, Ok(3));
+            assert_eq!(
+                samples[..3],
+                [Sample(0x0102), Sample(0x0304), Sample(0x0506)]
+            );
+            assert_eq!(device.read_register(), Ok(FifoLevel(1)));
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Registers that are a set of independent flags, declared with the `bitflags` crate.
+//!
+//! The [`flags_register!`](crate::flags_register) macro implements [`ToBits`](crate::ToBits),
+//! [`FromBits`](crate::FromBits), [`HardwareRegister`](crate::HardwareRegister) and
+//! [`FlagsRegister`] for a `bitflags!` type with `u8` bits, so that adding an `I2CRegister`
+//! or `SPIRegister` implementation makes it an `I2CRegister8` or `SPIRegister8`.
+//!
+//! Bits that do not correspond to a declared flag are handled according to [`UnknownBits`].
+//!
+//! ```
+//! use bitflags::bitflags;
+//! use hardware_registers::flags::UnknownBits;
+//! use hardware_registers::{flags_register, FromBits, ToBits};
+//!
+//! bitflags! {
+//!     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+//!     pub struct InterruptEnable: u8 {
+//!         const DATA_READY = 0b0000_0001;
+//!         const OVERRUN = 0b0000_0010;
+//!     }
+//! }
+//!
+//! flags_register!(InterruptEnable, UnknownBits::Retain);
+//!
+//! let flags = InterruptEnable::from_bits_ref(&0b1000_0001);
+//! assert!(flags.contains(InterruptEnable::DATA_READY));
+//! assert_eq!(flags.to_bits(), 0b1000_0001);
+//! ```
+
+#[cfg(any(feature = "i2c", feature = "spi"))]
+use crate::error::Error;
+use bitflags::Flags;
+
+/// How bits that do not correspond to a declared flag are handled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnknownBits {
+    /// Unknown bits are kept when reading and written back unchanged, e.g. reserved bits that
+    /// must keep their value.
+    Retain,
+    /// Unknown bits are discarded when reading and written as zero.
+    Truncate,
+    /// Unknown bits are kept, but `read_flags` fails with [`Error::Decode`] if any are set.
+    Reject,
+}
+
+/// A register that is a set of flags with `u8` bits.
+///
+/// Implemented by [`flags_register!`](crate::flags_register).
+pub trait FlagsRegister: Flags<Bits = u8> {
+    /// How bits that do not correspond to a declared flag are handled.
+    const UNKNOWN_BITS: UnknownBits;
+
+    /// Constructs the flags from the register content.
+    #[must_use]
+    fn decode(bits: u8) -> Self {
+        match Self::UNKNOWN_BITS {
+            UnknownBits::Retain | UnknownBits::Reject => Self::from_bits_retain(bits),
+            UnknownBits::Truncate => Self::from_bits_truncate(bits),
+        }
+    }
+
+    /// Returns the register content of the flags.
+    #[must_use]
+    fn encode(&self) -> u8 {
+        match Self::UNKNOWN_BITS {
+            UnknownBits::Retain | UnknownBits::Reject => self.bits(),
+            UnknownBits::Truncate => self.bits() & Self::all().bits(),
+        }
+    }
+}
+
+/// Fails with [`Error::Decode`] if unknown bits are set in flags that reject them.
+#[cfg(any(feature = "i2c", feature = "spi"))]
+pub(crate) fn check<F, E>(flags: F) -> Result<F, Error<E>>
+where
+    F: FlagsRegister,
+{
+    if F::UNKNOWN_BITS == UnknownBits::Reject && flags.bits() & !F::all().bits() != 0 {
+        Err(Error::Decode)
+    } else {
+        Ok(flags)
+    }
+}
+
+/// Implements [`FlagsRegister`] and the 8-bit register conversions for a `bitflags!` type.
+///
+/// The first argument is the type, the second its [`UnknownBits`] policy. See the
+/// [`flags`](crate::flags) module for an example.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+macro_rules! flags_register {
+    ($flags:ty, $unknown:expr) => {
+        impl $crate::flags::FlagsRegister for $flags {
+            const UNKNOWN_BITS: $crate::flags::UnknownBits = $unknown;
+        }
+
+        impl $crate::HardwareRegister<$crate::sizes::R1> for $flags {}
+
+        impl $crate::ToBits for $flags {
+            type Target = u8;
+
+            fn to_bits(&self) -> Self::Target {
+                $crate::flags::FlagsRegister::encode(self)
+            }
+        }
+
+        impl $crate::FromBits<u8> for $flags {
+            fn from_bits_ref(value: &u8) -> Self {
+                <Self as $crate::flags::FlagsRegister>::decode(*value)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromBits, ToBits};
+    use bitflags::bitflags;
+
+    bitflags! {
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        struct Retained: u8 {
+            const A = 0b01;
+            const B = 0b10;
+        }
+
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        struct Truncated: u8 {
+            const A = 0b01;
+            const B = 0b10;
+        }
+
+        #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+        struct Rejected: u8 {
+            const A = 0b01;
+            const B = 0b10;
+        }
+    }
+
+    flags_register!(Retained, UnknownBits::Retain);
+    flags_register!(Truncated, UnknownBits::Truncate);
+    flags_register!(Rejected, UnknownBits::Reject);
+
+    #[test]
+    fn unknown_bits() {
+        assert_eq!(Retained::from_bits_ref(&0x81).to_bits(), 0x81);
+        assert_eq!(Truncated::from_bits_ref(&0x81), Truncated::A);
+        assert_eq!(Truncated::from_bits_retain(0x82).to_bits(), 0x02);
+        assert_eq!(Rejected::from_bits_ref(&0x83).bits(), 0x83);
+    }
+
+    #[test]
+    #[cfg(any(feature = "i2c", feature = "spi"))]
+    fn check_rejects_unknown_bits() {
+        assert_eq!(check::<_, ()>(Rejected::A), Ok(Rejected::A));
+        assert_eq!(
+            check::<_, ()>(Rejected::from_bits_ref(&0x81)),
+            Err(Error::Decode)
+        );
+        assert_eq!(
+            check::<_, ()>(Retained::from_bits_ref(&0x81)),
+            Ok(Retained::from_bits_retain(0x81))
+        );
+    }
+
+    #[cfg(feature = "i2c")]
+    mod i2c {
+        use super::*;
+        use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegister, I2CRegisterAccess};
+        use crate::mock::{MockDevice, MockI2c};
+        use crate::register_address::RegisterAddress8;
+        use crate::sizes::R1;
+        use crate::WritableHardwareRegister;
+
+        impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for Rejected {
+            const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+            const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x22);
+            type Backing = u8;
+        }
+
+        impl WritableHardwareRegister<R1> for Rejected {}
+
+        #[test]
+        fn read_flags() {
+            let bus = MockI2c::new().with_device(
+                DeviceAddress7::new(0x18),
+                MockDevice::new::<RegisterAddress8>(),
+            );
+            let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+            assert_eq!(device.write_register(&Rejected::B), Ok(()));
+            assert_eq!(device.read_flags(), Ok(Rejected::B));
+            assert_eq!(
+                device.write_register(&Rejected::from_bits_retain(0x42)),
+                Ok(())
+            );
+            assert_eq!(device.read_flags::<Rejected>(), Err(Error::Decode));
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Formatting helpers for register values.
+//!
+//! [`RegisterValue`] renders a decoded register next to its address, using the register's
+//! own [`Debug`](core::fmt::Debug) implementation or, with the `defmt` crate feature enabled,
+//! its [`defmt::Format`](https://docs.rs/defmt/latest/defmt/trait.Format.html) implementation.
+//! Register types render their decoded fields by implementing or deriving the respective trait.
+//!
+//! ```
+//! use hardware_registers::format::RegisterValue;
+//! use hardware_registers::register_address::RegisterAddress8;
+//!
+//! #[derive(Debug)]
+//! struct CtrlReg1 {
+//!     x_enabled: bool,
+//! }
+//!
+//! let value = CtrlReg1 { x_enabled: true };
+//! let formatted = RegisterValue::new(RegisterAddress8::new(0x20), &value);
+//! assert_eq!(
+//!     format!("{formatted:?}"),
+//!     "0x20 (00100000): CtrlReg1 { x_enabled: true }"
+//! );
+//! ```
+//!
+//! For a field-by-field dump, register types implement [`DecodeRegister`] and are printed
+//! through [`DecodedRegister`], which shows the register's name, address and raw backing value
+//! followed by each decoded field. Registers generated by the `codegen` feature implement the
+//! trait automatically.
+//!
+//! ```
+//! use hardware_registers::format::{DecodeRegister, DecodedRegister, FieldValue, FieldVisitor};
+//! use hardware_registers::register_address::RegisterAddress8;
+//! use hardware_registers::ToBits;
+//!
+//! struct CtrlReg1(u8);
+//!
+//! impl ToBits for CtrlReg1 {
+//!     type Target = u8;
+//!
+//!     fn to_bits(&self) -> u8 {
+//!         self.0
+//!     }
+//! }
+//!
+//! impl DecodeRegister for CtrlReg1 {
+//!     const NAME: &'static str = "CTRL_REG1";
+//!
+//!     fn decode(&self, visitor: &mut dyn FieldVisitor) {
+//!         let odr = match self.0 >> 4 {
+//!             4 => FieldValue::Named("50Hz"),
+//!             odr => FieldValue::Unsigned(odr.into()),
+//!         };
+//!         visitor.field("ODR", odr);
+//!         visitor.field("Xen", FieldValue::Bit(self.0 & 0x1 != 0));
+//!     }
+//! }
+//!
+//! let value = CtrlReg1(0x47);
+//! let decoded = DecodedRegister::new(RegisterAddress8::new(0x20), &value);
+//! assert_eq!(
+//!     format!("{decoded:?}"),
+//!     "CTRL_REG1 @ 0x20 = 0x47 { ODR: 50Hz, Xen: 1 }"
+//! );
+//! ```
+
+use crate::backing::{ByteOrder, RegisterBacking};
+use crate::register_address::RegisterAddress;
+use crate::sizes::MAX_BYTES;
+use crate::ToBits;
+use core::mem::size_of;
+
+/// Formats a register value together with its address.
+#[derive(Copy, Clone)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisterValue<'a, A, T> {
+    address: A,
+    value: &'a T,
+}
+
+impl<'a, A, T> RegisterValue<'a, A, T> {
+    /// Constructs a new formatting adapter for the value of the register at `address`.
+    pub const fn new(address: A, value: &'a T) -> Self {
+        Self { address, value }
+    }
+}
+
+impl<A, T> core::fmt::Debug for RegisterValue<'_, A, T>
+where
+    A: core::fmt::Debug,
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.address, f)?;
+        f.write_str(": ")?;
+        core::fmt::Debug::fmt(self.value, f)
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<A, T> defmt::Format for RegisterValue<'_, A, T>
+where
+    A: defmt::Format,
+    T: defmt::Format,
+{
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{}: {}", self.address, self.value);
+    }
+}
+
+/// The decoded value of a register field.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FieldValue {
+    /// A single-bit flag, shown as `0` or `1`.
+    Bit(bool),
+    /// A numeric field, shown in decimal.
+    Unsigned(u128),
+    /// A field with a symbolic meaning, e.g. `50Hz`.
+    Named(&'static str),
+}
+
+impl core::fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Self::Bit(bit) => write!(f, "{}", u8::from(bit)),
+            Self::Unsigned(value) => write!(f, "{value}"),
+            Self::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for FieldValue {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match *self {
+            Self::Bit(bit) => defmt::write!(f, "{=u8}", u8::from(bit)),
+            Self::Unsigned(value) => defmt::write!(f, "{=u128}", value),
+            Self::Named(name) => defmt::write!(f, "{=str}", name),
+        }
+    }
+}
+
+/// Receives the decoded fields of a register.
+pub trait FieldVisitor {
+    /// Visits a single field.
+    fn field(&mut self, name: &'static str, value: FieldValue);
+}
+
+/// A register that can describe its fields for debugging output.
+pub trait DecodeRegister {
+    /// The name of the register, e.g. `CTRL_REG1`.
+    const NAME: &'static str;
+
+    /// Passes each field of the register to the visitor, in display order.
+    fn decode(&self, visitor: &mut dyn FieldVisitor);
+}
+
+/// Formats a register with its name, address, raw backing value and decoded fields.
+///
+/// The raw value is shown in hexadecimal with all bytes of the backing type, most significant
+/// byte first; byte array backings are shown in transfer order.
+#[derive(Copy, Clone)]
+pub struct DecodedRegister<'a, A, T> {
+    address: A,
+    value: &'a T,
+}
+
+impl<'a, A, T> DecodedRegister<'a, A, T> {
+    /// Constructs a new decoding adapter for the value of the register at `address`.
+    pub const fn new(address: A, value: &'a T) -> Self {
+        Self { address, value }
+    }
+}
+
+impl<A, T> DecodedRegister<'_, A, T>
+where
+    T: ToBits,
+    T::Target: RegisterBacking,
+{
+    fn raw_bytes(&self, buffer: &mut [u8; MAX_BYTES]) -> usize {
+        let len = size_of::<T::Target>().min(MAX_BYTES);
+        self.value
+            .to_bits()
+            .to_register_bytes(&mut buffer[..len], ByteOrder::BigEndian);
+        len
+    }
+}
+
+impl<A, T> core::fmt::Debug for DecodedRegister<'_, A, T>
+where
+    A: RegisterAddress,
+    T: DecodeRegister + ToBits,
+    T::Target: RegisterBacking,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        struct Visitor<'a, 'b> {
+            f: &'a mut core::fmt::Formatter<'b>,
+            result: core::fmt::Result,
+            first: bool,
+        }
+
+        impl FieldVisitor for Visitor<'_, '_> {
+            fn field(&mut self, name: &'static str, value: FieldValue) {
+                let separator = if self.first { " { " } else { ", " };
+                self.first = false;
+                if self.result.is_ok() {
+                    self.result = write!(self.f, "{separator}{name}: {value}");
+                }
+            }
+        }
+
+        write!(
+            f,
+            "{} @ 0x{:0width$X} = 0x",
+            T::NAME,
+            self.address.value(),
+            width = A::ADDR_BYTES * 2
+        )?;
+        let mut buffer = [0; MAX_BYTES];
+        let len = self.raw_bytes(&mut buffer);
+        for byte in &buffer[..len] {
+            write!(f, "{byte:02X}")?;
+        }
+
+        let mut visitor = Visitor {
+            f,
+            result: Ok(()),
+            first: true,
+        };
+        self.value.decode(&mut visitor);
+        visitor.result?;
+        if !visitor.first {
+            visitor.f.write_str(" }")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl<A, T> defmt::Format for DecodedRegister<'_, A, T>
+where
+    A: RegisterAddress,
+    T: DecodeRegister + ToBits,
+    T::Target: RegisterBacking,
+{
+    fn format(&self, f: defmt::Formatter<'_>) {
+        struct Visitor<'a> {
+            f: defmt::Formatter<'a>,
+            first: bool,
+        }
+
+        impl FieldVisitor for Visitor<'_> {
+            fn field(&mut self, name: &'static str, value: FieldValue) {
+                let separator = if self.first { " { " } else { ", " };
+                self.first = false;
+                defmt::write!(self.f, "{=str}{=str}: {}", separator, name, value);
+            }
+        }
+
+        defmt::write!(f, "{=str} @ 0x", T::NAME);
+        let address = self.address.value().to_be_bytes();
+        for byte in &address[address.len().saturating_sub(A::ADDR_BYTES)..] {
+            defmt::write!(f, "{=u8:02X}", *byte);
+        }
+        defmt::write!(f, "{=str}", " = 0x");
+        let mut buffer = [0; MAX_BYTES];
+        let len = self.raw_bytes(&mut buffer);
+        for byte in &buffer[..len] {
+            defmt::write!(f, "{=u8:02X}", *byte);
+        }
+
+        let mut visitor = Visitor { f, first: true };
+        self.value.decode(&mut visitor);
+        if !visitor.first {
+            defmt::write!(f, "{=str}", " }");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::RegisterAddress16;
+
+    struct Status([u8; 2]);
+
+    impl ToBits for Status {
+        type Target = [u8; 2];
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl DecodeRegister for Status {
+        const NAME: &'static str = "STATUS";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("READY", FieldValue::Bit(self.0[1] & 0x1 != 0));
+            visitor.field("COUNT", FieldValue::Unsigned(self.0[0].into()));
+        }
+    }
+
+    struct Id(u32);
+
+    impl ToBits for Id {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl DecodeRegister for Id {
+        const NAME: &'static str = "ID";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    #[test]
+    fn decoded() {
+        test_format::assert_debug_fmt!(
+            DecodedRegister::new(RegisterAddress16::new(0x0102), &Status([0x12, 0x01])),
+            "STATUS @ 0x0102 = 0x1201 { READY: 1, COUNT: 18 }"
+        );
+        test_format::assert_debug_fmt!(
+            DecodedRegister::new(0x0F_u8, &Id(0x00AB_CDEF)),
+            "ID @ 0x0F = 0x00ABCDEF"
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let value = 0x1234_u16;
+        test_format::assert_debug_fmt!(
+            RegisterValue::new(RegisterAddress16::new(0x0102), &value),
+            "0x0102 (0000000100000010): 4660"
+        );
+    }
+}
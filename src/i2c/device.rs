@@ -0,0 +1,234 @@
+use crate::access::{encode_address, encode_value, RegisterAccess};
+use crate::addressing::AddressingMode;
+use crate::command::CommandAccess;
+use crate::i2c::{DeviceAddress7, I2CBusAddress, I2CRegisterAccess};
+use crate::register_address::RegisterAddress;
+use embedded_hal::i2c::{I2c, Operation};
+
+/// A device on an I²C bus, providing access to its registers.
+///
+/// Register reads are performed as a write of the register address followed by a
+/// repeated-start read; register writes transmit the register address followed by the data
+/// in a single write. Multi-byte transfers are split or flagged according to the device's
+/// [`AddressingMode`], which defaults to address auto-increment.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct I2CDevice<B, D = DeviceAddress7> {
+    bus: B,
+    address: D,
+    addressing: AddressingMode,
+}
+
+impl<B, D> I2CDevice<B, D> {
+    /// Constructs a new device at the specified address.
+    pub const fn new(bus: B, address: D) -> Self {
+        Self {
+            bus,
+            address,
+            addressing: AddressingMode::AutoIncrement,
+        }
+    }
+
+    /// Sets how the device advances the register address during multi-byte transfers.
+    #[must_use]
+    pub const fn with_addressing(mut self, addressing: AddressingMode) -> Self {
+        self.addressing = addressing;
+        self
+    }
+
+    /// Returns the device address.
+    pub const fn address(&self) -> &D {
+        &self.address
+    }
+
+    /// Returns how the device advances the register address during multi-byte transfers.
+    pub const fn addressing(&self) -> AddressingMode {
+        self.addressing
+    }
+
+    /// Consumes self and returns the underlying bus.
+    pub fn release(self) -> B {
+        self.bus
+    }
+}
+
+impl<B, D> RegisterAccess for I2CDevice<B, D>
+where
+    D: I2CBusAddress,
+    B: I2c<D::Mode>,
+{
+    type Error = B::Error;
+
+    fn device_address(&self) -> Option<u16> {
+        Some(self.address.to_bus_address().into())
+    }
+
+    fn read_raw<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        for segment in self.addressing.segments(address.value(), buffer.len()) {
+            let (mut register, len) = encode_value::<A>(segment.address);
+            register[0] |= segment.flag;
+            self.bus.write_read(
+                self.address.to_bus_address(),
+                &register[..len],
+                &mut buffer[segment.range],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_raw<A>(&mut self, address: A, data: &[u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        for segment in self.addressing.segments(address.value(), data.len()) {
+            let (mut register, len) = encode_value::<A>(segment.address);
+            register[0] |= segment.flag;
+            self.bus.transaction(
+                self.address.to_bus_address(),
+                &mut [
+                    Operation::Write(&register[..len]),
+                    Operation::Write(&data[segment.range]),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    fn read_raw_burst<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        let (register, len) = encode_address(&address);
+        self.bus
+            .write_read(self.address.to_bus_address(), &register[..len], buffer)
+    }
+}
+
+impl<B, D> I2CRegisterAccess for I2CDevice<B, D>
+where
+    D: I2CBusAddress,
+    B: I2c<D::Mode>,
+{
+    type DeviceAddress = D;
+}
+
+impl<B, D> CommandAccess for I2CDevice<B, D>
+where
+    D: I2CBusAddress,
+    B: I2c<D::Mode>,
+{
+    type Error = B::Error;
+
+    fn write_command(&mut self, command: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(self.address.to_bus_address(), command)
+    }
+
+    fn read_response(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus.read(self.address.to_bus_address(), buffer)
+    }
+
+    fn transfer_command(&mut self, command: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus
+            .write_read(self.address.to_bus_address(), command, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i2c::{I2CRegister, RegisterAddress8};
+    use crate::sizes::R2;
+    use crate::test_support::test_register;
+    use embedded_hal::i2c::{ErrorKind, ErrorType};
+
+    /// A two-register device with auto-increment.
+    #[derive(Default)]
+    struct Bus {
+        registers: [u8; 2],
+        pointer: usize,
+    }
+
+    impl ErrorType for Bus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c for Bus {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            assert_eq!(address, 0x18);
+            let mut first = true;
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => {
+                        for byte in &**bytes {
+                            if first {
+                                self.pointer = usize::from(*byte - 0x20);
+                                first = false;
+                            } else {
+                                self.registers[self.pointer] = *byte;
+                                self.pointer += 1;
+                            }
+                        }
+                    }
+                    Operation::Read(buffer) => {
+                        for byte in buffer.iter_mut() {
+                            *byte = self.registers[self.pointer];
+                            self.pointer += 1;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    test_register!(Threshold(u16): R2, writable);
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R2> for Threshold {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        const RESET_VALUE: Option<u16> = Some(0x0102);
+        type Backing = u16;
+    }
+
+    #[test]
+    fn write() {
+        let mut device = I2CDevice::new(Bus::default(), DeviceAddress7::new(0x18));
+        assert_eq!(device.write_register(&Threshold(0x1234)), Ok(()));
+        assert_eq!(device.release().registers, [0x12, 0x34]);
+    }
+
+    #[test]
+    fn read() {
+        let bus = Bus {
+            registers: [0xAB, 0xCD],
+            pointer: 0,
+        };
+        let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+        assert_eq!(device.read_register(), Ok(Threshold(0xABCD)));
+    }
+
+    #[test]
+    fn modify() {
+        let mut device = I2CDevice::new(Bus::default(), DeviceAddress7::new(0x18));
+        let value = device.modify_register(|r: Threshold| Threshold(r.0 | 0x8001));
+        assert_eq!(value, Ok(Threshold(0x8001)));
+        assert_eq!(device.release().registers, [0x80, 0x01]);
+    }
+
+    #[test]
+    fn reset() {
+        assert_eq!(Threshold::from_reset_value(), Some(Threshold(0x0102)));
+
+        let mut device = I2CDevice::new(Bus::default(), DeviceAddress7::new(0x18));
+        let value = device.reset_register::<Threshold, _, _>();
+        assert_eq!(value, Ok(Some(Threshold(0x0102))));
+        assert_eq!(device.release().registers, [0x01, 0x02]);
+    }
+}
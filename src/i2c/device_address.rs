@@ -1,4 +1,7 @@
+use crate::error::Error;
+use core::convert::Infallible;
 use core::ops::Deref;
+use embedded_hal::i2c::{AddressMode, SevenBitAddress, TenBitAddress};
 
 /// A device address.
 pub trait DeviceAddress {
@@ -12,6 +15,15 @@ pub trait DeviceAddress {
     }
 }
 
+/// A device address that can be used on an [`embedded_hal::i2c::I2c`] bus.
+pub trait I2CBusAddress: DeviceAddress + Copy {
+    /// The corresponding embedded-hal address mode.
+    type Mode: AddressMode + Into<u16>;
+
+    /// Converts the address into its embedded-hal representation.
+    fn to_bus_address(self) -> Self::Mode;
+}
+
 /// An 7-bit device address.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 #[allow(clippy::module_name_repetitions)]
@@ -32,12 +44,29 @@ impl DeviceAddress7 {
     pub const fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Indicates whether the address is reserved by the I²C specification.
+    ///
+    /// The ranges `0x00..=0x07` (general call, START byte, CBUS, HS-mode master codes) and
+    /// `0x78..=0x7F` (10-bit addressing, device ID) cannot be assigned to devices.
+    #[must_use]
+    pub const fn is_reserved(self) -> bool {
+        self.0 < 0x08 || self.0 > 0x77
+    }
 }
 
 impl DeviceAddress for DeviceAddress7 {
     const ADDR_BITS: usize = 7;
 }
 
+impl I2CBusAddress for DeviceAddress7 {
+    type Mode = SevenBitAddress;
+
+    fn to_bus_address(self) -> Self::Mode {
+        self.0
+    }
+}
+
 impl From<u8> for DeviceAddress7 {
     fn from(value: u8) -> Self {
         Self::new(value)
@@ -70,6 +99,14 @@ impl DeviceAddress for DeviceAddress10 {
     const ADDR_BITS: usize = 10;
 }
 
+impl I2CBusAddress for DeviceAddress10 {
+    type Mode = TenBitAddress;
+
+    fn to_bus_address(self) -> Self::Mode {
+        self.0
+    }
+}
+
 impl From<u8> for DeviceAddress10 {
     fn from(value: u8) -> Self {
         Self(value.into())
@@ -89,12 +126,14 @@ impl From<DeviceAddress7> for DeviceAddress10 {
 }
 
 impl TryFrom<DeviceAddress10> for DeviceAddress7 {
-    type Error = <u16 as TryInto<u8>>::Error;
+    type Error = Error<Infallible>;
 
+    /// Converts the address, failing with [`Error::AddressOutOfRange`] if it exceeds 7 bits.
     fn try_from(value: DeviceAddress10) -> Result<Self, Self::Error> {
-        let value = value.into_inner();
-        let addr_8: u8 = value.try_into()?;
-        Ok(Self(addr_8))
+        match u8::try_from(value.into_inner()) {
+            Ok(address) if address <= 0x7F => Ok(Self(address)),
+            _ => Err(Error::AddressOutOfRange),
+        }
     }
 }
 
@@ -144,10 +183,52 @@ impl core::fmt::Display for DeviceAddress10 {
     }
 }
 
+#[cfg(feature = "serde")]
+impl_serde_address!(
+    DeviceAddress7,
+    u8,
+    serialize_u8,
+    0b0111_1111,
+    "a 7-bit device address"
+);
+#[cfg(feature = "serde")]
+impl_serde_address!(
+    DeviceAddress10,
+    u16,
+    serialize_u16,
+    0b11_1111_1111,
+    "a 10-bit device address"
+);
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for DeviceAddress7 {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "0x{=u8:02X} ({=u8:07b})", self.0, self.0);
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for DeviceAddress10 {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "0x{=u16:03X} ({=u16:010b})", self.0, self.0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn dev7_reserved() {
+        assert!(DeviceAddress7::new(0x00).is_reserved());
+        assert!(DeviceAddress7::new(0x07).is_reserved());
+        assert!(!DeviceAddress7::new(0x08).is_reserved());
+        assert!(!DeviceAddress7::new(0x77).is_reserved());
+        assert!(DeviceAddress7::new(0x78).is_reserved());
+    }
+
     #[test]
     fn dev7_from_u8() {
         let addr = DeviceAddress7::from(0b111_0000);
@@ -187,6 +268,40 @@ mod tests {
         assert_eq!(addr.into_inner(), 0b0000_0000_0111_0000);
     }
 
+    #[test]
+    fn dev7_try_from_dev10() {
+        assert_eq!(
+            DeviceAddress7::try_from(DeviceAddress10::new(0x7F)),
+            Ok(DeviceAddress7::new(0x7F))
+        );
+        assert_eq!(
+            DeviceAddress7::try_from(DeviceAddress10::new(0x80)),
+            Err(Error::AddressOutOfRange)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dev7_serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+        assert_tokens(&DeviceAddress7::new(0x18), &[Token::U8(0x18)]);
+        assert_de_tokens_error::<DeviceAddress7>(
+            &[Token::U8(0x80)],
+            "invalid value: integer `128`, expected a 7-bit device address",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dev10_serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+        assert_tokens(&DeviceAddress10::new(0x3FF), &[Token::U16(0x3FF)]);
+        assert_de_tokens_error::<DeviceAddress10>(
+            &[Token::U16(0x400)],
+            "invalid value: integer `1024`, expected a 10-bit device address",
+        );
+    }
+
     #[test]
     fn dev7_debug() {
         let addr = DeviceAddress7::new(0b111_0000);
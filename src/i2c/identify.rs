@@ -0,0 +1,303 @@
+use crate::backing::RegisterBacking;
+use crate::i2c::{I2CBusAddress, I2CDevice, I2CRegister, I2CRegisterAccess, RegisterAddress};
+use crate::sizes::RegisterSizeInformation;
+use embedded_hal::i2c::{Error, ErrorKind, ErrorType, I2c};
+
+/// An I²C register identifying the device, such as `WHO_AM_I` or `CHIP_ID`.
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use hardware_registers::i2c::{DeviceAddress7, I2CRegister, IdentityRegister, RegisterAddress8};
+/// use hardware_registers::sizes::R1;
+/// use hardware_registers::{FromBits, HardwareRegister, ToBits};
+///
+/// #[derive(Debug, Copy, Clone)]
+/// struct WhoAmI(u8);
+///
+/// impl HardwareRegister<R1> for WhoAmI {}
+///
+/// impl ToBits for WhoAmI {
+///     type Target = u8;
+///
+///     fn to_bits(&self) -> u8 {
+///         self.0
+///     }
+/// }
+///
+/// impl FromBits<u8> for WhoAmI {
+///     fn from_bits_ref(value: &u8) -> Self {
+///         Self(*value)
+///     }
+/// }
+///
+/// impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+///     const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+///     const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x0F);
+///     type Backing = u8;
+/// }
+///
+/// impl IdentityRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+///     const EXPECTED: &'static [u8] = &[0x33];
+///     const ALTERNATE_DEVICE_ADDRESSES: &'static [DeviceAddress7] = &[DeviceAddress7::new(0x19)];
+/// }
+///
+/// /// Gets the address of an attached accelerometer, if any.
+/// fn find_accelerometer<B: I2c>(bus: &mut B) -> Result<Option<DeviceAddress7>, B::Error> {
+///     WhoAmI::probe(bus)
+/// }
+/// ```
+pub trait IdentityRegister<D, R, Size>: I2CRegister<D, R, Size>
+where
+    D: I2CBusAddress + PartialEq + 'static,
+    R: RegisterAddress,
+    Size: RegisterSizeInformation,
+    <Self as I2CRegister<D, R, Size>>::Backing: 'static,
+{
+    /// The register values identifying the device, e.g. one per silicon revision.
+    const EXPECTED: &'static [<Self as I2CRegister<D, R, Size>>::Backing];
+
+    /// Further device addresses to probe after the
+    /// [`DEFAULT_DEVICE_ADDRESS`](I2CRegister::DEFAULT_DEVICE_ADDRESS), e.g. for a strapped
+    /// address pin.
+    const ALTERNATE_DEVICE_ADDRESSES: &'static [D] = &[];
+
+    /// Indicates whether the register value is one of the [`EXPECTED`](Self::EXPECTED) values.
+    #[must_use]
+    fn is_expected(&self) -> bool
+    where
+        <Self as I2CRegister<D, R, Size>>::Backing: PartialEq,
+    {
+        Self::EXPECTED.contains(&self.to_bits())
+    }
+
+    /// Reads the register at the default and all alternate device addresses and returns the
+    /// first address at which it holds an expected value.
+    ///
+    /// Addresses that are not acknowledged or hold a different value are skipped.
+    ///
+    /// # Errors
+    /// Returns the first bus error other than a missing acknowledge.
+    fn probe<B>(bus: &mut B) -> Result<Option<D>, B::Error>
+    where
+        B: I2c<D::Mode>,
+        <Self as I2CRegister<D, R, Size>>::Backing: RegisterBacking + PartialEq,
+    {
+        let alternates = Self::ALTERNATE_DEVICE_ADDRESSES
+            .iter()
+            .copied()
+            .filter(|address| *address != Self::DEFAULT_DEVICE_ADDRESS);
+        for address in core::iter::once(Self::DEFAULT_DEVICE_ADDRESS).chain(alternates) {
+            let mut device = I2CDevice::new(&mut *bus, address);
+            match device.read_register::<Self, R, Size>() {
+                Ok(value) if value.is_expected() => return Ok(Some(address)),
+                Ok(_) => {}
+                Err(error) if matches!(error.kind(), ErrorKind::NoAcknowledge(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A device that can be identified on a bus, for use with [`identify`].
+pub struct KnownDevice<B, D>
+where
+    B: ErrorType,
+{
+    /// The device name.
+    pub name: &'static str,
+    probe: fn(&mut B) -> Result<Option<D>, B::Error>,
+}
+
+impl<B, D> KnownDevice<B, D>
+where
+    B: ErrorType,
+{
+    /// Constructs a known device from its name and probe function, usually
+    /// [`IdentityRegister::probe`] of the device's identity register.
+    #[must_use]
+    pub const fn new(name: &'static str, probe: fn(&mut B) -> Result<Option<D>, B::Error>) -> Self {
+        Self { name, probe }
+    }
+}
+
+impl<B, D> core::fmt::Debug for KnownDevice<B, D>
+where
+    B: ErrorType,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KnownDevice")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A device found by [`identify`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Identified<D> {
+    /// The name of the [`KnownDevice`].
+    pub name: &'static str,
+    /// The address the device responded at.
+    pub address: D,
+}
+
+/// Probes each known device in turn and yields those that are attached.
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use hardware_registers::i2c::{identify, DeviceAddress7, KnownDevice};
+///
+/// fn probe_lis3dh<B: I2c>(bus: &mut B) -> Result<Option<DeviceAddress7>, B::Error> {
+///     // Usually the `IdentityRegister::probe` of the device's identity register.
+///     # let _ = bus;
+///     Ok(Some(DeviceAddress7::new(0x18)))
+/// }
+///
+/// fn list_devices<B: I2c>(bus: &mut B) -> Result<(), B::Error> {
+///     let known = [KnownDevice::new("LIS3DH", probe_lis3dh::<B>)];
+///     for device in identify(bus, &known) {
+///         let device = device?;
+///         assert_eq!((device.name, device.address), ("LIS3DH", DeviceAddress7::new(0x18)));
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn identify<'a, B, D>(bus: &'a mut B, devices: &'a [KnownDevice<B, D>]) -> Identify<'a, B, D>
+where
+    B: ErrorType,
+{
+    Identify {
+        bus,
+        devices: devices.iter(),
+    }
+}
+
+/// An iterator over the attached devices, created by [`identify`].
+///
+/// Yields an error if probing a device fails; probing continues with the next device.
+pub struct Identify<'a, B, D>
+where
+    B: ErrorType,
+{
+    bus: &'a mut B,
+    devices: core::slice::Iter<'a, KnownDevice<B, D>>,
+}
+
+impl<B, D> core::fmt::Debug for Identify<'_, B, D>
+where
+    B: ErrorType,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Identify")
+            .field("remaining", &self.devices.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B, D> Iterator for Identify<'_, B, D>
+where
+    B: ErrorType,
+{
+    type Item = Result<Identified<D>, B::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for device in self.devices.by_ref() {
+            match (device.probe)(self.bus) {
+                Ok(Some(address)) => {
+                    return Some(Ok(Identified {
+                        name: device.name,
+                        address,
+                    }))
+                }
+                Ok(None) => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i2c::{DeviceAddress7, RegisterAddress8};
+    use crate::mock::{MockDevice, MockI2c};
+    use crate::sizes::R1;
+    use crate::test_support::test_register;
+
+    test_register!(WhoAmI(u8): R1);
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x0F);
+        type Backing = u8;
+    }
+
+    impl IdentityRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+        const EXPECTED: &'static [u8] = &[0x33, 0x34];
+        const ALTERNATE_DEVICE_ADDRESSES: &'static [DeviceAddress7] =
+            &[DeviceAddress7::new(0x18), DeviceAddress7::new(0x19)];
+    }
+
+    test_register!(ChipId(u8): R1);
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for ChipId {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x76);
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0xD0);
+        type Backing = u8;
+    }
+
+    impl IdentityRegister<DeviceAddress7, RegisterAddress8, R1> for ChipId {
+        const EXPECTED: &'static [u8] = &[0x60];
+    }
+
+    fn bus(devices: &[(u8, u8, u8)]) -> MockI2c {
+        let mut bus = MockI2c::new();
+        for (address, register, value) in devices {
+            let mut device = MockDevice::new::<RegisterAddress8>();
+            device
+                .registers_mut()
+                .set(RegisterAddress8::new(*register), &[*value]);
+            bus.attach(DeviceAddress7::new(*address), device);
+        }
+        bus
+    }
+
+    #[test]
+    fn probe_tries_alternate_addresses() {
+        let mut bus = bus(&[(0x19, 0x0F, 0x34)]);
+        assert_eq!(WhoAmI::probe(&mut bus), Ok(Some(DeviceAddress7::new(0x19))));
+    }
+
+    #[test]
+    fn probe_rejects_unexpected_identity() {
+        let mut bus = bus(&[(0x18, 0x0F, 0x44)]);
+        assert_eq!(WhoAmI::probe(&mut bus), Ok(None));
+    }
+
+    #[test]
+    fn identify_lists_attached_devices() {
+        let known = [
+            KnownDevice::new("LIS3DH", WhoAmI::probe),
+            KnownDevice::new("BME280", ChipId::probe),
+        ];
+        let mut bus = bus(&[(0x76, 0xD0, 0x60), (0x19, 0x0F, 0x33)]);
+        let found: Result<Vec<_>, _> = identify(&mut bus, &known).collect();
+        assert_eq!(
+            found,
+            Ok(vec![
+                Identified {
+                    name: "LIS3DH",
+                    address: DeviceAddress7::new(0x19)
+                },
+                Identified {
+                    name: "BME280",
+                    address: DeviceAddress7::new(0x76)
+                }
+            ])
+        );
+
+        let mut empty = MockI2c::new();
+        assert_eq!(identify(&mut empty, &known).count(), 0);
+    }
+}
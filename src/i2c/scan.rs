@@ -0,0 +1,289 @@
+use crate::i2c::{DeviceAddress10, DeviceAddress7, I2CBusAddress};
+use embedded_hal::i2c::{Error, ErrorKind, I2c, SevenBitAddress, TenBitAddress};
+
+/// The transfer used to probe whether a device responds at an address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Probe {
+    /// Writes zero bytes, i.e. only transmits the address ("SMBus quick write").
+    ///
+    /// This does not transfer any data, but some devices do not acknowledge empty writes
+    /// and a few write-only devices may latch state from it.
+    QuickWrite,
+    /// Reads a single byte.
+    ///
+    /// Unlike a quick write, this cannot be mistaken for a write by devices such as EEPROMs,
+    /// but it may have side effects on devices that clear status on read.
+    ReadByte,
+    /// Reads a single byte from the typical EEPROM ranges `0x30..=0x37` and `0x50..=0x5F`
+    /// and uses a quick write everywhere else, like `i2cdetect` does.
+    Auto,
+}
+
+impl Probe {
+    /// Selects the transfer to use at the specified address.
+    const fn resolve(self, address: u16, ten_bit: bool) -> Self {
+        match self {
+            Self::Auto if !ten_bit && matches!(address, 0x30..=0x37 | 0x50..=0x5F) => {
+                Self::ReadByte
+            }
+            Self::Auto => Self::QuickWrite,
+            probe => probe,
+        }
+    }
+}
+
+/// The addresses that responded during a bus scan, in ascending order.
+///
+/// Holds at most `N` addresses; further responding devices only mark the result as
+/// [truncated](Self::is_truncated).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScanResult<A, const N: usize> {
+    addresses: [A; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<A, const N: usize> ScanResult<A, N>
+where
+    A: Copy + Eq + From<u8>,
+{
+    fn new() -> Self {
+        Self {
+            addresses: [A::from(0); N],
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, address: A) {
+        if self.len < N {
+            self.addresses[self.len] = address;
+            self.len += 1;
+        } else {
+            self.truncated = true;
+        }
+    }
+
+    /// Gets the responding addresses.
+    #[must_use]
+    pub fn as_slice(&self) -> &[A] {
+        &self.addresses[..self.len]
+    }
+
+    /// Iterates the responding addresses.
+    pub fn iter(&self) -> core::slice::Iter<'_, A> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the number of responding addresses that were recorded.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indicates whether no device responded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Indicates whether more devices responded than the result could hold.
+    #[must_use]
+    pub const fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Indicates whether a device responded at the specified address.
+    #[must_use]
+    pub fn contains(&self, address: A) -> bool {
+        self.as_slice().contains(&address)
+    }
+
+    /// Iterates the expected addresses at which no device responded.
+    ///
+    /// On a [truncated](Self::is_truncated) result, addresses beyond the capacity are
+    /// reported as missing.
+    pub fn missing<'a>(&'a self, expected: &'a [A]) -> impl Iterator<Item = A> + 'a {
+        expected
+            .iter()
+            .copied()
+            .filter(move |address| !self.contains(*address))
+    }
+}
+
+impl<'a, A, const N: usize> IntoIterator for &'a ScanResult<A, N>
+where
+    A: Copy + Eq + From<u8>,
+{
+    type Item = &'a A;
+    type IntoIter = core::slice::Iter<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Probes every non-reserved 7-bit address, i.e. `0x08..=0x77`.
+///
+/// An address counts as present if the probe is acknowledged; a missing acknowledge is
+/// reported by the bus as [`ErrorKind::NoAcknowledge`].
+///
+/// ```
+/// use embedded_hal::i2c::I2c;
+/// use hardware_registers::i2c::{scan, DeviceAddress7, Probe, ScanResult};
+///
+/// /// Verifies at boot that the accelerometer and the EEPROM are attached.
+/// fn devices_present<B: I2c>(bus: &mut B) -> Result<bool, B::Error> {
+///     let expected = [DeviceAddress7::new(0x18), DeviceAddress7::new(0x50)];
+///     let found: ScanResult<DeviceAddress7, 8> = scan(bus, Probe::Auto)?;
+///     let missing = found.missing(&expected).count();
+///     Ok(missing == 0)
+/// }
+/// ```
+///
+/// # Errors
+/// Returns the first bus error other than a missing acknowledge.
+pub fn scan<B, const N: usize>(
+    bus: &mut B,
+    probe: Probe,
+) -> Result<ScanResult<DeviceAddress7, N>, B::Error>
+where
+    B: I2c<SevenBitAddress>,
+{
+    let addresses = (0..=0x7F)
+        .map(DeviceAddress7::new)
+        .filter(|address| !address.is_reserved());
+    scan_addresses(bus, addresses, probe)
+}
+
+/// Probes every 10-bit address, i.e. `0x000..=0x3FF`.
+///
+/// The [`Auto`](Probe::Auto) probe uses quick writes on all 10-bit addresses.
+///
+/// # Errors
+/// Returns the first bus error other than a missing acknowledge.
+pub fn scan_ten_bit<B, const N: usize>(
+    bus: &mut B,
+    probe: Probe,
+) -> Result<ScanResult<DeviceAddress10, N>, B::Error>
+where
+    B: I2c<TenBitAddress>,
+{
+    scan_addresses(bus, (0..=0x3FF).map(DeviceAddress10::new), probe)
+}
+
+fn scan_addresses<B, A, const N: usize>(
+    bus: &mut B,
+    addresses: impl Iterator<Item = A>,
+    probe: Probe,
+) -> Result<ScanResult<A, N>, B::Error>
+where
+    A: I2CBusAddress + Eq + From<u8>,
+    B: I2c<A::Mode>,
+{
+    let ten_bit = A::ADDR_BITS == 10;
+    let mut result = ScanResult::new();
+    for address in addresses {
+        let response = match probe.resolve(address.to_bus_address().into(), ten_bit) {
+            Probe::ReadByte => bus.read(address.to_bus_address(), &mut [0]),
+            _ => bus.write(address.to_bus_address(), &[]),
+        };
+        match response {
+            Ok(()) => result.push(address),
+            Err(error) if matches!(error.kind(), ErrorKind::NoAcknowledge(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockDevice, MockI2c};
+    use crate::register_address::RegisterAddress8;
+    use embedded_hal::i2c::{ErrorType, NoAcknowledgeSource, Operation};
+
+    fn bus(addresses: &[u8]) -> MockI2c {
+        let mut bus = MockI2c::new();
+        for address in addresses {
+            bus.attach(
+                DeviceAddress7::new(*address),
+                MockDevice::new::<RegisterAddress8>(),
+            );
+        }
+        bus
+    }
+
+    #[test]
+    fn finds_devices_and_skips_reserved_addresses() {
+        let mut bus = bus(&[0x03, 0x18, 0x50, 0x7A]);
+        let found: ScanResult<DeviceAddress7, 4> = match scan(&mut bus, Probe::QuickWrite) {
+            Ok(found) => found,
+            Err(error) => panic!("{error:?}"),
+        };
+        assert_eq!(
+            found.as_slice(),
+            [DeviceAddress7::new(0x18), DeviceAddress7::new(0x50)]
+        );
+        assert!(!found.is_truncated());
+        let expected = [DeviceAddress7::new(0x18), DeviceAddress7::new(0x19)];
+        assert!(found
+            .missing(&expected)
+            .eq([DeviceAddress7::new(0x19)].into_iter()));
+    }
+
+    #[test]
+    fn truncates_at_capacity() {
+        let mut bus = bus(&[0x18, 0x19, 0x1A]);
+        let found: Result<ScanResult<DeviceAddress7, 2>, _> = scan(&mut bus, Probe::ReadByte);
+        assert_eq!(found.map(|f| (f.len(), f.is_truncated())), Ok((2, true)));
+    }
+
+    /// A 10-bit bus recording the probe transfers, with one device at `0x2A5`.
+    #[derive(Default)]
+    struct TenBitBus {
+        reads: usize,
+        writes: usize,
+    }
+
+    impl ErrorType for TenBitBus {
+        type Error = ErrorKind;
+    }
+
+    impl I2c<TenBitAddress> for TenBitBus {
+        fn transaction(
+            &mut self,
+            address: u16,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                match operation {
+                    Operation::Read(_) => self.reads += 1,
+                    Operation::Write(_) => self.writes += 1,
+                }
+            }
+            match address {
+                0x2A5 => Ok(()),
+                0x3FF => Err(ErrorKind::Bus),
+                _ => Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+            }
+        }
+    }
+
+    #[test]
+    fn ten_bit_scan_reports_bus_errors() {
+        let mut bus = TenBitBus::default();
+        let found: Result<ScanResult<DeviceAddress10, 4>, _> = scan_ten_bit(&mut bus, Probe::Auto);
+        assert_eq!(found, Err(ErrorKind::Bus));
+        assert_eq!((bus.reads, bus.writes), (0, 0x400));
+    }
+
+    #[test]
+    fn auto_probe_reads_eeprom_ranges() {
+        assert_eq!(Probe::Auto.resolve(0x50, false), Probe::ReadByte);
+        assert_eq!(Probe::Auto.resolve(0x18, false), Probe::QuickWrite);
+        assert_eq!(Probe::Auto.resolve(0x50, true), Probe::QuickWrite);
+        assert_eq!(Probe::ReadByte.resolve(0x18, false), Probe::ReadByte);
+    }
+}
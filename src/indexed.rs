@@ -0,0 +1,243 @@
+//! Families of identical registers at a fixed stride.
+//!
+//! Devices such as LED drivers, GPIO expanders or FIFO slots have several registers of the
+//! same layout, e.g. the `LEDn_ON_L` registers of a PCA9685 at `0x06 + 4 * n`. A
+//! [`RegisterArray`] describes such a family by its base address, stride and length and
+//! yields the address of the register at an index, failing if the index is out of bounds.
+//!
+//! An [`IndexedRegister`] type is read and written at a runtime index with the
+//! `read_indexed` and `write_indexed` methods of
+//! [`I2CRegisterAccess`](crate::i2c::I2CRegisterAccess) and
+//! [`SPIRegisterAccess`](crate::spi::SPIRegisterAccess). For a constant index,
+//! [`RegisterArray::at`] is evaluated at compile time and can define the `REGISTER_ADDRESS`
+//! of a regular register type, in which case an out-of-bounds index fails the build.
+//!
+//! ```
+//! use hardware_registers::indexed::RegisterArray;
+//! use hardware_registers::register_address::RegisterAddress8;
+//!
+//! const LED_ON_L: RegisterArray<RegisterAddress8> =
+//!     RegisterArray::new(RegisterAddress8::new(0x06), 4, 16);
+//!
+//! const LED3_ON_L: RegisterAddress8 = LED_ON_L.at(3);
+//! assert_eq!(LED3_ON_L, RegisterAddress8::new(0x12));
+//! assert!(LED_ON_L.get(16).is_err());
+//! ```
+
+use crate::error::Error;
+use crate::register_address::{RegisterAddress, RegisterAddress16, RegisterAddress8};
+use crate::sizes::RegisterSizeInformation;
+use crate::{FromBits, HardwareRegister, ToBits};
+use core::convert::Infallible;
+
+/// A register address that can be offset to address the registers of a [`RegisterArray`].
+pub trait ArrayAddress: RegisterAddress + Copy {
+    /// Returns the address `offset` bytes after this one, or `None` if it does not fit.
+    fn checked_offset(self, offset: usize) -> Option<Self>;
+}
+
+impl ArrayAddress for RegisterAddress8 {
+    fn checked_offset(self, offset: usize) -> Option<Self> {
+        u8::try_from(offset)
+            .ok()
+            .and_then(|offset| self.into_inner().checked_add(offset))
+            .map(Self::new)
+    }
+}
+
+impl ArrayAddress for RegisterAddress16 {
+    fn checked_offset(self, offset: usize) -> Option<Self> {
+        u16::try_from(offset)
+            .ok()
+            .and_then(|offset| self.into_inner().checked_add(offset))
+            .map(Self::new)
+    }
+}
+
+/// The addresses of `len` identical registers, starting at a base address and spaced
+/// `stride` bytes apart.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RegisterArray<R> {
+    base: R,
+    stride: usize,
+    len: usize,
+}
+
+impl<R> RegisterArray<R> {
+    /// Constructs an array of `len` registers, the first one at `base`, spaced `stride`
+    /// bytes apart.
+    #[must_use]
+    pub const fn new(base: R, stride: usize, len: usize) -> Self {
+        Self { base, stride, len }
+    }
+
+    /// Returns the address of the first register.
+    #[must_use]
+    pub const fn base(&self) -> &R {
+        &self.base
+    }
+
+    /// Returns the distance between two registers, in bytes.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the number of registers.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indicates whether the array has no registers.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<R> RegisterArray<R>
+where
+    R: ArrayAddress,
+{
+    /// Returns the address of the register at the specified index.
+    ///
+    /// # Errors
+    /// Returns [`Error::AddressOutOfRange`] if the index is out of bounds or the address
+    /// does not fit the address type.
+    pub fn get(&self, index: usize) -> Result<R, Error<Infallible>> {
+        if index >= self.len {
+            return Err(Error::AddressOutOfRange);
+        }
+        index
+            .checked_mul(self.stride)
+            .and_then(|offset| self.base.checked_offset(offset))
+            .ok_or(Error::AddressOutOfRange)
+    }
+
+    /// Returns an iterator over the addresses of all registers.
+    pub fn iter(&self) -> impl Iterator<Item = R> + '_ {
+        (0..self.len).filter_map(|index| self.get(index).ok())
+    }
+}
+
+impl RegisterArray<RegisterAddress8> {
+    /// Returns the address of the register at the specified index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds or the address exceeds 8 bits; when evaluated
+    /// in a constant, the build fails instead.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn at(&self, index: usize) -> RegisterAddress8 {
+        assert!(index < self.len, "register index out of bounds");
+        let address = self.base.into_inner() as usize + index * self.stride;
+        assert!(address <= u8::MAX as usize, "register address out of range");
+        RegisterAddress8::new(address as u8)
+    }
+}
+
+impl RegisterArray<RegisterAddress16> {
+    /// Returns the address of the register at the specified index.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds or the address exceeds 16 bits; when evaluated
+    /// in a constant, the build fails instead.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn at(&self, index: usize) -> RegisterAddress16 {
+        assert!(index < self.len, "register index out of bounds");
+        let address = self.base.into_inner() as usize + index * self.stride;
+        assert!(
+            address <= u16::MAX as usize,
+            "register address out of range"
+        );
+        RegisterAddress16::new(address as u16)
+    }
+}
+
+/// A register type shared by the registers of a [`RegisterArray`].
+pub trait IndexedRegister<R, Size>:
+    HardwareRegister<Size> + ToBits<Target = Self::Backing> + FromBits<Self::Backing>
+where
+    R: ArrayAddress,
+    Size: RegisterSizeInformation,
+{
+    /// The addresses of the registers.
+    const REGISTERS: RegisterArray<R>;
+
+    /// The backing type for the register, e.g. `u8` for an 8-bit register.
+    type Backing;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARRAY: RegisterArray<RegisterAddress8> =
+        RegisterArray::new(RegisterAddress8::new(0x06), 4, 16);
+
+    #[test]
+    fn addresses() {
+        assert_eq!(ARRAY.at(0), RegisterAddress8::new(0x06));
+        assert_eq!(ARRAY.get(15), Ok(RegisterAddress8::new(0x42)));
+        assert_eq!(ARRAY.get(16), Err(Error::AddressOutOfRange));
+        assert_eq!(ARRAY.iter().count(), 16);
+    }
+
+    #[test]
+    fn address_overflow() {
+        let array = RegisterArray::new(RegisterAddress8::new(0xF0), 8, 4);
+        assert_eq!(array.get(1), Ok(RegisterAddress8::new(0xF8)));
+        assert_eq!(array.get(2), Err(Error::AddressOutOfRange));
+        let array = RegisterArray::new(RegisterAddress16::new(0xF0), 8, 4);
+        assert_eq!(array.get(3), Ok(RegisterAddress16::new(0x108)));
+    }
+
+    #[test]
+    #[should_panic(expected = "register index out of bounds")]
+    fn at_out_of_bounds() {
+        let _ = ARRAY.at(16);
+    }
+
+    #[cfg(feature = "i2c")]
+    mod i2c {
+        use super::*;
+        use crate::backing::ByteOrder;
+        use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegisterAccess};
+        use crate::mock::{MockDevice, MockI2c};
+        use crate::sizes::R2;
+        use crate::test_support::test_register;
+
+        test_register!(
+            /// The on-time of a PCA9685 LED channel.
+            LedOn(u16): R2, writable, ByteOrder::LittleEndian
+        );
+
+        impl IndexedRegister<RegisterAddress8, R2> for LedOn {
+            const REGISTERS: RegisterArray<RegisterAddress8> = ARRAY;
+            type Backing = u16;
+        }
+
+        #[test]
+        fn read_write_indexed() {
+            let bus = MockI2c::new().with_device(
+                DeviceAddress7::new(0x40),
+                MockDevice::new::<RegisterAddress8>(),
+            );
+            let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x40));
+            assert_eq!(device.write_indexed(3, &LedOn(0x0123)), Ok(()));
+            assert_eq!(device.read_indexed(3), Ok(LedOn(0x0123)));
+            assert_eq!(
+                device.write_indexed(16, &LedOn(0)),
+                Err(Error::AddressOutOfRange)
+            );
+            let bus = device.release();
+            let registers = match bus.device(DeviceAddress7::new(0x40)) {
+                Some(mock) => mock.registers().get::<_, 2>(RegisterAddress8::new(0x12)),
+                None => panic!("the device is attached"),
+            };
+            assert_eq!(registers, [0x23, 0x01]);
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! Generic, embedded-friendly hardware registers support, including
 //! traits and types for understanding I²C registers.
 
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![forbid(unsafe_code)]
 #![deny(warnings, clippy::pedantic)]
 #![warn(
@@ -19,17 +19,103 @@
 // Enables the `doc_cfg` feature when the `docsrs` configuration attribute is defined.
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+use crate::backing::ByteOrder;
 use crate::sizes::RegisterSizeInformation;
 
+// Allows generated code to refer to this crate by name in tests.
+#[cfg(all(test, feature = "codegen", feature = "spi"))]
+extern crate self as hardware_registers;
+
+/// Implements `Serialize` and `Deserialize` for an address newtype, rejecting
+/// values that exceed the address width on deserialization.
+#[cfg(feature = "serde")]
+macro_rules! impl_serde_address {
+    ($ident: ident, $inner: ty, $serialize: ident, $max: expr, $expected: literal) => {
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl serde::Serialize for $ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.$serialize(self.0)
+            }
+        }
+
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de> serde::Deserialize<'de> for $ident {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = <$inner as serde::Deserialize>::deserialize(deserializer)?;
+                if value > $max {
+                    return Err(serde::de::Error::invalid_value(
+                        serde::de::Unexpected::Unsigned(value.into()),
+                        &$expected,
+                    ));
+                }
+                Ok(Self(value))
+            }
+        }
+    };
+}
+
+pub mod access;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod addressing;
+pub mod backing;
+#[cfg(feature = "codegen")]
+#[cfg_attr(docsrs, doc(cfg(feature = "codegen")))]
+pub mod codegen;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod command;
+pub mod error;
+pub mod export;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod fifo;
+#[cfg(feature = "bitflags")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+pub mod flags;
+pub mod format;
 #[cfg(feature = "i2c")]
 #[cfg_attr(docsrs, doc(cfg(feature = "i2c")))]
 pub mod i2c;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod indexed;
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "linux", target_os = "linux"))))]
+pub mod linux;
 pub mod sizes;
 #[cfg(feature = "spi")]
 #[cfg_attr(docsrs, doc(cfg(feature = "spi")))]
 pub mod spi;
 
+pub mod map;
+#[cfg(any(test, feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod mock;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod poll;
+#[cfg(any(test, feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod recording;
 pub mod register_address;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod sequence;
+#[cfg(any(test, feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod snapshot;
+#[cfg(any(feature = "i2c", feature = "spi"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "i2c", feature = "spi"))))]
+pub mod status;
+#[cfg(all(test, any(feature = "i2c", feature = "spi")))]
+mod test_support;
 
 /// A generic hardware register of specified byte size.
 pub trait HardwareRegister<Size>
@@ -41,6 +127,9 @@ where
 
     /// The size of the register in bits.
     const SIZE_BITS: usize = Size::BITS;
+
+    /// The order in which the register's bytes are transferred.
+    const BYTE_ORDER: ByteOrder = ByteOrder::BigEndian;
 }
 
 /// A writable hardware register of specified byte size.
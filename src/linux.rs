@@ -0,0 +1,63 @@
+//! Linux host backends for prototyping drivers on e.g. a Raspberry Pi.
+//!
+//! [`LinuxI2c`] drives an I²C bus through `/dev/i2c-N` using the `I2C_RDWR` ioctl, and
+//! [`LinuxSpi`] drives an SPI device through `/dev/spidevX.Y`. Both implement the
+//! `embedded-hal` bus traits, so they plug into [`I2CDevice`](crate::i2c::I2CDevice) and
+//! [`SPIDevice`](crate::spi::SPIDevice) like any microcontroller HAL:
+//!
+//! ```no_run
+//! use hardware_registers::access::RegisterAccess;
+//! use hardware_registers::i2c::{DeviceAddress7, I2CDevice, RegisterAddress8};
+//! use hardware_registers::linux::LinuxI2c;
+//!
+//! let bus = LinuxI2c::open("/dev/i2c-1")?;
+//! let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+//!
+//! let mut who_am_i = [0_u8; 1];
+//! device.read_raw(RegisterAddress8::new(0x0F), &mut who_am_i)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The ioctls themselves are abstracted by the [`I2cIoctl`] and [`SpiIoctl`] traits, which are
+//! implemented for the device files of the `i2cdev` and `spidev` crates. Implement them for a
+//! fake device to test host tooling on machines without the respective hardware.
+
+mod i2c;
+mod spi;
+
+pub use crate::linux::i2c::{I2cIoctl, LinuxI2c};
+pub use crate::linux::spi::{LinuxSpi, SpiIoctl};
+pub use i2cdev::linux::LinuxI2CBus;
+pub use spidev::{SpiModeFlags, Spidev, SpidevOptions};
+use std::fmt::{Display, Formatter};
+
+/// An error reported by a Linux bus device file.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LinuxError(std::io::Error);
+
+impl LinuxError {
+    /// Consumes self and returns the underlying I/O error.
+    #[must_use]
+    pub fn into_inner(self) -> std::io::Error {
+        self.0
+    }
+}
+
+impl From<std::io::Error> for LinuxError {
+    fn from(error: std::io::Error) -> Self {
+        Self(error)
+    }
+}
+
+impl Display for LinuxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for LinuxError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
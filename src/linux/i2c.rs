@@ -0,0 +1,292 @@
+use crate::linux::LinuxError;
+use embedded_hal::i2c::{
+    Error, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress,
+    TenBitAddress,
+};
+use i2cdev::core::{I2CMessage, I2CTransfer};
+use i2cdev::linux::{I2CMessageFlags, LinuxI2CBus, LinuxI2CMessage};
+use std::path::Path;
+
+/// The `ENXIO` error number, reported when the addressed device does not acknowledge.
+const ENXIO: i32 = 6;
+
+/// The `EREMOTEIO` error number, reported when a data byte is not acknowledged.
+const EREMOTEIO: i32 = 121;
+
+/// The `I2C_RDWR` ioctl of an I²C bus device file.
+pub trait I2cIoctl {
+    /// Performs the operations as a single combined transfer, sending each operation as one
+    /// message that begins with a (repeated) start condition.
+    ///
+    /// # Errors
+    /// Returns the error reported by the ioctl.
+    fn transfer(
+        &mut self,
+        address: u16,
+        ten_bit: bool,
+        operations: &mut [Operation<'_>],
+    ) -> std::io::Result<()>;
+}
+
+impl I2cIoctl for LinuxI2CBus {
+    fn transfer(
+        &mut self,
+        address: u16,
+        ten_bit: bool,
+        operations: &mut [Operation<'_>],
+    ) -> std::io::Result<()> {
+        let flags = if ten_bit {
+            I2CMessageFlags::TEN_BIT_ADDRESS
+        } else {
+            I2CMessageFlags::empty()
+        };
+        let mut messages: Vec<_> = operations
+            .iter_mut()
+            .map(|operation| match operation {
+                Operation::Read(buffer) => {
+                    LinuxI2CMessage::read(buffer).with_flags(flags | I2CMessageFlags::READ)
+                }
+                Operation::Write(bytes) => LinuxI2CMessage::write(bytes).with_flags(flags),
+            })
+            .map(|message| message.with_address(address))
+            .collect();
+        I2CTransfer::transfer(self, &mut messages)?;
+        Ok(())
+    }
+}
+
+/// An I²C bus on a Linux host, accessed through `/dev/i2c-N`.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LinuxI2c<B = LinuxI2CBus> {
+    bus: B,
+}
+
+impl LinuxI2c {
+    /// Opens the bus device file, e.g. `/dev/i2c-1`.
+    ///
+    /// # Errors
+    /// Returns an error if the device file cannot be opened.
+    pub fn open<P>(path: P) -> Result<Self, LinuxError>
+    where
+        P: AsRef<Path>,
+    {
+        let bus = LinuxI2CBus::new(path).map_err(std::io::Error::from)?;
+        Ok(Self::new(bus))
+    }
+}
+
+impl<B> LinuxI2c<B> {
+    /// Wraps the provided ioctl implementation.
+    pub const fn new(bus: B) -> Self {
+        Self { bus }
+    }
+
+    /// Consumes self and returns the ioctl implementation.
+    pub fn release(self) -> B {
+        self.bus
+    }
+}
+
+impl Error for LinuxError {
+    fn kind(&self) -> ErrorKind {
+        match self.0.raw_os_error() {
+            Some(ENXIO) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Some(EREMOTEIO) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl<B> ErrorType for LinuxI2c<B> {
+    type Error = LinuxError;
+}
+
+impl<B> I2c<SevenBitAddress> for LinuxI2c<B>
+where
+    B: I2cIoctl,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        transfer(&mut self.bus, address.into(), false, operations)
+    }
+}
+
+impl<B> I2c<TenBitAddress> for LinuxI2c<B>
+where
+    B: I2cIoctl,
+{
+    fn transaction(
+        &mut self,
+        address: TenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        transfer(&mut self.bus, address, true, operations)
+    }
+}
+
+/// Performs the operations, merging adjacent operations of the same direction into one message.
+///
+/// `embedded-hal` requires adjacent operations of the same direction to be transferred without
+/// an intermediate start condition, e.g. the register address and data of a register write.
+fn transfer<B>(
+    bus: &mut B,
+    address: u16,
+    ten_bit: bool,
+    operations: &mut [Operation<'_>],
+) -> Result<(), LinuxError>
+where
+    B: I2cIoctl,
+{
+    let mut buffers: Vec<(bool, Vec<u8>)> = Vec::new();
+    for operation in &*operations {
+        let is_read = matches!(operation, Operation::Read(_));
+        match buffers.last_mut() {
+            Some((read, _)) if *read == is_read => {}
+            _ => buffers.push((is_read, Vec::new())),
+        }
+        if let Some((_, buffer)) = buffers.last_mut() {
+            match operation {
+                Operation::Read(read) => buffer.resize(buffer.len() + read.len(), 0),
+                Operation::Write(bytes) => buffer.extend_from_slice(bytes),
+            }
+        }
+    }
+
+    let mut messages: Vec<_> = buffers
+        .iter_mut()
+        .map(|(read, buffer)| {
+            if *read {
+                Operation::Read(buffer)
+            } else {
+                Operation::Write(buffer)
+            }
+        })
+        .collect();
+    bus.transfer(address, ten_bit, &mut messages)?;
+    drop(messages);
+
+    let received = buffers
+        .iter()
+        .filter(|(read, _)| *read)
+        .flat_map(|(_, buffer)| buffer);
+    let targets = operations.iter_mut().flat_map(|operation| match operation {
+        Operation::Read(buffer) => &mut buffer[..],
+        Operation::Write(_) => &mut [],
+    });
+    for (target, byte) in targets.zip(received) {
+        *target = *byte;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::RegisterAccess;
+    use crate::i2c::{DeviceAddress10, DeviceAddress7, I2CDevice, RegisterAddress8};
+    use crate::mock::{MockDevice, MockI2c};
+    use std::io::ErrorKind as IoErrorKind;
+
+    /// A fake bus device file that forwards transfers to a simulated bus.
+    struct FakeBus {
+        bus: MockI2c,
+        transfers: Vec<(u16, bool, usize)>,
+    }
+
+    impl I2cIoctl for FakeBus {
+        fn transfer(
+            &mut self,
+            address: u16,
+            ten_bit: bool,
+            operations: &mut [Operation<'_>],
+        ) -> std::io::Result<()> {
+            self.transfers.push((address, ten_bit, operations.len()));
+            let address = u8::try_from(address).map_err(|_| IoErrorKind::InvalidInput)?;
+            self.bus
+                .transaction(address, operations)
+                .map_err(|_| std::io::Error::from_raw_os_error(ENXIO))
+        }
+    }
+
+    fn fake() -> FakeBus {
+        let mut device = MockDevice::new::<RegisterAddress8>();
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x0F), &[0x33]);
+        FakeBus {
+            bus: MockI2c::new().with_device(DeviceAddress7::new(0x18), device),
+            transfers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn register_access_uses_combined_transfers() {
+        let mut device = I2CDevice::new(LinuxI2c::new(fake()), DeviceAddress7::new(0x18));
+        let mut buffer = [0_u8; 1];
+        assert!(device
+            .read_raw(RegisterAddress8::new(0x0F), &mut buffer)
+            .is_ok());
+        assert_eq!(buffer, [0x33]);
+        assert!(device
+            .write_raw(RegisterAddress8::new(0x20), &[0x47])
+            .is_ok());
+        assert_eq!(
+            device.release().release().transfers,
+            [(0x18, false, 2), (0x18, false, 1)]
+        );
+    }
+
+    #[test]
+    fn adjacent_reads_are_merged() {
+        let mut fake = fake();
+        if let Some(device) = fake.bus.device_mut(DeviceAddress7::new(0x18)) {
+            device
+                .registers_mut()
+                .set(RegisterAddress8::new(0x28), &[0x1A, 0x2B]);
+        }
+        let mut bus = LinuxI2c::new(fake);
+        let (mut low, mut high) = ([0_u8; 1], [0_u8; 1]);
+        let result = bus.transaction(
+            0x18_u8,
+            &mut [
+                Operation::Write(&[0x28]),
+                Operation::Read(&mut low),
+                Operation::Read(&mut high),
+            ],
+        );
+        assert!(result.is_ok());
+        assert_eq!((low, high), ([0x1A], [0x2B]));
+        assert_eq!(bus.release().transfers, [(0x18, false, 2)]);
+    }
+
+    #[test]
+    fn missing_device_is_not_acknowledged() {
+        let mut device = I2CDevice::new(LinuxI2c::new(fake()), DeviceAddress7::new(0x19));
+        let mut buffer = [0_u8; 1];
+        let error = device.read_raw(RegisterAddress8::new(0x0F), &mut buffer);
+        assert_eq!(
+            error.map_err(|e| e.kind()),
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+    }
+
+    #[test]
+    fn ten_bit_addresses_set_the_flag() {
+        let mut bus = LinuxI2c::new(fake());
+        let _ = bus.write(DeviceAddress10::new(0x218).into_inner(), &[0x20]);
+        assert_eq!(bus.release().transfers, [(0x218, true, 1)]);
+    }
+
+    #[test]
+    fn regular_file_rejects_the_ioctl() {
+        let path = std::env::temp_dir().join(format!("hwreg-i2c-{}", std::process::id()));
+        assert!(std::fs::write(&path, []).is_ok());
+        let result = LinuxI2c::open(&path).and_then(|mut bus| bus.write(0x18_u8, &[0x00]));
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Err(e) if e.kind() == ErrorKind::Other));
+    }
+}
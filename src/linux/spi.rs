@@ -0,0 +1,157 @@
+use crate::linux::LinuxError;
+use embedded_hal::spi::{Error, ErrorKind, ErrorType, Operation, SpiDevice};
+use spidev::{Spidev, SpidevTransfer};
+use std::path::Path;
+
+/// The `SPI_IOC_MESSAGE` ioctl of an SPI device file.
+pub trait SpiIoctl {
+    /// Performs the operations as a single transfer while keeping the chip select asserted.
+    ///
+    /// # Errors
+    /// Returns the error reported by the ioctl.
+    fn transfer(&mut self, operations: &mut [Operation<'_, u8>]) -> std::io::Result<()>;
+}
+
+impl SpiIoctl for Spidev {
+    fn transfer(&mut self, operations: &mut [Operation<'_, u8>]) -> std::io::Result<()> {
+        // The kernel expects transmit and receive buffers of equal length, so transfers of
+        // differing lengths and in-place transfers are staged in scratch buffers.
+        let mut scratch: Vec<(Vec<u8>, Vec<u8>)> = operations
+            .iter()
+            .map(|operation| match operation {
+                Operation::Transfer(read, write) => {
+                    let len = read.len().max(write.len());
+                    let mut tx = write.to_vec();
+                    tx.resize(len, 0);
+                    (tx, vec![0; len])
+                }
+                Operation::TransferInPlace(buffer) => (buffer.to_vec(), vec![0; buffer.len()]),
+                _ => (Vec::new(), Vec::new()),
+            })
+            .collect();
+
+        let mut transfers: Vec<_> = operations
+            .iter_mut()
+            .zip(&mut scratch)
+            .map(|(operation, (tx, rx))| match operation {
+                Operation::Read(buffer) => SpidevTransfer::read(buffer),
+                Operation::Write(bytes) => SpidevTransfer::write(bytes),
+                Operation::Transfer(..) | Operation::TransferInPlace(..) => {
+                    SpidevTransfer::read_write(tx, rx)
+                }
+                Operation::DelayNs(ns) => {
+                    let us = *ns / 1000 + u32::from(*ns % 1000 != 0);
+                    SpidevTransfer::delay(u16::try_from(us).unwrap_or(u16::MAX))
+                }
+            })
+            .collect();
+        self.transfer_multiple(&mut transfers)?;
+        drop(transfers);
+
+        for (operation, (_, rx)) in operations.iter_mut().zip(&scratch) {
+            match operation {
+                Operation::Transfer(read, _) => read.copy_from_slice(&rx[..read.len()]),
+                Operation::TransferInPlace(buffer) => buffer.copy_from_slice(rx),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An SPI device on a Linux host, accessed through `/dev/spidevX.Y`.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct LinuxSpi<B = Spidev> {
+    device: B,
+}
+
+impl LinuxSpi {
+    /// Opens the device file, e.g. `/dev/spidev0.0`, keeping the kernel's default bus settings.
+    ///
+    /// Use [`Spidev::configure`] and [`LinuxSpi::new`] to set the mode or clock rate.
+    ///
+    /// # Errors
+    /// Returns an error if the device file cannot be opened.
+    pub fn open<P>(path: P) -> Result<Self, LinuxError>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self::new(Spidev::open(path)?))
+    }
+}
+
+impl<B> LinuxSpi<B> {
+    /// Wraps the provided ioctl implementation.
+    pub const fn new(device: B) -> Self {
+        Self { device }
+    }
+
+    /// Consumes self and returns the ioctl implementation.
+    pub fn release(self) -> B {
+        self.device
+    }
+}
+
+impl Error for LinuxError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<B> ErrorType for LinuxSpi<B> {
+    type Error = LinuxError;
+}
+
+impl<B> SpiDevice for LinuxSpi<B>
+where
+    B: SpiIoctl,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        Ok(self.device.transfer(operations)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::RegisterAccess;
+    use crate::mock::{MockDevice, MockSpi};
+    use crate::register_address::RegisterAddress8;
+    use crate::spi::SPIDevice;
+
+    /// A fake device file that forwards transfers to a simulated device.
+    struct FakeDevice(MockSpi);
+
+    impl SpiIoctl for FakeDevice {
+        fn transfer(&mut self, operations: &mut [Operation<'_, u8>]) -> std::io::Result<()> {
+            self.0
+                .transaction(operations)
+                .map_err(|_| std::io::ErrorKind::InvalidData.into())
+        }
+    }
+
+    #[test]
+    fn register_access() {
+        let mut device = MockDevice::new::<RegisterAddress8>();
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x0F), &[0x33]);
+        let mut device = SPIDevice::new(LinuxSpi::new(FakeDevice(MockSpi::new(device))));
+
+        let mut buffer = [0_u8; 1];
+        assert!(device
+            .read_raw(RegisterAddress8::new(0x0F), &mut buffer)
+            .is_ok());
+        assert_eq!(buffer, [0x33]);
+    }
+
+    #[test]
+    fn regular_file_rejects_the_ioctl() {
+        let path = std::env::temp_dir().join(format!("hwreg-spi-{}", std::process::id()));
+        assert!(std::fs::write(&path, []).is_ok());
+        let result = LinuxSpi::open(&path).and_then(|mut spi| spi.write(&[0x00]));
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Err(e) if e.kind() == ErrorKind::Other));
+    }
+}
@@ -0,0 +1,308 @@
+//! Runtime descriptions of a device's registers.
+//!
+//! A [`RegisterMap`] lists the registers of a device along with their addresses, sizes,
+//! access policies, bit fields and documented reset values. Maps are `const`-constructible
+//! and can therefore be placed in flash on embedded targets.
+//!
+//! ```
+//! use hardware_registers::map::{Access, RegisterInfo, RegisterMap};
+//! use hardware_registers::register_address::RegisterAddress8;
+//! use hardware_registers::sizes::R1;
+//!
+//! const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+//!     RegisterInfo::new("WHO_AM_I", RegisterAddress8::new(0x0F), R1::BYTES, Access::ReadOnly)
+//!         .with_reset(&[0x33]),
+//!     RegisterInfo::new("CTRL_REG1", RegisterAddress8::new(0x20), R1::BYTES, Access::ReadWrite)
+//!         .with_reset(&[0x07]),
+//! ];
+//!
+//! const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("LIS3DH", REGISTERS);
+//! assert_eq!(MAP.find("CTRL_REG1").map(|r| r.reset), Some(Some(&[0x07][..])));
+//! ```
+
+use crate::access::RegisterAccess;
+use crate::backing::ByteOrder;
+use crate::register_address::RegisterAddress;
+
+/// The access policy of a register.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Access {
+    /// The register can only be read.
+    ReadOnly,
+    /// The register can only be written.
+    WriteOnly,
+    /// The register can be read and written.
+    ReadWrite,
+}
+
+impl Access {
+    /// Indicates whether the register can be read.
+    #[must_use]
+    pub const fn is_readable(self) -> bool {
+        matches!(self, Self::ReadOnly | Self::ReadWrite)
+    }
+
+    /// Indicates whether the register can be written.
+    #[must_use]
+    pub const fn is_writable(self) -> bool {
+        matches!(self, Self::WriteOnly | Self::ReadWrite)
+    }
+}
+
+/// Describes a bit field within a register.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FieldInfo {
+    /// The name of the field, e.g. `ODR`.
+    pub name: &'static str,
+    /// The position of the field's least significant bit.
+    pub offset: usize,
+    /// The width of the field in bits.
+    pub width: usize,
+    /// The access policy of the field.
+    pub access: Access,
+}
+
+impl FieldInfo {
+    /// Describes a field of `width` bits starting at bit `offset`.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero.
+    #[must_use]
+    pub const fn new(name: &'static str, offset: usize, width: usize, access: Access) -> Self {
+        assert!(width > 0, "a field must be at least one bit wide");
+        Self {
+            name,
+            offset,
+            width,
+            access,
+        }
+    }
+
+    /// Gets the position of the field's most significant bit.
+    ///
+    /// A field of zero width, which [`new`](Self::new) rejects, reports its offset.
+    #[must_use]
+    pub const fn msb(&self) -> usize {
+        self.offset + self.width.saturating_sub(1)
+    }
+}
+
+/// Describes a single register of a device.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisterInfo<A> {
+    /// The name of the register, e.g. `CTRL_REG1`.
+    pub name: &'static str,
+    /// The address of the register.
+    pub address: A,
+    /// The size of the register in bytes.
+    pub size: usize,
+    /// The access policy of the register.
+    pub access: Access,
+    /// The documented power-on reset value, in transfer byte order.
+    pub reset: Option<&'static [u8]>,
+    /// The order in which the register's bytes are transferred.
+    pub byte_order: ByteOrder,
+    /// The bit fields of the register, in any order.
+    pub fields: &'static [FieldInfo],
+}
+
+impl<A> RegisterInfo<A> {
+    /// Describes a register without a documented reset value.
+    #[must_use]
+    pub const fn new(name: &'static str, address: A, size: usize, access: Access) -> Self {
+        Self {
+            name,
+            address,
+            size,
+            access,
+            reset: None,
+            byte_order: ByteOrder::BigEndian,
+            fields: &[],
+        }
+    }
+
+    /// Sets the documented power-on reset value, in transfer byte order.
+    #[must_use]
+    pub const fn with_reset(mut self, reset: &'static [u8]) -> Self {
+        self.reset = Some(reset);
+        self
+    }
+
+    /// Sets the order in which the register's bytes are transferred.
+    #[must_use]
+    pub const fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Sets the bit fields of the register.
+    #[must_use]
+    pub const fn with_fields(mut self, fields: &'static [FieldInfo]) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Gets the size of the register in bits.
+    #[must_use]
+    pub const fn bits(&self) -> usize {
+        self.size * 8
+    }
+
+    /// Gets the value of the specified bit of the reset value, if one is documented.
+    #[must_use]
+    pub fn reset_bit(&self, bit: usize) -> Option<bool> {
+        let reset = self.reset?;
+        let index = match self.byte_order {
+            ByteOrder::BigEndian => reset.len().checked_sub(bit / 8 + 1)?,
+            ByteOrder::LittleEndian => bit / 8,
+        };
+        reset.get(index).map(|byte| byte & (1 << (bit % 8)) != 0)
+    }
+
+    /// Gets the reset value of a field of up to 128 bits, if the register documents one.
+    #[must_use]
+    pub fn field_reset(&self, field: &FieldInfo) -> Option<u128> {
+        if field.width > 128 {
+            return None;
+        }
+        (0..field.width).rev().try_fold(0, |value, bit| {
+            self.reset_bit(field.offset + bit)
+                .map(|set| (value << 1) | u128::from(set))
+        })
+    }
+}
+
+/// Describes the registers of a device.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisterMap<A: 'static> {
+    /// The name of the device.
+    pub name: &'static str,
+    /// The registers of the device.
+    pub registers: &'static [RegisterInfo<A>],
+}
+
+impl<A> RegisterMap<A> {
+    /// Constructs a new register map.
+    #[must_use]
+    pub const fn new(name: &'static str, registers: &'static [RegisterInfo<A>]) -> Self {
+        Self { name, registers }
+    }
+
+    /// Finds a register by its name.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&'static RegisterInfo<A>> {
+        self.registers.iter().find(|r| r.name == name)
+    }
+
+    /// Restores all writable registers that have a documented reset value.
+    ///
+    /// Registers are written in map order. Returns the number of registers written.
+    ///
+    /// # Errors
+    /// Returns the first bus error encountered; registers after the failing one are not written.
+    pub fn restore_defaults<T>(&self, access: &mut T) -> Result<usize, T::Error>
+    where
+        T: RegisterAccess,
+        A: RegisterAddress + Copy,
+    {
+        let mut written = 0;
+        for register in self.registers.iter().filter(|r| r.access.is_writable()) {
+            if let Some(reset) = register.reset {
+                access.write_raw(register.address, reset)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::RegisterAddress8;
+
+    const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("ID", RegisterAddress8::new(0x0F), 1, Access::ReadOnly)
+            .with_reset(&[0x33]),
+        RegisterInfo::new("CTRL1", RegisterAddress8::new(0x20), 1, Access::ReadWrite)
+            .with_reset(&[0x07]),
+        RegisterInfo::new("CTRL2", RegisterAddress8::new(0x21), 2, Access::WriteOnly)
+            .with_reset(&[0x01, 0x02]),
+        RegisterInfo::new("CTRL3", RegisterAddress8::new(0x22), 1, Access::ReadWrite),
+    ];
+
+    const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("TEST", REGISTERS);
+
+    #[derive(Default)]
+    struct Writes {
+        writes: [(u16, [u8; 2], usize); 4],
+        count: usize,
+    }
+
+    impl RegisterAccess for Writes {
+        type Error = ();
+
+        fn read_raw<R>(&mut self, _address: R, _buffer: &mut [u8]) -> Result<(), Self::Error>
+        where
+            R: RegisterAddress,
+        {
+            Err(())
+        }
+
+        fn write_raw<R>(&mut self, address: R, data: &[u8]) -> Result<(), Self::Error>
+        where
+            R: RegisterAddress,
+        {
+            let mut bytes = [0; 2];
+            bytes[..data.len()].copy_from_slice(data);
+            self.writes[self.count] = (address.value(), bytes, data.len());
+            self.count += 1;
+            Ok(())
+        }
+    }
+
+    const CTRL: RegisterInfo<RegisterAddress8> =
+        RegisterInfo::new("CTRL", RegisterAddress8::new(0x30), 2, Access::ReadWrite)
+            .with_reset(&[0x34, 0x12])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("LOW", 0, 4, Access::ReadWrite),
+                FieldInfo::new("HIGH", 8, 8, Access::ReadOnly),
+            ]);
+
+    #[test]
+    fn field_reset() {
+        assert_eq!(CTRL.bits(), 16);
+        assert_eq!(CTRL.field_reset(&CTRL.fields[0]), Some(0x4));
+        assert_eq!(CTRL.field_reset(&CTRL.fields[1]), Some(0x12));
+        assert_eq!(CTRL.fields[1].msb(), 15);
+        let big_endian = CTRL.with_byte_order(ByteOrder::BigEndian);
+        assert_eq!(big_endian.field_reset(&CTRL.fields[1]), Some(0x34));
+        assert_eq!(REGISTERS[3].reset_bit(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "a field must be at least one bit wide")]
+    fn zero_width_field() {
+        let _ = FieldInfo::new("EMPTY", 4, 0, Access::ReadWrite);
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(MAP.find("CTRL2").map(|r| r.size), Some(2));
+        assert!(MAP.find("CTRL4").is_none());
+    }
+
+    #[test]
+    fn restore_defaults_writes_writable_registers() {
+        let mut bus = Writes::default();
+        assert_eq!(MAP.restore_defaults(&mut bus), Ok(2));
+        assert_eq!(bus.count, 2);
+        assert_eq!(bus.writes[0], (0x20, [0x07, 0], 1));
+        assert_eq!(bus.writes[1], (0x21, [0x01, 0x02], 2));
+    }
+}
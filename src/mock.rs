@@ -0,0 +1,352 @@
+//! Simulated register-file devices for testing drivers without hardware.
+//!
+//! A [`MockDevice`] holds a byte-addressed [`RegisterFile`] and responds to the same
+//! transactions the [`I2CDevice`](crate::i2c::I2CDevice) and [`SPIDevice`](crate::spi::SPIDevice)
+//! access layers emit: the register address is transmitted first, followed by the data
+//! read from or written to consecutive addresses.
+//!
+//! Use [`MockI2c`] to attach one or more devices to a simulated I²C bus, or [`MockSpi`]
+//! to simulate a single device on an SPI bus.
+//!
+//! ```
+//! # #[cfg(feature = "i2c")]
+//! # {
+//! use hardware_registers::access::RegisterAccess;
+//! use hardware_registers::i2c::{DeviceAddress7, I2CDevice, RegisterAddress8};
+//! use hardware_registers::mock::{MockDevice, MockI2c};
+//!
+//! let mut device = MockDevice::new::<RegisterAddress8>();
+//! device.registers_mut().set(RegisterAddress8::new(0x0F), &[0x33]);
+//!
+//! let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), device);
+//! let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+//!
+//! let mut who_am_i = [0_u8; 1];
+//! device.read_raw(RegisterAddress8::new(0x0F), &mut who_am_i).unwrap();
+//! assert_eq!(who_am_i, [0x33]);
+//! # }
+//! ```
+
+#[cfg(feature = "i2c")]
+mod i2c;
+#[cfg(feature = "spi")]
+mod spi;
+
+#[cfg(feature = "i2c")]
+pub use crate::mock::i2c::MockI2c;
+#[cfg(feature = "spi")]
+pub use crate::mock::spi::MockSpi;
+use crate::register_address::RegisterAddress;
+use std::collections::BTreeMap;
+
+/// A hook invoked after a register byte was read.
+type ReadHook = Box<dyn FnMut(&mut RegisterFile)>;
+
+/// A hook invoked after a register byte was written, receiving the written and previous value.
+type WriteHook = Box<dyn FnMut(&mut RegisterFile, u8, u8)>;
+
+/// A byte-addressed register file. Unset addresses read as zero.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisterFile {
+    cells: BTreeMap<u16, u8>,
+}
+
+impl RegisterFile {
+    /// Constructs an empty register file.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the provided bytes at consecutive addresses, starting at `address`.
+    pub fn set<A>(&mut self, address: A, bytes: &[u8])
+    where
+        A: RegisterAddress + Copy,
+    {
+        let mut address = address.value();
+        for byte in bytes {
+            self.cells.insert(address, *byte);
+            address = address.wrapping_add(1);
+        }
+    }
+
+    /// Reads `N` bytes from consecutive addresses, starting at `address`.
+    #[must_use]
+    pub fn get<A, const N: usize>(&self, address: A) -> [u8; N]
+    where
+        A: RegisterAddress + Copy,
+    {
+        let mut bytes = [0_u8; N];
+        let mut address = address.value();
+        for byte in &mut bytes {
+            *byte = self.byte(address);
+            address = address.wrapping_add(1);
+        }
+        bytes
+    }
+
+    /// Reads the byte at the specified address.
+    #[must_use]
+    pub fn byte(&self, address: u16) -> u8 {
+        self.cells.get(&address).copied().unwrap_or_default()
+    }
+
+    /// Stores a byte at the specified address.
+    pub fn set_byte(&mut self, address: u16, value: u8) {
+        self.cells.insert(address, value);
+    }
+
+    /// Iterates all explicitly set addresses and their values in address order.
+    pub fn iter(&self) -> impl Iterator<Item = (u16, u8)> + '_ {
+        self.cells.iter().map(|(a, v)| (*a, *v))
+    }
+}
+
+/// A simulated device backed by a [`RegisterFile`].
+///
+/// The device keeps an address pointer that is set by the register address at the start
+/// of every write and advances with every byte transferred, unless auto-increment is disabled
+/// or an [increment flag](Self::with_increment_flag) is required but not set.
+pub struct MockDevice {
+    registers: RegisterFile,
+    address_bytes: usize,
+    auto_increment: bool,
+    increment_flag: u8,
+    incrementing: bool,
+    pointer: u16,
+    read_hooks: BTreeMap<u16, ReadHook>,
+    write_hooks: BTreeMap<u16, WriteHook>,
+}
+
+impl MockDevice {
+    /// Constructs a device whose registers are addressed by `A`, e.g.
+    /// [`RegisterAddress8`](crate::register_address::RegisterAddress8).
+    #[must_use]
+    pub fn new<A>() -> Self
+    where
+        A: RegisterAddress,
+    {
+        Self {
+            registers: RegisterFile::new(),
+            address_bytes: A::ADDR_BYTES,
+            auto_increment: true,
+            increment_flag: 0,
+            incrementing: true,
+            pointer: 0,
+            read_hooks: BTreeMap::new(),
+            write_hooks: BTreeMap::new(),
+        }
+    }
+
+    /// Enables or disables address auto-increment. Enabled by default.
+    #[must_use]
+    pub fn with_auto_increment(mut self, enabled: bool) -> Self {
+        self.auto_increment = enabled;
+        self
+    }
+
+    /// Only increments the address if the `flag` bits are set in the first address byte,
+    /// as with [`AddressingMode::IncrementFlag`](crate::addressing::AddressingMode::IncrementFlag).
+    ///
+    /// The flag bits are not part of the register address. A flag of zero restores
+    /// unconditional auto-increment.
+    #[must_use]
+    pub fn with_increment_flag(mut self, flag: u8) -> Self {
+        self.increment_flag = flag;
+        self
+    }
+
+    /// Registers a hook that is invoked after the byte at `address` was read,
+    /// e.g. to clear a status register or advance a FIFO.
+    #[must_use]
+    pub fn on_read<A, F>(mut self, address: A, hook: F) -> Self
+    where
+        A: RegisterAddress + Copy,
+        F: FnMut(&mut RegisterFile) + 'static,
+    {
+        self.read_hooks.insert(address.value(), Box::new(hook));
+        self
+    }
+
+    /// Registers a hook that is invoked after the byte at `address` was written.
+    ///
+    /// The hook receives the written and the previous value, e.g. to implement
+    /// write-one-to-clear semantics or to trigger a reset.
+    #[must_use]
+    pub fn on_write<A, F>(mut self, address: A, hook: F) -> Self
+    where
+        A: RegisterAddress + Copy,
+        F: FnMut(&mut RegisterFile, u8, u8) + 'static,
+    {
+        self.write_hooks.insert(address.value(), Box::new(hook));
+        self
+    }
+
+    /// Gets the register file.
+    #[must_use]
+    pub fn registers(&self) -> &RegisterFile {
+        &self.registers
+    }
+
+    /// Gets the register file mutably.
+    pub fn registers_mut(&mut self) -> &mut RegisterFile {
+        &mut self.registers
+    }
+
+    /// Returns the number of register address bytes the device expects.
+    #[must_use]
+    pub fn address_bytes(&self) -> usize {
+        self.address_bytes
+    }
+
+    /// Sets the address pointer, stripping the increment flag from the first address byte.
+    #[cfg_attr(not(any(feature = "i2c", feature = "spi")), allow(dead_code))]
+    pub(crate) fn select(&mut self, address: u16) {
+        let flag = u16::from(self.increment_flag) << (8 * self.address_bytes.saturating_sub(1));
+        self.incrementing = flag == 0 || address & flag != 0;
+        self.pointer = address & !flag;
+    }
+
+    /// Reads the byte at the address pointer and advances it.
+    #[cfg_attr(not(any(feature = "i2c", feature = "spi")), allow(dead_code))]
+    pub(crate) fn read_byte(&mut self) -> u8 {
+        let address = self.pointer;
+        let value = self.registers.byte(address);
+        if let Some(hook) = self.read_hooks.get_mut(&address) {
+            hook(&mut self.registers);
+        }
+        self.advance();
+        value
+    }
+
+    /// Writes the byte at the address pointer and advances it.
+    #[cfg_attr(not(any(feature = "i2c", feature = "spi")), allow(dead_code))]
+    pub(crate) fn write_byte(&mut self, value: u8) {
+        let address = self.pointer;
+        let previous = self.registers.byte(address);
+        self.registers.set_byte(address, value);
+        if let Some(hook) = self.write_hooks.get_mut(&address) {
+            hook(&mut self.registers, value, previous);
+        }
+        self.advance();
+    }
+
+    fn advance(&mut self) {
+        if self.auto_increment && self.incrementing {
+            self.pointer = self.pointer.wrapping_add(1);
+        }
+    }
+}
+
+impl core::fmt::Debug for MockDevice {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MockDevice")
+            .field("registers", &self.registers)
+            .field("address_bytes", &self.address_bytes)
+            .field("auto_increment", &self.auto_increment)
+            .field("increment_flag", &self.increment_flag)
+            .field("pointer", &self.pointer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decodes the register address from the leading bytes of a transfer.
+///
+/// Returns the number of bytes consumed once the full address was received.
+#[derive(Debug, Default)]
+#[cfg(any(feature = "i2c", feature = "spi"))]
+struct AddressDecoder {
+    address: u16,
+    received: usize,
+}
+
+#[cfg(any(feature = "i2c", feature = "spi"))]
+impl AddressDecoder {
+    /// Feeds a byte and returns `true` if it completed the address.
+    fn push(&mut self, byte: u8, address_bytes: usize) -> bool {
+        self.address = (self.address << 8) | u16::from(byte);
+        self.received += 1;
+        self.received == address_bytes
+    }
+
+    fn is_complete(&self, address_bytes: usize) -> bool {
+        self.received >= address_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::register_address::{RegisterAddress16, RegisterAddress8};
+
+    #[test]
+    fn register_file_set_get() {
+        let mut registers = RegisterFile::new();
+        registers.set(RegisterAddress16::new(0x1000), &[1, 2, 3]);
+        assert_eq!(
+            registers.get::<_, 3>(RegisterAddress16::new(0x1000)),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            registers.get::<_, 2>(RegisterAddress16::new(0x1002)),
+            [3, 0]
+        );
+    }
+
+    #[test]
+    fn device_auto_increment() {
+        let mut device = MockDevice::new::<RegisterAddress8>();
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x10), &[1, 2]);
+        device.select(0x10);
+        assert_eq!((device.read_byte(), device.read_byte()), (1, 2));
+
+        let mut device = device.with_auto_increment(false);
+        device.select(0x10);
+        assert_eq!((device.read_byte(), device.read_byte()), (1, 1));
+    }
+
+    #[test]
+    fn device_increment_flag() {
+        let mut device = MockDevice::new::<RegisterAddress8>().with_increment_flag(0x80);
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x28), &[1, 2]);
+        device.select(0xA8);
+        assert_eq!((device.read_byte(), device.read_byte()), (1, 2));
+        device.select(0x28);
+        assert_eq!((device.read_byte(), device.read_byte()), (1, 1));
+
+        let mut device = MockDevice::new::<RegisterAddress16>().with_increment_flag(0x80);
+        device.select(0x8100);
+        device.write_byte(3);
+        device.write_byte(4);
+        assert_eq!(
+            device.registers().get(RegisterAddress16::new(0x0100)),
+            [3, 4]
+        );
+    }
+
+    #[test]
+    fn device_hooks() {
+        let mut device = MockDevice::new::<RegisterAddress8>()
+            .on_read(RegisterAddress8::new(0x01), |r| r.set_byte(0x01, 0))
+            .on_write(RegisterAddress8::new(0x02), |r, written, previous| {
+                r.set_byte(0x02, previous & !written);
+            });
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x01), &[0xFF, 0b1111]);
+
+        device.select(0x01);
+        assert_eq!(device.read_byte(), 0xFF);
+        device.select(0x01);
+        assert_eq!(device.read_byte(), 0x00);
+
+        device.select(0x02);
+        device.write_byte(0b0101);
+        assert_eq!(device.registers().byte(0x02), 0b1010);
+    }
+}
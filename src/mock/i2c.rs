@@ -0,0 +1,145 @@
+use crate::i2c::DeviceAddress7;
+use crate::mock::{AddressDecoder, MockDevice};
+use embedded_hal::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+use std::collections::BTreeMap;
+
+/// A simulated I²C bus with any number of attached [`MockDevice`]s.
+///
+/// Transactions addressed to a device that is not attached fail with
+/// [`ErrorKind::NoAcknowledge`] for the address.
+#[derive(Debug, Default)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MockI2c {
+    devices: BTreeMap<u8, MockDevice>,
+}
+
+impl MockI2c {
+    /// Constructs a bus without any attached devices.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches a device at the specified address, replacing any previously attached device.
+    #[must_use]
+    pub fn with_device(mut self, address: DeviceAddress7, device: MockDevice) -> Self {
+        self.attach(address, device);
+        self
+    }
+
+    /// Attaches a device at the specified address, returning any previously attached device.
+    pub fn attach(&mut self, address: DeviceAddress7, device: MockDevice) -> Option<MockDevice> {
+        self.devices.insert(address.into_inner(), device)
+    }
+
+    /// Gets the device attached at the specified address.
+    #[must_use]
+    pub fn device(&self, address: DeviceAddress7) -> Option<&MockDevice> {
+        self.devices.get(&address.into_inner())
+    }
+
+    /// Gets the device attached at the specified address mutably.
+    pub fn device_mut(&mut self, address: DeviceAddress7) -> Option<&mut MockDevice> {
+        self.devices.get_mut(&address.into_inner())
+    }
+}
+
+impl ErrorType for MockI2c {
+    type Error = ErrorKind;
+}
+
+impl I2c for MockI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let device = self
+            .devices
+            .get_mut(&address)
+            .ok_or(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))?;
+
+        let address_bytes = device.address_bytes();
+        let mut decoder = AddressDecoder::default();
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    for byte in &**bytes {
+                        if decoder.is_complete(address_bytes) {
+                            device.write_byte(*byte);
+                        } else if decoder.push(*byte, address_bytes) {
+                            device.select(decoder.address);
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in &mut **buffer {
+                        *byte = device.read_byte();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::RegisterAccess;
+    use crate::i2c::{I2CDevice, RegisterAddress16, RegisterAddress8};
+
+    #[test]
+    fn unknown_address_is_not_acknowledged() {
+        let mut bus = MockI2c::new();
+        assert_eq!(
+            bus.write(0x18, &[0x00]),
+            Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+        );
+    }
+
+    #[test]
+    fn write_and_read_through_access_layer() {
+        let bus = MockI2c::new().with_device(
+            DeviceAddress7::new(0x18),
+            MockDevice::new::<RegisterAddress8>(),
+        );
+        let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[1, 2, 3]),
+            Ok(())
+        );
+
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x21), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [2, 3]);
+
+        let bus = device.release();
+        let registers = bus
+            .device(DeviceAddress7::new(0x18))
+            .map(MockDevice::registers);
+        assert_eq!(
+            registers.map(|r| r.get(RegisterAddress8::new(0x20))),
+            Some([1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn sixteen_bit_register_addresses() {
+        let mut mock = MockDevice::new::<RegisterAddress16>();
+        mock.registers_mut()
+            .set(RegisterAddress16::new(0x3000), &[0xAB, 0xCD]);
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x44), mock);
+        let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x44));
+
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress16::new(0x3000), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0xAB, 0xCD]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::mock::{AddressDecoder, MockDevice};
+use crate::spi::SPIDevice;
+use embedded_hal::spi::{ErrorKind, ErrorType, Operation, SpiDevice};
+
+/// A simulated SPI device wrapping a [`MockDevice`].
+///
+/// Every transaction starts with the register address; a set read flag in the first
+/// address byte selects a read, otherwise the remaining written bytes are stored.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct MockSpi {
+    device: MockDevice,
+    read_flag: u8,
+}
+
+impl MockSpi {
+    /// Constructs a new SPI device using the [default read flag](SPIDevice::DEFAULT_READ_FLAG).
+    #[must_use]
+    pub fn new(device: MockDevice) -> Self {
+        Self {
+            device,
+            read_flag: SPIDevice::<()>::DEFAULT_READ_FLAG,
+        }
+    }
+
+    /// Sets the bits that mark a read in the first address byte.
+    #[must_use]
+    pub fn with_read_flag(mut self, read_flag: u8) -> Self {
+        self.read_flag = read_flag;
+        self
+    }
+
+    /// Gets the simulated device.
+    #[must_use]
+    pub fn device(&self) -> &MockDevice {
+        &self.device
+    }
+
+    /// Gets the simulated device mutably.
+    pub fn device_mut(&mut self) -> &mut MockDevice {
+        &mut self.device
+    }
+}
+
+impl ErrorType for MockSpi {
+    type Error = ErrorKind;
+}
+
+impl SpiDevice for MockSpi {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let address_bytes = self.device.address_bytes();
+        let mut decoder = AddressDecoder::default();
+        let mut read = false;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    for byte in &**bytes {
+                        if decoder.is_complete(address_bytes) {
+                            if read {
+                                return Err(ErrorKind::Other);
+                            }
+                            self.device.write_byte(*byte);
+                        } else {
+                            let mut byte = *byte;
+                            if decoder.received == 0 {
+                                read = byte & self.read_flag != 0;
+                                byte &= !self.read_flag;
+                            }
+                            if decoder.push(byte, address_bytes) {
+                                self.device.select(decoder.address);
+                            }
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    if !read || !decoder.is_complete(address_bytes) {
+                        return Err(ErrorKind::Other);
+                    }
+                    for byte in &mut **buffer {
+                        *byte = self.device.read_byte();
+                    }
+                }
+                Operation::DelayNs(_) => {}
+                Operation::Transfer(_, _) | Operation::TransferInPlace(_) => {
+                    return Err(ErrorKind::Other);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::access::RegisterAccess;
+    use crate::spi::RegisterAddress8;
+
+    #[test]
+    fn write_and_read_through_access_layer() {
+        let mut device = SPIDevice::new(MockSpi::new(MockDevice::new::<RegisterAddress8>()));
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x47, 0x11]),
+            Ok(())
+        );
+
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x20), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x47, 0x11]);
+    }
+
+    #[test]
+    fn read_without_read_flag_fails() {
+        let mut spi = MockSpi::new(MockDevice::new::<RegisterAddress8>());
+        let mut buffer = [0_u8; 1];
+        assert_eq!(
+            spi.transaction(&mut [Operation::Write(&[0x20]), Operation::Read(&mut buffer)]),
+            Err(ErrorKind::Other)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Polling a register until it reaches an expected state.
+//!
+//! Drivers frequently wait for a status bit, such as a data-ready or busy flag, by reading a
+//! register repeatedly. The `wait_until` methods of
+//! [`I2CRegisterAccess`](crate::i2c::I2CRegisterAccess) and
+//! [`SPIRegisterAccess`](crate::spi::SPIRegisterAccess) read the register, test it with a
+//! predicate and delay for the poll interval until the predicate holds or the [`Timeout`]
+//! elapses, in which case they fail with [`Error::Timeout`](crate::error::Error::Timeout).
+//!
+//! With the `async` feature enabled, `wait_until_async` awaits an `embedded-hal-async`
+//! delay between the reads instead of blocking in it. The register reads still use the
+//! blocking bus, so only the delays yield to the executor. The feature requires Rust 1.75.
+//!
+//! The elapsed time is the sum of the delays; the duration of the bus transactions is not
+//! accounted for, so the actual time until a timeout is reported may be longer.
+
+/// The time to wait for a register to reach an expected state, and how often to read it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Timeout {
+    timeout_us: u32,
+    interval_us: u32,
+}
+
+impl Timeout {
+    /// The default interval between two reads, in microseconds.
+    pub const DEFAULT_INTERVAL_US: u32 = 1_000;
+
+    /// Constructs a timeout of the specified number of microseconds.
+    #[must_use]
+    pub const fn from_micros(timeout_us: u32) -> Self {
+        Self {
+            timeout_us,
+            interval_us: Self::DEFAULT_INTERVAL_US,
+        }
+    }
+
+    /// Constructs a timeout of the specified number of milliseconds.
+    #[must_use]
+    pub const fn from_millis(timeout_ms: u32) -> Self {
+        Self::from_micros(timeout_ms.saturating_mul(1_000))
+    }
+
+    /// Sets the interval between two reads, in microseconds.
+    ///
+    /// An interval of zero is treated as one microsecond.
+    #[must_use]
+    pub const fn with_interval_us(mut self, interval_us: u32) -> Self {
+        self.interval_us = if interval_us == 0 { 1 } else { interval_us };
+        self
+    }
+
+    /// Returns the timeout, in microseconds.
+    #[must_use]
+    pub const fn timeout_us(&self) -> u32 {
+        self.timeout_us
+    }
+
+    /// Returns the interval between two reads, in microseconds.
+    #[must_use]
+    pub const fn interval_us(&self) -> u32 {
+        self.interval_us
+    }
+
+    /// Starts tracking the elapsed time of a polling loop.
+    pub(crate) const fn start(self) -> Deadline {
+        Deadline {
+            timeout: self,
+            waited_us: 0,
+        }
+    }
+}
+
+/// The elapsed time of a polling loop.
+pub(crate) struct Deadline {
+    timeout: Timeout,
+    waited_us: u32,
+}
+
+impl Deadline {
+    /// Returns the delay before the next read, or `None` if the timeout has elapsed.
+    ///
+    /// The last delay is shortened so that the final read happens at the timeout.
+    pub(crate) fn next_delay_us(&mut self) -> Option<u32> {
+        let remaining = self.timeout.timeout_us - self.waited_us;
+        if remaining == 0 {
+            return None;
+        }
+        let delay = remaining.min(self.timeout.interval_us);
+        self.waited_us += delay;
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delays_add_up_to_timeout() {
+        let mut deadline = Timeout::from_micros(2_500).start();
+        assert_eq!(deadline.next_delay_us(), Some(1_000));
+        assert_eq!(deadline.next_delay_us(), Some(1_000));
+        assert_eq!(deadline.next_delay_us(), Some(500));
+        assert_eq!(deadline.next_delay_us(), None);
+    }
+
+    #[test]
+    fn zero_timeout_reads_once() {
+        let mut deadline = Timeout::from_millis(0).with_interval_us(0).start();
+        assert_eq!(deadline.next_delay_us(), None);
+        assert_eq!(Timeout::from_millis(1).with_interval_us(0).interval_us(), 1);
+    }
+
+    #[cfg(feature = "i2c")]
+    mod i2c {
+        use super::*;
+        use crate::error::Error;
+        use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegister, I2CRegisterAccess};
+        use crate::mock::{MockDevice, MockI2c};
+        use crate::register_address::RegisterAddress8;
+        use crate::sizes::R1;
+        use crate::test_support::{test_register, Clock};
+
+        test_register!(
+            /// A status register with a data-ready bit.
+            Status(u8): R1
+        );
+
+        impl Status {
+            fn ready(self) -> bool {
+                self.0 & 0x01 != 0
+            }
+        }
+
+        impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for Status {
+            const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+            const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x27);
+            type Backing = u8;
+        }
+
+        /// Constructs a device whose status register reads as ready from the `ready_after`th read.
+        fn device(ready_after: u8) -> I2CDevice<MockI2c> {
+            let mock = MockDevice::new::<RegisterAddress8>().on_read(
+                RegisterAddress8::new(0x27),
+                move |registers| {
+                    let reads = registers.byte(0x80) + 1;
+                    registers.set_byte(0x80, reads);
+                    if reads + 1 >= ready_after {
+                        registers.set_byte(0x27, 0x01);
+                    }
+                },
+            );
+            let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock);
+            I2CDevice::new(bus, DeviceAddress7::new(0x18))
+        }
+
+        #[test]
+        fn waits_until_ready() {
+            let mut clock = Clock::default();
+            let status =
+                device(3).wait_until(|s: &Status| s.ready(), Timeout::from_millis(10), &mut clock);
+            assert_eq!(status, Ok(Status(0x01)));
+            assert_eq!(clock.elapsed_ns, 2_000_000);
+        }
+
+        #[test]
+        fn times_out() {
+            let mut clock = Clock::default();
+            let status = device(u8::MAX).wait_until(
+                |s: &Status| s.ready(),
+                Timeout::from_micros(2_500).with_interval_us(500),
+                &mut clock,
+            );
+            assert_eq!(status, Err(Error::Timeout));
+            assert_eq!(clock.elapsed_ns, 2_500_000);
+        }
+
+        #[test]
+        #[cfg(feature = "async")]
+        fn waits_until_ready_async() {
+            use std::future::Future;
+            use std::sync::Arc;
+            use std::task::{Context, Poll, Wake, Waker};
+
+            struct NoopWaker;
+
+            impl Wake for NoopWaker {
+                fn wake(self: Arc<Self>) {}
+            }
+
+            let mut clock = Clock::default();
+            let mut device = device(2);
+            let waker = Waker::from(Arc::new(NoopWaker));
+            let status = {
+                let future = device.wait_until_async(
+                    |s: &Status| s.ready(),
+                    Timeout::from_millis(10),
+                    &mut clock,
+                );
+                match std::pin::pin!(future).poll(&mut Context::from_waker(&waker)) {
+                    Poll::Ready(status) => status,
+                    Poll::Pending => panic!("the delay never yields"),
+                }
+            };
+            assert_eq!(status, Ok(Status(0x01)));
+            assert_eq!(clock.elapsed_ns, 1_000_000);
+        }
+    }
+}
@@ -0,0 +1,683 @@
+//! Recording and replaying of register traffic.
+//!
+//! A [`Recorder`] wraps any [`RegisterAccess`] implementation, such as an
+//! [`I2CDevice`](crate::i2c::I2CDevice), and logs every successful register access.
+//! The resulting [`Recording`] can be serialized to a stable text format and later be fed
+//! into a [`Replayer`], which acts as the bus in a test and asserts that the driver performs
+//! the identical sequence of accesses.
+//!
+//! ## Text format
+//!
+//! Every access is written on its own line as four whitespace-separated fields:
+//!
+//! ```text
+//! # direction device register data
+//! W 18 20 47
+//! R 18 28 1A00
+//! R - 0F 33
+//! ```
+//!
+//! - The direction is `R` for reads and `W` for writes.
+//! - The device address is given in hexadecimal, or `-` if the bus does not address devices (e.g. SPI).
+//! - The register address is given in hexadecimal using two digits per address byte,
+//!   i.e. `20` for an 8-bit and `0020` for a 16-bit register address.
+//! - The data is given as a sequence of hexadecimal byte pairs, or `-` if no data was transferred.
+//!
+//! Empty lines and lines starting with `#` are ignored.
+
+use crate::access::RegisterAccess;
+use crate::register_address::RegisterAddress;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+/// The direction of a register access.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    /// Data was read from the device.
+    Read,
+    /// Data was written to the device.
+    Write,
+}
+
+/// A single recorded register access.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RecordedAccess {
+    /// The direction of the access.
+    pub direction: Direction,
+    /// The address of the device on the bus, if the bus addresses devices.
+    pub device: Option<u16>,
+    /// The register address.
+    pub register: u16,
+    /// The number of bytes in the register address.
+    pub address_bytes: usize,
+    /// The bytes read or written.
+    pub data: Vec<u8>,
+}
+
+impl RecordedAccess {
+    fn new<A>(direction: Direction, device: Option<u16>, address: &A, data: &[u8]) -> Self
+    where
+        A: RegisterAddress,
+    {
+        Self {
+            direction,
+            device,
+            register: address.value(),
+            address_bytes: A::ADDR_BYTES,
+            data: data.to_vec(),
+        }
+    }
+}
+
+impl Display for RecordedAccess {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_char(match self.direction {
+            Direction::Read => 'R',
+            Direction::Write => 'W',
+        })?;
+        match self.device {
+            Some(device) => write!(f, " {device:02X}")?,
+            None => f.write_str(" -")?,
+        }
+        write!(
+            f,
+            " {:0width$X} ",
+            self.register,
+            width = self.address_bytes * 2
+        )?;
+        if self.data.is_empty() {
+            return f.write_char('-');
+        }
+        for byte in &self.data {
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// An error encountered while parsing a [`Recording`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// The one-based line number.
+    pub line: usize,
+    /// A description of the problem.
+    pub message: &'static str,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl FromStr for RecordedAccess {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let direction = match fields.next() {
+            Some("R") => Direction::Read,
+            Some("W") => Direction::Write,
+            _ => return Err("expected direction R or W"),
+        };
+        let device = match fields.next() {
+            Some("-") => None,
+            Some(device) => {
+                Some(u16::from_str_radix(device, 16).map_err(|_| "invalid device address")?)
+            }
+            None => return Err("missing device address"),
+        };
+        let register = fields.next().ok_or("missing register address")?;
+        if register.is_empty() || register.len() > 4 || register.len() % 2 != 0 {
+            return Err("register address must have two or four digits");
+        }
+        let address_bytes = register.len() / 2;
+        let register = u16::from_str_radix(register, 16).map_err(|_| "invalid register address")?;
+        let data = match fields.next() {
+            Some("-") => Vec::new(),
+            Some(data) => parse_hex(data).ok_or("invalid data")?,
+            None => return Err("missing data"),
+        };
+        if fields.next().is_some() {
+            return Err("unexpected trailing field");
+        }
+        Ok(Self {
+            direction,
+            device,
+            register,
+            address_bytes,
+            data,
+        })
+    }
+}
+
+pub(crate) fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// A sequence of recorded register accesses.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Recording {
+    accesses: Vec<RecordedAccess>,
+}
+
+impl Recording {
+    /// Constructs an empty recording.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an access.
+    pub fn push(&mut self, access: RecordedAccess) {
+        self.accesses.push(access);
+    }
+
+    /// Gets the recorded accesses in order.
+    #[must_use]
+    pub fn accesses(&self) -> &[RecordedAccess] {
+        &self.accesses
+    }
+
+    /// Returns the number of recorded accesses.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.accesses.len()
+    }
+
+    /// Indicates whether no access was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.accesses.is_empty()
+    }
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for access in &self.accesses {
+            writeln!(f, "{access}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut recording = Self::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let access = line.parse().map_err(|message| ParseError {
+                line: index + 1,
+                message,
+            })?;
+            recording.push(access);
+        }
+        Ok(recording)
+    }
+}
+
+/// Records the register accesses performed through the wrapped access layer.
+#[derive(Debug)]
+pub struct Recorder<T> {
+    inner: T,
+    recording: Recording,
+}
+
+impl<T> Recorder<T> {
+    /// Wraps the provided access layer.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            recording: Recording::new(),
+        }
+    }
+
+    /// Gets the accesses recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Consumes self and returns the wrapped access layer and the recording.
+    pub fn into_parts(self) -> (T, Recording) {
+        (self.inner, self.recording)
+    }
+}
+
+impl<T> RegisterAccess for Recorder<T>
+where
+    T: RegisterAccess,
+{
+    type Error = T::Error;
+
+    fn device_address(&self) -> Option<u16> {
+        self.inner.device_address()
+    }
+
+    fn read_raw<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        let access = RecordedAccess::new(Direction::Read, self.device_address(), &address, &[]);
+        self.inner.read_raw(address, buffer)?;
+        self.recording.push(RecordedAccess {
+            data: buffer.to_vec(),
+            ..access
+        });
+        Ok(())
+    }
+
+    fn write_raw<A>(&mut self, address: A, data: &[u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        let access = RecordedAccess::new(Direction::Write, self.device_address(), &address, data);
+        self.inner.write_raw(address, data)?;
+        self.recording.push(access);
+        Ok(())
+    }
+
+    fn read_raw_burst<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        let access = RecordedAccess::new(Direction::Read, self.device_address(), &address, &[]);
+        self.inner.read_raw_burst(address, buffer)?;
+        self.recording.push(RecordedAccess {
+            data: buffer.to_vec(),
+            ..access
+        });
+        Ok(())
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl<T> crate::i2c::I2CRegisterAccess for Recorder<T>
+where
+    T: crate::i2c::I2CRegisterAccess,
+{
+    type DeviceAddress = T::DeviceAddress;
+}
+
+#[cfg(feature = "spi")]
+impl<T> crate::spi::SPIRegisterAccess for Recorder<T> where T: crate::spi::SPIRegisterAccess {}
+
+/// Replays a [`Recording`] as a bus, asserting that the same accesses are performed in the
+/// same order.
+///
+/// The replayer implements the `embedded-hal` I²C and SPI bus traits and takes the place of
+/// the bus below an [`I2CDevice`](crate::i2c::I2CDevice) or
+/// [`SPIDevice`](crate::spi::SPIDevice). Every transaction is decoded into a register access,
+/// taking the device address from the I²C transaction and the number of register address
+/// bytes from the recorded access; a transaction that reads after writing the register
+/// address is a read. Recorded accesses correspond to single transactions for devices using
+/// [`AddressingMode::AutoIncrement`](crate::addressing::AddressingMode::AutoIncrement).
+///
+/// Reads are answered with the recorded data. Any deviation from the recording panics.
+#[derive(Debug)]
+pub struct Replayer {
+    recording: Recording,
+    position: usize,
+    #[cfg(feature = "spi")]
+    read_flag: u8,
+}
+
+impl Replayer {
+    /// Constructs a replayer for the provided recording.
+    #[must_use]
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            position: 0,
+            #[cfg(feature = "spi")]
+            read_flag: crate::spi::SPIDevice::<()>::DEFAULT_READ_FLAG,
+        }
+    }
+
+    /// Sets the bits that mark an SPI read in the first address byte; they are removed before
+    /// the register address is compared.
+    #[cfg(feature = "spi")]
+    #[must_use]
+    pub fn with_read_flag(mut self, read_flag: u8) -> Self {
+        self.read_flag = read_flag;
+        self
+    }
+
+    /// Returns the number of accesses that were not replayed yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.recording.len() - self.position
+    }
+
+    /// Asserts that every recorded access was replayed.
+    ///
+    /// # Panics
+    /// Panics if recorded accesses remain.
+    pub fn finish(self) {
+        assert_eq!(
+            self.remaining(),
+            0,
+            "replay ended early; next expected access: {}",
+            self.recording.accesses()[self.position]
+        );
+    }
+
+    /// Replays a transaction that wrote `written` and reads `read` bytes, if any, and
+    /// returns the data to read.
+    #[cfg(any(feature = "i2c", feature = "spi"))]
+    fn replay(&mut self, device: Option<u16>, written: &[u8], read: Option<usize>) -> &[u8] {
+        let position = self.position;
+        let expected = match self.recording.accesses().get(position) {
+            Some(expected) => expected,
+            None => {
+                panic!("unexpected access #{position} after end of recording: wrote {written:02X?}")
+            }
+        };
+        // Reads write nothing but the register address; writes must at least send the
+        // address width of the recording.
+        let address_bytes = match read {
+            Some(_) => written.len(),
+            None => expected.address_bytes.min(written.len()),
+        };
+        let register = written[..address_bytes]
+            .iter()
+            .fold(0_u16, |value, byte| value << 8 | u16::from(*byte));
+        let actual = RecordedAccess {
+            direction: if read.is_some() {
+                Direction::Read
+            } else {
+                Direction::Write
+            },
+            device,
+            register,
+            address_bytes,
+            data: match read {
+                Some(_) => expected.data.clone(),
+                None => written[address_bytes..].to_vec(),
+            },
+        };
+        match read {
+            Some(len) => assert!(
+                *expected == actual && expected.data.len() == len,
+                "access #{position} differs from recording: expected {expected}, got read of {len} bytes at {actual}"
+            ),
+            None => assert!(
+                *expected == actual,
+                "access #{position} differs from recording: expected {expected}, got {actual}"
+            ),
+        }
+        self.position += 1;
+        &expected.data
+    }
+
+    /// Replays an I²C transaction addressed to `address`.
+    #[cfg(feature = "i2c")]
+    fn replay_i2c(&mut self, address: u16, operations: &mut [embedded_hal::i2c::Operation<'_>]) {
+        use embedded_hal::i2c::Operation;
+
+        let mut written = Vec::new();
+        let mut read = None;
+        for operation in operations.iter() {
+            match operation {
+                Operation::Write(bytes) => {
+                    assert!(read.is_none(), "writes after a read cannot be replayed");
+                    written.extend_from_slice(bytes);
+                }
+                Operation::Read(buffer) => read = Some(read.unwrap_or(0) + buffer.len()),
+            }
+        }
+        let mut data = self.replay(Some(address), &written, read);
+        for operation in operations {
+            if let Operation::Read(buffer) = operation {
+                let (head, tail) = data.split_at(buffer.len());
+                buffer.copy_from_slice(head);
+                data = tail;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl embedded_hal::i2c::ErrorType for Replayer {
+    type Error = embedded_hal::i2c::ErrorKind;
+}
+
+#[cfg(feature = "i2c")]
+impl embedded_hal::i2c::I2c<embedded_hal::i2c::SevenBitAddress> for Replayer {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.replay_i2c(address.into(), operations);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl embedded_hal::i2c::I2c<embedded_hal::i2c::TenBitAddress> for Replayer {
+    fn transaction(
+        &mut self,
+        address: u16,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.replay_i2c(address, operations);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "spi")]
+impl embedded_hal::spi::ErrorType for Replayer {
+    type Error = embedded_hal::spi::ErrorKind;
+}
+
+#[cfg(feature = "spi")]
+impl embedded_hal::spi::SpiDevice for Replayer {
+    fn transaction(
+        &mut self,
+        operations: &mut [embedded_hal::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        use embedded_hal::spi::Operation;
+
+        let mut written = Vec::new();
+        let mut read = None;
+        for operation in operations.iter() {
+            match operation {
+                Operation::Write(bytes) => {
+                    assert!(read.is_none(), "writes after a read cannot be replayed");
+                    written.extend_from_slice(bytes);
+                }
+                Operation::Read(buffer) => read = Some(read.unwrap_or(0) + buffer.len()),
+                Operation::DelayNs(_) => {}
+                Operation::Transfer(_, _) | Operation::TransferInPlace(_) => {
+                    panic!("full-duplex transfers cannot be replayed")
+                }
+            }
+        }
+        if read.is_some() {
+            if let Some(first) = written.first_mut() {
+                *first &= !self.read_flag;
+            }
+        }
+        let mut data = self.replay(None, &written, read);
+        for operation in operations {
+            if let Operation::Read(buffer) = operation {
+                let (head, tail) = data.split_at(buffer.len());
+                buffer.copy_from_slice(head);
+                data = tail;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "i2c"))]
+mod tests {
+    use super::*;
+    use crate::i2c::{DeviceAddress7, I2CDevice, RegisterAddress16, RegisterAddress8};
+    use crate::mock::{MockDevice, MockI2c};
+
+    const TEXT: &str = "\
+# direction device register data
+W 18 20 47
+R 18 28 1A00
+W - 1000 -
+";
+
+    fn parse(text: &str) -> Recording {
+        match text.parse() {
+            Ok(recording) => recording,
+            Err(error) => panic!("invalid recording: {error}"),
+        }
+    }
+
+    #[test]
+    fn parse_and_format_round_trip() {
+        let recording = parse(TEXT);
+        assert_eq!(recording.len(), 3);
+        assert_eq!(
+            recording.accesses()[1],
+            RecordedAccess {
+                direction: Direction::Read,
+                device: Some(0x18),
+                register: 0x28,
+                address_bytes: 1,
+                data: vec![0x1A, 0x00],
+            }
+        );
+        assert_eq!(
+            recording.to_string(),
+            "W 18 20 47\nR 18 28 1A00\nW - 1000 -\n"
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        let error = "W 18 20 47\nX 18 20 47".parse::<Recording>();
+        assert_eq!(
+            error,
+            Err(ParseError {
+                line: 2,
+                message: "expected direction R or W"
+            })
+        );
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let mut mock = MockDevice::new::<RegisterAddress8>();
+        mock.registers_mut()
+            .set(RegisterAddress8::new(0x28), &[0x1A, 0x00]);
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock);
+        let mut recorder = Recorder::new(I2CDevice::new(bus, DeviceAddress7::new(0x18)));
+
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            recorder.write_raw(RegisterAddress8::new(0x20), &[0x47]),
+            Ok(())
+        );
+        assert_eq!(
+            recorder.read_raw(RegisterAddress8::new(0x28), &mut buffer),
+            Ok(())
+        );
+
+        let (_, recording) = recorder.into_parts();
+        assert_eq!(recording.to_string(), "W 18 20 47\nR 18 28 1A00\n");
+
+        let mut device = I2CDevice::new(Replayer::new(recording), DeviceAddress7::new(0x18));
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x47]),
+            Ok(())
+        );
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x28), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x1A, 0x00]);
+        device.release().finish();
+    }
+
+    fn replay(text: &str, device: u8) -> I2CDevice<Replayer> {
+        I2CDevice::new(Replayer::new(parse(text)), DeviceAddress7::new(device))
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_different_write() {
+        let _ = replay("W 18 20 47", 0x18).write_raw(RegisterAddress8::new(0x20), &[0x48]);
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_different_address_width() {
+        let _ = replay("W 18 20 47", 0x18).write_raw(RegisterAddress16::new(0x20), &[0x47]);
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_different_device() {
+        let mut buffer = [0_u8; 2];
+        let _ = replay("R 18 28 1A00", 0x19).read_raw(RegisterAddress8::new(0x28), &mut buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_different_read_length() {
+        let mut buffer = [0_u8; 1];
+        let _ = replay("R 18 28 1A00", 0x18).read_raw(RegisterAddress8::new(0x28), &mut buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_narrower_register_address() {
+        let mut buffer = [0_u8; 2];
+        let _ = replay("R 18 0028 1A00", 0x18).read_raw(RegisterAddress8::new(0x28), &mut buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "access #0 differs from recording")]
+    fn replay_detects_truncated_write() {
+        let mut replayer = Replayer::new(parse("W 18 0020 47"));
+        let _ = embedded_hal::i2c::I2c::write(&mut replayer, 0x18_u8, &[0x00]);
+    }
+
+    #[test]
+    #[cfg(feature = "spi")]
+    fn replay_spi() {
+        use crate::spi::SPIDevice;
+
+        let mut device = SPIDevice::new(Replayer::new(parse("W - 20 47\nR - 28 1A00")));
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x47]),
+            Ok(())
+        );
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x28), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x1A, 0x00]);
+        device.release().finish();
+    }
+
+    #[test]
+    #[should_panic(expected = "replay ended early")]
+    fn replay_detects_missing_accesses() {
+        let replayer = Replayer::new(parse("W 18 20 47"));
+        replayer.finish();
+    }
+}
@@ -1,5 +1,7 @@
 //! Traits and types for register addresses.
 
+use crate::error::Error;
+use core::convert::Infallible;
 use core::ops::Deref;
 
 /// A register address.
@@ -7,21 +9,36 @@ pub trait RegisterAddress {
     /// The number of bits of the address.
     const ADDR_BITS: usize;
 
+    /// The number of bytes required to transmit the address.
+    const ADDR_BYTES: usize = (Self::ADDR_BITS + 7) / 8;
+
     /// Returns the number of bits in the address.
     #[must_use]
     fn addr_bits(&self) -> usize {
         Self::ADDR_BITS
     }
+
+    /// Returns the address value, widened to 16 bits.
+    #[must_use]
+    fn value(&self) -> u16;
 }
 
 /// Implements 8-bit register addresses.
 impl RegisterAddress for u8 {
     const ADDR_BITS: usize = 8;
+
+    fn value(&self) -> u16 {
+        (*self).into()
+    }
 }
 
 /// Implements 16-bit register addresses.
 impl RegisterAddress for u16 {
     const ADDR_BITS: usize = 16;
+
+    fn value(&self) -> u16 {
+        *self
+    }
 }
 
 /// An 6-bit register address.
@@ -45,6 +62,10 @@ impl RegisterAddress6 {
 
 impl RegisterAddress for RegisterAddress6 {
     const ADDR_BITS: usize = 6;
+
+    fn value(&self) -> u16 {
+        self.0.into()
+    }
 }
 
 impl From<u8> for RegisterAddress6 {
@@ -74,6 +95,10 @@ impl RegisterAddress8 {
 
 impl RegisterAddress for RegisterAddress8 {
     const ADDR_BITS: usize = 8;
+
+    fn value(&self) -> u16 {
+        self.0.into()
+    }
 }
 
 impl From<u8> for RegisterAddress8 {
@@ -103,6 +128,10 @@ impl RegisterAddress16 {
 
 impl RegisterAddress for RegisterAddress16 {
     const ADDR_BITS: usize = 16;
+
+    fn value(&self) -> u16 {
+        self.0
+    }
 }
 
 impl From<u8> for RegisterAddress16 {
@@ -124,12 +153,13 @@ impl From<RegisterAddress8> for RegisterAddress16 {
 }
 
 impl TryFrom<RegisterAddress16> for RegisterAddress8 {
-    type Error = <u16 as TryInto<u8>>::Error;
+    type Error = Error<Infallible>;
 
+    /// Converts the address, failing with [`Error::AddressOutOfRange`] if it exceeds 8 bits.
     fn try_from(value: RegisterAddress16) -> Result<Self, Self::Error> {
-        let value = value.into_inner();
-        let addr_8: u8 = value.try_into()?;
-        Ok(Self(addr_8))
+        u8::try_from(value.into_inner())
+            .map(Self)
+            .map_err(|_| Error::AddressOutOfRange)
     }
 }
 
@@ -202,6 +232,55 @@ impl core::fmt::Display for RegisterAddress16 {
     }
 }
 
+#[cfg(feature = "serde")]
+impl_serde_address!(
+    RegisterAddress6,
+    u8,
+    serialize_u8,
+    0b0011_1111,
+    "a 6-bit register address"
+);
+#[cfg(feature = "serde")]
+impl_serde_address!(
+    RegisterAddress8,
+    u8,
+    serialize_u8,
+    u8::MAX,
+    "an 8-bit register address"
+);
+#[cfg(feature = "serde")]
+impl_serde_address!(
+    RegisterAddress16,
+    u16,
+    serialize_u16,
+    u16::MAX,
+    "a 16-bit register address"
+);
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for RegisterAddress6 {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "0x{=u8:02X} ({=u8:06b})", self.0, self.0);
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for RegisterAddress8 {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "0x{=u8:02X} ({=u8:08b})", self.0, self.0);
+    }
+}
+
+#[cfg(feature = "defmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+impl defmt::Format for RegisterAddress16 {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "0x{=u16:04X} ({=u16:016b})", self.0, self.0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,6 +299,14 @@ mod tests {
         assert_eq!(addr.addr_bits(), 16);
     }
 
+    #[test]
+    fn addr_bytes() {
+        assert_eq!(RegisterAddress6::ADDR_BYTES, 1);
+        assert_eq!(RegisterAddress8::ADDR_BYTES, 1);
+        assert_eq!(RegisterAddress16::ADDR_BYTES, 2);
+        assert_eq!(RegisterAddress16::new(0x1234).value(), 0x1234);
+    }
+
     #[test]
     fn reg16_from_u16() {
         let addr = RegisterAddress16::from(0b11_1111_0000_u16);
@@ -239,6 +326,36 @@ mod tests {
         assert_eq!(addr.into_inner(), 0b0000_0000_1111_0000);
     }
 
+    #[test]
+    fn reg8_try_from_reg16() {
+        assert_eq!(
+            RegisterAddress8::try_from(RegisterAddress16::new(0xFF)),
+            Ok(RegisterAddress8::new(0xFF))
+        );
+        assert_eq!(
+            RegisterAddress8::try_from(RegisterAddress16::new(0x100)),
+            Err(Error::AddressOutOfRange)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn reg6_serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+        assert_tokens(&RegisterAddress6::new(0x3F), &[Token::U8(0x3F)]);
+        assert_de_tokens_error::<RegisterAddress6>(
+            &[Token::U8(0x40)],
+            "invalid value: integer `64`, expected a 6-bit register address",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn reg16_serde() {
+        use serde_test::{assert_tokens, Token};
+        assert_tokens(&RegisterAddress16::new(0x1234), &[Token::U16(0x1234)]);
+    }
+
     #[test]
     fn reg8_debug() {
         let addr = RegisterAddress8::new(0b0011_0011);
@@ -0,0 +1,519 @@
+//! Register sequences such as initialization scripts, described as data.
+//!
+//! Datasheets often specify the initialization of a device as an ordered list of register
+//! writes and delays. A [`Sequence`] captures such a list as a `const` slice of [`Step`]s,
+//! which [`Sequence::run`] executes over any [`RegisterAccess`]. If a step fails, the
+//! returned [`SequenceError`] reports its index.
+//!
+//! ```
+//! # #[cfg(feature = "i2c")]
+//! # fn main() {
+//! use hardware_registers::i2c::{DeviceAddress7, I2CRegister, RegisterAddress8};
+//! use hardware_registers::poll::Timeout;
+//! use hardware_registers::sequence::{Sequence, Step};
+//! use hardware_registers::sizes::R1;
+//! use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+//!
+//! #[derive(Debug, Copy, Clone)]
+//! struct PowerManagement(u8);
+//!
+//! impl HardwareRegister<R1> for PowerManagement {}
+//! impl WritableHardwareRegister<R1> for PowerManagement {}
+//!
+//! impl ToBits for PowerManagement {
+//!     type Target = u8;
+//!
+//!     fn to_bits(&self) -> u8 {
+//!         self.0
+//!     }
+//! }
+//!
+//! impl FromBits<u8> for PowerManagement {
+//!     fn from_bits_ref(value: &u8) -> Self {
+//!         Self(*value)
+//!     }
+//! }
+//!
+//! impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for PowerManagement {
+//!     const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x68);
+//!     const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x6B);
+//!     type Backing = u8;
+//! }
+//!
+//! /// Resets the device, waits for the reset bit to clear and selects the PLL clock.
+//! const INIT: Sequence<'static, RegisterAddress8> = Sequence::new(&[
+//!     Step::write_i2c::<PowerManagement, _, _>(0x80),
+//!     Step::delay_ms(100),
+//!     Step::poll_i2c::<PowerManagement, _, _>(0x80, 0x00, Timeout::from_millis(10)),
+//!     Step::modify_i2c::<PowerManagement, _, _>(0x07, 0x01),
+//! ]);
+//! # assert_eq!(INIT.steps().len(), 4);
+//! # }
+//! # #[cfg(not(feature = "i2c"))]
+//! # fn main() {}
+//! ```
+
+use crate::access::RegisterAccess;
+use crate::backing::{ByteOrder, RegisterBacking};
+use crate::error::Error;
+use crate::poll::Timeout;
+use crate::register_address::RegisterAddress;
+use crate::sizes::RegisterSizeInformation;
+use core::fmt::{Debug, Display, Formatter};
+use embedded_hal::delay::DelayNs;
+
+/// The largest register, in bytes, that a step can access.
+const MAX_STEP_BYTES: usize = 16;
+
+/// The location and layout of a register accessed by a [`Step`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Location<A> {
+    address: A,
+    bytes: usize,
+    byte_order: ByteOrder,
+}
+
+impl<A> Location<A> {
+    /// Constructs the location of a register of `bytes` bytes.
+    ///
+    /// # Panics
+    /// Panics if the register is empty or larger than 16 bytes, i.e. does not fit a `u128`.
+    #[must_use]
+    pub const fn new(address: A, bytes: usize, byte_order: ByteOrder) -> Self {
+        assert!(
+            bytes > 0 && bytes <= MAX_STEP_BYTES,
+            "sequence steps support registers of 1 to 16 bytes"
+        );
+        Self {
+            address,
+            bytes,
+            byte_order,
+        }
+    }
+
+    /// Returns the register address.
+    pub const fn address(&self) -> &A {
+        &self.address
+    }
+
+    /// Returns the register size in bytes.
+    #[must_use]
+    pub const fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Returns the byte order of the register.
+    #[must_use]
+    pub const fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+}
+
+/// A step of a [`Sequence`].
+///
+/// Register values are given as bits, since register types cannot be converted in `const`
+/// contexts. Steps are usually built with the typed constructors, such as
+/// [`Step::write_i2c`] or [`Step::modify_spi`], which take the location from the register type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Step<A> {
+    /// Writes a value to a register.
+    Write {
+        /// The register to write.
+        register: Location<A>,
+        /// The value to write.
+        value: u128,
+    },
+    /// Reads a register, replaces the bits under `mask` with those of `value` and writes it
+    /// back, e.g. to update a field.
+    Modify {
+        /// The register to modify.
+        register: Location<A>,
+        /// The bits to replace.
+        mask: u128,
+        /// The new value of the bits under the mask.
+        value: u128,
+    },
+    /// Waits for the specified number of microseconds.
+    Delay {
+        /// The delay, in microseconds.
+        us: u32,
+    },
+    /// Reads a register until the bits under `mask` equal those of `value`.
+    PollUntil {
+        /// The register to read.
+        register: Location<A>,
+        /// The bits to compare.
+        mask: u128,
+        /// The expected value of the bits under the mask.
+        value: u128,
+        /// The time to wait for the expected value.
+        timeout: Timeout,
+    },
+}
+
+impl<A> Step<A> {
+    /// Waits for the specified number of microseconds.
+    #[must_use]
+    pub const fn delay_us(us: u32) -> Self {
+        Self::Delay { us }
+    }
+
+    /// Waits for the specified number of milliseconds.
+    #[must_use]
+    pub const fn delay_ms(ms: u32) -> Self {
+        Self::Delay {
+            us: ms.saturating_mul(1_000),
+        }
+    }
+}
+
+#[cfg(feature = "i2c")]
+impl<A> Step<A>
+where
+    A: RegisterAddress,
+{
+    const fn i2c_location<Reg, D, Size>() -> Location<A>
+    where
+        Reg: crate::i2c::I2CRegister<D, A, Size>,
+        D: crate::i2c::DeviceAddress,
+        Size: RegisterSizeInformation,
+    {
+        Location::new(
+            <Reg as crate::i2c::I2CRegister<D, A, Size>>::REGISTER_ADDRESS,
+            Size::BYTES,
+            Reg::BYTE_ORDER,
+        )
+    }
+
+    /// Writes the bits of a value to an I²C register.
+    #[must_use]
+    pub const fn write_i2c<Reg, D, Size>(value: u128) -> Self
+    where
+        Reg: crate::i2c::WritableI2CRegister<D, A, Size>,
+        D: crate::i2c::DeviceAddress,
+        Size: RegisterSizeInformation,
+    {
+        Self::Write {
+            register: Self::i2c_location::<Reg, D, Size>(),
+            value,
+        }
+    }
+
+    /// Replaces the bits under `mask` of an I²C register with those of `value`.
+    #[must_use]
+    pub const fn modify_i2c<Reg, D, Size>(mask: u128, value: u128) -> Self
+    where
+        Reg: crate::i2c::WritableI2CRegister<D, A, Size>,
+        D: crate::i2c::DeviceAddress,
+        Size: RegisterSizeInformation,
+    {
+        Self::Modify {
+            register: Self::i2c_location::<Reg, D, Size>(),
+            mask,
+            value,
+        }
+    }
+
+    /// Reads an I²C register until the bits under `mask` equal those of `value`.
+    #[must_use]
+    pub const fn poll_i2c<Reg, D, Size>(mask: u128, value: u128, timeout: Timeout) -> Self
+    where
+        Reg: crate::i2c::I2CRegister<D, A, Size>,
+        D: crate::i2c::DeviceAddress,
+        Size: RegisterSizeInformation,
+    {
+        Self::PollUntil {
+            register: Self::i2c_location::<Reg, D, Size>(),
+            mask,
+            value,
+            timeout,
+        }
+    }
+}
+
+#[cfg(feature = "spi")]
+impl<A> Step<A>
+where
+    A: RegisterAddress,
+{
+    const fn spi_location<Reg, Size>() -> Location<A>
+    where
+        Reg: crate::spi::SPIRegister<A, Size>,
+        Size: RegisterSizeInformation,
+    {
+        Location::new(
+            <Reg as crate::spi::SPIRegister<A, Size>>::REGISTER_ADDRESS,
+            Size::BYTES,
+            Reg::BYTE_ORDER,
+        )
+    }
+
+    /// Writes the bits of a value to an SPI register.
+    #[must_use]
+    pub const fn write_spi<Reg, Size>(value: u128) -> Self
+    where
+        Reg: crate::spi::WritableSPIRegister<A, Size>,
+        Size: RegisterSizeInformation,
+    {
+        Self::Write {
+            register: Self::spi_location::<Reg, Size>(),
+            value,
+        }
+    }
+
+    /// Replaces the bits under `mask` of an SPI register with those of `value`.
+    #[must_use]
+    pub const fn modify_spi<Reg, Size>(mask: u128, value: u128) -> Self
+    where
+        Reg: crate::spi::WritableSPIRegister<A, Size>,
+        Size: RegisterSizeInformation,
+    {
+        Self::Modify {
+            register: Self::spi_location::<Reg, Size>(),
+            mask,
+            value,
+        }
+    }
+
+    /// Reads an SPI register until the bits under `mask` equal those of `value`.
+    #[must_use]
+    pub const fn poll_spi<Reg, Size>(mask: u128, value: u128, timeout: Timeout) -> Self
+    where
+        Reg: crate::spi::SPIRegister<A, Size>,
+        Size: RegisterSizeInformation,
+    {
+        Self::PollUntil {
+            register: Self::spi_location::<Reg, Size>(),
+            mask,
+            value,
+            timeout,
+        }
+    }
+}
+
+/// The failure of a step of a [`Sequence`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SequenceError<E> {
+    /// The index of the failed step.
+    pub step: usize,
+    /// The cause of the failure.
+    pub error: Error<E>,
+}
+
+impl<E> Display for SequenceError<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "step {} failed: {}", self.step, self.error)
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl<E> std::error::Error for SequenceError<E> where E: Debug {}
+
+/// An ordered list of register operations and delays.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sequence<'a, A> {
+    steps: &'a [Step<A>],
+}
+
+impl<'a, A> Sequence<'a, A> {
+    /// Constructs a sequence of the provided steps.
+    #[must_use]
+    pub const fn new(steps: &'a [Step<A>]) -> Self {
+        Self { steps }
+    }
+
+    /// Gets the steps of the sequence.
+    #[must_use]
+    pub const fn steps(&self) -> &'a [Step<A>] {
+        self.steps
+    }
+}
+
+impl<A> Sequence<'_, A>
+where
+    A: RegisterAddress + Copy,
+{
+    /// Executes the steps in order, stopping at the first failing step.
+    ///
+    /// # Errors
+    /// Returns the index of the failing step along with [`Error::Bus`] if a bus transaction
+    /// failed or [`Error::Timeout`] if a polled register did not reach the expected value.
+    pub fn run<T, D>(&self, access: &mut T, delay: &mut D) -> Result<(), SequenceError<T::Error>>
+    where
+        T: RegisterAccess,
+        D: DelayNs,
+    {
+        for (index, step) in self.steps.iter().enumerate() {
+            run_step(step, access, delay).map_err(|error| SequenceError { step: index, error })?;
+        }
+        Ok(())
+    }
+}
+
+fn run_step<A, T, D>(step: &Step<A>, access: &mut T, delay: &mut D) -> Result<(), Error<T::Error>>
+where
+    A: RegisterAddress + Copy,
+    T: RegisterAccess,
+    D: DelayNs,
+{
+    match *step {
+        Step::Write { register, value } => write(access, &register, value),
+        Step::Modify {
+            register,
+            mask,
+            value,
+        } => {
+            let current = read(access, &register)?;
+            write(access, &register, (current & !mask) | (value & mask))
+        }
+        Step::Delay { us } => {
+            delay.delay_us(us);
+            Ok(())
+        }
+        Step::PollUntil {
+            register,
+            mask,
+            value,
+            timeout,
+        } => {
+            let mut deadline = timeout.start();
+            loop {
+                if read(access, &register)? & mask == value & mask {
+                    return Ok(());
+                }
+                match deadline.next_delay_us() {
+                    Some(us) => delay.delay_us(us),
+                    None => return Err(Error::Timeout),
+                }
+            }
+        }
+    }
+}
+
+fn read<A, T>(access: &mut T, register: &Location<A>) -> Result<u128, Error<T::Error>>
+where
+    A: RegisterAddress + Copy,
+    T: RegisterAccess,
+{
+    let mut buffer = [0_u8; MAX_STEP_BYTES];
+    let buffer = &mut buffer[..register.bytes];
+    access
+        .read_raw(register.address, buffer)
+        .map_err(Error::Bus)?;
+    Ok(u128::from_register_bytes(buffer, register.byte_order))
+}
+
+fn write<A, T>(access: &mut T, register: &Location<A>, value: u128) -> Result<(), Error<T::Error>>
+where
+    A: RegisterAddress + Copy,
+    T: RegisterAccess,
+{
+    let mut buffer = [0_u8; MAX_STEP_BYTES];
+    let buffer = &mut buffer[..register.bytes];
+    value.to_register_bytes(buffer, register.byte_order);
+    access
+        .write_raw(register.address, buffer)
+        .map_err(Error::Bus)
+}
+
+#[cfg(all(test, feature = "i2c"))]
+mod tests {
+    use super::*;
+    use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegister, RegisterAddress8};
+    use crate::mock::{MockDevice, MockI2c};
+    use crate::sizes::{R1, R2};
+    use crate::test_support::{test_register, Clock};
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+    test_register!(PowerManagement(u8): R1, writable);
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for PowerManagement {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x68);
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x6B);
+        type Backing = u8;
+    }
+
+    test_register!(Threshold(u16): R2, writable, ByteOrder::LittleEndian);
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R2> for Threshold {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x68);
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        type Backing = u16;
+    }
+
+    const INIT: Sequence<'static, RegisterAddress8> = Sequence::new(&[
+        Step::write_i2c::<PowerManagement, _, _>(0x80),
+        Step::delay_ms(100),
+        Step::poll_i2c::<PowerManagement, _, _>(0x80, 0x00, Timeout::from_millis(10)),
+        Step::modify_i2c::<PowerManagement, _, _>(0x07, 0x01),
+        Step::write_i2c::<Threshold, _, _>(0x1234),
+    ]);
+
+    /// Constructs a device whose reset bit clears on the second read after it was set.
+    fn device() -> I2CDevice<MockI2c> {
+        let mock = MockDevice::new::<RegisterAddress8>()
+            .on_write(RegisterAddress8::new(0x6B), |registers, written, _| {
+                registers.set_byte(0x6B, written | 0x40);
+                registers.set_byte(0x80, 0);
+            })
+            .on_read(RegisterAddress8::new(0x6B), |registers| {
+                let reads = registers.byte(0x80) + 1;
+                registers.set_byte(0x80, reads);
+                if reads == 1 {
+                    let value = registers.byte(0x6B);
+                    registers.set_byte(0x6B, value & !0x80);
+                }
+            });
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x68), mock);
+        I2CDevice::new(bus, DeviceAddress7::new(0x68))
+    }
+
+    #[test]
+    fn runs_steps_in_order() {
+        let mut device = device();
+        let mut clock = Clock::default();
+        assert_eq!(INIT.run(&mut device, &mut clock), Ok(()));
+        assert_eq!(clock.elapsed_ns, 101_000_000);
+
+        let bus = device.release();
+        let registers = bus
+            .device(DeviceAddress7::new(0x68))
+            .map(MockDevice::registers);
+        assert_eq!(registers.map(|r| r.byte(0x6B)), Some(0x41));
+        assert_eq!(
+            registers.map(|r| r.get::<_, 2>(RegisterAddress8::new(0x20))),
+            Some([0x34, 0x12])
+        );
+    }
+
+    #[test]
+    fn reports_failed_step() {
+        let mut absent = I2CDevice::new(MockI2c::new(), DeviceAddress7::new(0x68));
+        let mut clock = Clock::default();
+        let error = INIT.run(&mut absent, &mut clock);
+        assert_eq!(
+            error,
+            Err(SequenceError {
+                step: 0,
+                error: Error::Bus(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+            })
+        );
+
+        let steps = [
+            Step::delay_us(5),
+            Step::poll_i2c::<PowerManagement, _, _>(0x01, 0x01, Timeout::from_micros(3)),
+        ];
+        let stuck = Sequence::new(&steps);
+        let mut device = device();
+        assert_eq!(
+            stuck
+                .run(&mut device, &mut clock)
+                .map_err(|e| e.to_string()),
+            Err("step 1 failed: timed out".into())
+        );
+    }
+}
@@ -1,5 +1,8 @@
 //! Provides traits and types to identify register sizes.
 
+/// The size of the largest supported register ([`R64`]), in bytes.
+pub const MAX_BYTES: usize = R64::BYTES;
+
 /// Accesses register size information.
 #[allow(clippy::module_name_repetitions)]
 pub trait RegisterSizeInformation {
@@ -76,6 +79,52 @@ macro_rules! impl_register_size {
                 f.write_str(" bits)")
             }
         }
+
+        /// Serializes as the register size in bytes.
+        #[cfg(feature = "serde")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl serde::Serialize for $ident {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_u64($bytes)
+            }
+        }
+
+        /// Deserializes from the register size in bytes, which must match exactly.
+        #[cfg(feature = "serde")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+        impl<'de> serde::Deserialize<'de> for $ident {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = <u64 as serde::Deserialize>::deserialize(deserializer)?;
+                if value != $bytes {
+                    return Err(serde::de::Error::invalid_value(
+                        serde::de::Unexpected::Unsigned(value),
+                        &concat!("a register size of ", $bytes, " bytes"),
+                    ));
+                }
+                Ok(Self)
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "defmt")))]
+        impl defmt::Format for $ident {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                let plural = if self.bytes() > 1 { "s" } else { "" };
+                defmt::write!(
+                    f,
+                    "{=usize} byte{=str} ({=usize} bits)",
+                    self.bytes(),
+                    plural,
+                    self.bits()
+                );
+            }
+        }
     };
 }
 
@@ -97,6 +146,17 @@ mod tests {
         test_format::assert_debug_fmt!(R4, "4 bytes (32 bits)");
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    pub fn serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+        assert_tokens(&R4, &[Token::U64(4)]);
+        assert_de_tokens_error::<R4>(
+            &[Token::U64(2)],
+            "invalid value: integer `2`, expected a register size of 4 bytes",
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     pub fn display() {
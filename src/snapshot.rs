@@ -0,0 +1,390 @@
+//! Whole-device register dumps.
+//!
+//! A [`Snapshot`] captures every readable register of a [`RegisterMap`] in address order.
+//! Two snapshots can be compared with [`Snapshot::diff`], and a snapshot can be written back
+//! to the device with [`Snapshot::restore`], which only writes registers the map describes as
+//! writable.
+//!
+//! ## Text format
+//!
+//! Snapshots serialize to a line-based text format similar to that of
+//! [`Recording`](crate::recording::Recording), with one register per line:
+//!
+//! ```text
+//! # register data
+//! 0F 33
+//! 20 47
+//! 28 1A00
+//! ```
+//!
+//! - The register address is given in hexadecimal using two digits per address byte.
+//! - The data is given as a sequence of hexadecimal byte pairs; its length is the register size.
+//!
+//! Empty lines and lines starting with `#` are ignored.
+
+use crate::access::RegisterAccess;
+use crate::map::RegisterMap;
+use crate::recording::{parse_hex, ParseError};
+use crate::register_address::RegisterAddress;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The saved content of a single register.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SnapshotEntry {
+    /// The register address.
+    pub register: u16,
+    /// The number of bytes in the register address.
+    pub address_bytes: usize,
+    /// The raw register bytes, in transfer order.
+    pub data: Vec<u8>,
+}
+
+impl Display for SnapshotEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:0width$X} ",
+            self.register,
+            width = self.address_bytes * 2
+        )?;
+        write_hex(f, &self.data)
+    }
+}
+
+impl FromStr for SnapshotEntry {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split_whitespace();
+        let register = fields.next().ok_or("missing register address")?;
+        if register.is_empty() || register.len() > 4 || register.len() % 2 != 0 {
+            return Err("register address must have two or four digits");
+        }
+        let address_bytes = register.len() / 2;
+        let register = u16::from_str_radix(register, 16).map_err(|_| "invalid register address")?;
+        let data = parse_hex(fields.next().ok_or("missing data")?).ok_or("invalid data")?;
+        if data.is_empty() {
+            return Err("missing data");
+        }
+        if fields.next().is_some() {
+            return Err("unexpected trailing field");
+        }
+        Ok(Self {
+            register,
+            address_bytes,
+            data,
+        })
+    }
+}
+
+fn write_hex(f: &mut Formatter<'_>, data: &[u8]) -> std::fmt::Result {
+    data.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+}
+
+/// A register whose content differs between two snapshots.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Difference {
+    /// The register address.
+    pub register: u16,
+    /// The number of bytes in the register address.
+    pub address_bytes: usize,
+    /// The register content in the first snapshot, if it was captured.
+    pub before: Option<Vec<u8>>,
+    /// The register content in the second snapshot, if it was captured.
+    pub after: Option<Vec<u8>>,
+}
+
+impl Display for Difference {
+    /// Formats the difference as `20: 47 -> 57`, using `-` for a register missing on one side.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:0width$X}: ",
+            self.register,
+            width = self.address_bytes * 2
+        )?;
+        match &self.before {
+            Some(data) => write_hex(f, data)?,
+            None => f.write_str("-")?,
+        }
+        f.write_str(" -> ")?;
+        match &self.after {
+            Some(data) => write_hex(f, data),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+/// The content of all readable registers of a device, in address order.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Snapshot {
+    entries: Vec<SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// Constructs an empty snapshot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads every readable register of the map.
+    ///
+    /// Registers are read in address order, regardless of their order in the map.
+    ///
+    /// # Errors
+    /// Returns the first bus error encountered.
+    pub fn capture<A, T>(map: &RegisterMap<A>, access: &mut T) -> Result<Self, T::Error>
+    where
+        A: RegisterAddress + Copy,
+        T: RegisterAccess,
+    {
+        let mut registers: Vec<_> = map
+            .registers
+            .iter()
+            .filter(|r| r.access.is_readable())
+            .collect();
+        registers.sort_by_key(|r| r.address.value());
+
+        let mut snapshot = Self::new();
+        for register in registers {
+            let mut data = vec![0; register.size];
+            access.read_raw(register.address, &mut data)?;
+            snapshot.insert(SnapshotEntry {
+                register: register.address.value(),
+                address_bytes: A::ADDR_BYTES,
+                data,
+            });
+        }
+        Ok(snapshot)
+    }
+
+    /// Adds an entry, replacing any entry for the same register address.
+    pub fn insert(&mut self, entry: SnapshotEntry) {
+        match self
+            .entries
+            .binary_search_by_key(&entry.register, |e| e.register)
+        {
+            Ok(index) => self.entries[index] = entry,
+            Err(index) => self.entries.insert(index, entry),
+        }
+    }
+
+    /// Gets the saved content of the register at the specified address.
+    #[must_use]
+    pub fn get(&self, register: u16) -> Option<&SnapshotEntry> {
+        self.entries
+            .binary_search_by_key(&register, |e| e.register)
+            .ok()
+            .map(|index| &self.entries[index])
+    }
+
+    /// Gets the saved registers in address order.
+    #[must_use]
+    pub fn entries(&self) -> &[SnapshotEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of saved registers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether no register was saved.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lists the registers whose content differs from `other`, in address order.
+    ///
+    /// Registers present in only one of the snapshots are reported as well.
+    #[must_use]
+    pub fn diff(&self, other: &Snapshot) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let (mut before, mut after) = (
+            self.entries.iter().peekable(),
+            other.entries.iter().peekable(),
+        );
+        loop {
+            let (entry, b, a) = match (before.peek().copied(), after.peek().copied()) {
+                (None, None) => break,
+                (Some(b), Some(a)) if b.register == a.register => {
+                    before.next();
+                    after.next();
+                    (b, Some(b), Some(a))
+                }
+                (Some(b), Some(a)) if b.register < a.register => {
+                    before.next();
+                    (b, Some(b), None)
+                }
+                (Some(b), None) => {
+                    before.next();
+                    (b, Some(b), None)
+                }
+                (_, Some(a)) => {
+                    after.next();
+                    (a, None, Some(a))
+                }
+            };
+            if b.map(|e| &e.data) != a.map(|e| &e.data) {
+                differences.push(Difference {
+                    register: entry.register,
+                    address_bytes: entry.address_bytes,
+                    before: b.map(|e| e.data.clone()),
+                    after: a.map(|e| e.data.clone()),
+                });
+            }
+        }
+        differences
+    }
+
+    /// Writes the saved content back to all registers the map describes as writable.
+    ///
+    /// Registers are written in address order. Saved registers that are missing from the map,
+    /// are not writable or whose size differs from the map are skipped. Returns the number of
+    /// registers written.
+    ///
+    /// # Errors
+    /// Returns the first bus error encountered; registers after the failing one are not written.
+    pub fn restore<A, T>(&self, map: &RegisterMap<A>, access: &mut T) -> Result<usize, T::Error>
+    where
+        A: RegisterAddress + Copy,
+        T: RegisterAccess,
+    {
+        let mut written = 0;
+        for entry in &self.entries {
+            let register = map.registers.iter().find(|r| {
+                r.address.value() == entry.register
+                    && r.access.is_writable()
+                    && r.size == entry.data.len()
+            });
+            if let Some(register) = register {
+                access.write_raw(register.address, &entry.data)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut snapshot = Self::new();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line.parse().map_err(|message| ParseError {
+                line: index + 1,
+                message,
+            })?;
+            snapshot.insert(entry);
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(all(test, feature = "i2c"))]
+mod tests {
+    use super::*;
+    use crate::i2c::{DeviceAddress7, I2CDevice, RegisterAddress8};
+    use crate::map::{Access, RegisterInfo};
+    use crate::mock::{MockDevice, MockI2c};
+
+    const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("OUT_X", RegisterAddress8::new(0x28), 2, Access::ReadOnly),
+        RegisterInfo::new("WHO_AM_I", RegisterAddress8::new(0x0F), 1, Access::ReadOnly),
+        RegisterInfo::new(
+            "CTRL_REG1",
+            RegisterAddress8::new(0x20),
+            1,
+            Access::ReadWrite,
+        ),
+        RegisterInfo::new("RESET", RegisterAddress8::new(0x30), 1, Access::WriteOnly),
+    ];
+
+    const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("TEST", REGISTERS);
+
+    fn parse(text: &str) -> Snapshot {
+        match text.parse() {
+            Ok(snapshot) => snapshot,
+            Err(error) => panic!("invalid snapshot: {error}"),
+        }
+    }
+
+    fn device() -> I2CDevice<MockI2c, DeviceAddress7> {
+        let mut mock = MockDevice::new::<RegisterAddress8>();
+        mock.registers_mut()
+            .set(RegisterAddress8::new(0x0F), &[0x33]);
+        mock.registers_mut()
+            .set(RegisterAddress8::new(0x20), &[0x47]);
+        mock.registers_mut()
+            .set(RegisterAddress8::new(0x28), &[0x1A, 0x00]);
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock);
+        I2CDevice::new(bus, DeviceAddress7::new(0x18))
+    }
+
+    #[test]
+    fn capture_reads_readable_registers_in_address_order() {
+        let snapshot = match Snapshot::capture(&MAP, &mut device()) {
+            Ok(snapshot) => snapshot,
+            Err(error) => panic!("{error:?}"),
+        };
+        assert_eq!(snapshot.to_string(), "0F 33\n20 47\n28 1A00\n");
+        assert_eq!(parse(&snapshot.to_string()), snapshot);
+    }
+
+    #[test]
+    fn diff() {
+        let before = parse("0F 33\n20 47\n28 1A00");
+        let after = parse("# comment\n0F 33\n20 57\n30 01");
+        let differences: Vec<_> = before
+            .diff(&after)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            differences,
+            ["20: 47 -> 57", "28: 1A00 -> -", "30: - -> 01"]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn restore_writes_writable_registers() {
+        let mut device = device();
+        let snapshot = parse("0F 00\n20 07\n28 0000\n30 01");
+        assert_eq!(snapshot.restore(&MAP, &mut device), Ok(2));
+        let snapshot = match Snapshot::capture(&MAP, &mut device) {
+            Ok(snapshot) => snapshot,
+            Err(error) => panic!("{error:?}"),
+        };
+        assert_eq!(snapshot.to_string(), "0F 33\n20 07\n28 1A00\n");
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        assert_eq!(
+            "20 47\n20".parse::<Snapshot>(),
+            Err(ParseError {
+                line: 2,
+                message: "missing data"
+            })
+        );
+    }
+}
@@ -1,8 +1,21 @@
 //! Support for SPI registers.
 
+mod device;
+
+use crate::access::{self, RegisterAccess};
+use crate::backing::RegisterBacking;
+use crate::error::Error;
+use crate::fifo::FifoCount;
+#[cfg(feature = "bitflags")]
+use crate::flags::FlagsRegister;
+use crate::indexed::{ArrayAddress, IndexedRegister};
+use crate::poll::Timeout;
 pub use crate::register_address::{RegisterAddress, RegisterAddress16, RegisterAddress8};
 use crate::sizes::{RegisterSizeInformation, R1, R2};
+pub use crate::spi::device::SPIDevice;
+use crate::status::StatusRegister;
 use crate::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+use embedded_hal::delay::DelayNs;
 
 /// A readable SPI register.
 pub trait SPIRegister<R, Size>:
@@ -14,8 +27,19 @@ where
     /// The register address
     const REGISTER_ADDRESS: R;
 
+    /// The documented power-on reset value of the register, if any.
+    const RESET_VALUE: Option<Self::Backing> = None;
+
     /// The backing type for the register, e.g. `u8` for an 8-bit register, or `u16` for a 10-bit register.
     type Backing;
+
+    /// Constructs the register from its [`RESET_VALUE`](Self::RESET_VALUE).
+    ///
+    /// Returns `None` if the register does not document a reset value.
+    #[must_use]
+    fn from_reset_value() -> Option<Self> {
+        Self::RESET_VALUE.map(Self::from_bits)
+    }
 }
 
 /// A writable SPI register.
@@ -36,6 +60,259 @@ where
 {
 }
 
+/// Typed register access for SPI devices.
+///
+/// This trait is implemented by [`SPIDevice`] and provides the register-level operations
+/// on top of the byte-level [`RegisterAccess`].
+#[allow(clippy::module_name_repetitions)]
+pub trait SPIRegisterAccess: RegisterAccess {
+    /// Reads a register.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn read_register<Reg, R, Size>(&mut self) -> Result<Reg, Self::Error>
+    where
+        Reg: SPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::read_register(self, Reg::REGISTER_ADDRESS)
+    }
+
+    /// Writes a register.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn write_register<Reg, R, Size>(&mut self, value: &Reg) -> Result<(), Self::Error>
+    where
+        Reg: WritableSPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::write_register(self, Reg::REGISTER_ADDRESS, value)
+    }
+
+    /// Reads a register, applies the provided function and writes back the result.
+    ///
+    /// # Errors
+    /// Returns an error if a bus transaction fails.
+    fn modify_register<Reg, R, Size, F>(&mut self, f: F) -> Result<Reg, Self::Error>
+    where
+        Reg: WritableSPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+        F: FnOnce(Reg) -> Reg,
+    {
+        access::modify_register(self, Reg::REGISTER_ADDRESS, f)
+    }
+
+    /// Writes the register's [`RESET_VALUE`](SPIRegister::RESET_VALUE).
+    ///
+    /// Returns the written value, or `None` if the register does not document a reset value,
+    /// in which case nothing is written.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn reset_register<Reg, R, Size>(&mut self) -> Result<Option<Reg>, Self::Error>
+    where
+        Reg: WritableSPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::reset_register(self, Reg::REGISTER_ADDRESS, Reg::from_reset_value())
+    }
+
+    /// Reads the register of an [`IndexedRegister`] family at the specified index.
+    ///
+    /// # Errors
+    /// Returns [`Error::AddressOutOfRange`] if the index is out of bounds and [`Error::Bus`]
+    /// if the bus transaction fails.
+    fn read_indexed<Reg, R, Size>(&mut self, index: usize) -> Result<Reg, Error<Self::Error>>
+    where
+        Reg: IndexedRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: ArrayAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::read_indexed(self, index)
+    }
+
+    /// Writes the register of an [`IndexedRegister`] family at the specified index.
+    ///
+    /// # Errors
+    /// Returns [`Error::AddressOutOfRange`] if the index is out of bounds and [`Error::Bus`]
+    /// if the bus transaction fails.
+    fn write_indexed<Reg, R, Size>(
+        &mut self,
+        index: usize,
+        value: &Reg,
+    ) -> Result<(), Error<Self::Error>>
+    where
+        Reg: IndexedRegister<R, Size> + WritableHardwareRegister<Size>,
+        Reg::Backing: RegisterBacking,
+        R: ArrayAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::write_indexed(self, index, value)
+    }
+
+    /// Reads frames from a FIFO data register until the slice is filled.
+    ///
+    /// The register is read in bursts with [`read_raw_burst`](RegisterAccess::read_raw_burst),
+    /// relying on the device not to advance the register address while reading the FIFO;
+    /// see the [`fifo`](crate::fifo) module.
+    ///
+    /// # Errors
+    /// Returns an error if a bus transaction fails.
+    fn read_fifo<Reg, R, Size>(&mut self, frames: &mut [Reg]) -> Result<(), Self::Error>
+    where
+        Reg: SPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+    {
+        access::read_fifo(self, Reg::REGISTER_ADDRESS, frames)
+    }
+
+    /// Reads the FIFO level from the `Count` register, then reads as many frames as are
+    /// available and fit into the slice from a FIFO data register.
+    ///
+    /// Returns the number of frames read.
+    ///
+    /// # Errors
+    /// Returns an error if a bus transaction fails.
+    fn read_fifo_counted<Count, CR, CSize, Reg, R, Size>(
+        &mut self,
+        frames: &mut [Reg],
+    ) -> Result<usize, Self::Error>
+    where
+        Count: SPIRegister<CR, CSize> + FifoCount,
+        Count::Backing: RegisterBacking,
+        CR: RegisterAddress,
+        CSize: RegisterSizeInformation,
+        Reg: SPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+    {
+        access::read_fifo_counted::<_, Count, _, _, _, _, _>(
+            self,
+            Count::REGISTER_ADDRESS,
+            Reg::REGISTER_ADDRESS,
+            frames,
+        )
+    }
+
+    /// Reads a [`StatusRegister`] and clears the flags that were read.
+    ///
+    /// For [`ClearOnRead`](crate::status::ClearSemantics::ClearOnRead) registers the read
+    /// itself clears the flags; otherwise the read flags are cleared by
+    /// [`clear_status`](Self::clear_status), so that flags raised in between are not lost.
+    ///
+    /// # Errors
+    /// Returns an error if a bus transaction fails.
+    fn take_status<Reg, R, Size>(&mut self) -> Result<Reg, Self::Error>
+    where
+        Reg: SPIRegister<R, Size> + StatusRegister<Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+    {
+        access::take_status(self, Reg::REGISTER_ADDRESS)
+    }
+
+    /// Clears the specified flags of a [`StatusRegister`] according to its
+    /// [`ClearSemantics`](crate::status::ClearSemantics).
+    ///
+    /// The register is written even if it is not writable otherwise. Flags of
+    /// [`ClearOnRead`](crate::status::ClearSemantics::ClearOnRead) registers cannot be
+    /// cleared individually; the register is read, clearing all of them.
+    ///
+    /// # Errors
+    /// Returns an error if the bus transaction fails.
+    fn clear_status<Reg, R, Size>(&mut self, flags: &Reg) -> Result<(), Self::Error>
+    where
+        Reg: SPIRegister<R, Size> + StatusRegister<Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress,
+        Size: RegisterSizeInformation,
+    {
+        access::clear_status(self, Reg::REGISTER_ADDRESS, flags)
+    }
+
+    /// Reads a register of [`FlagsRegister`] flags.
+    ///
+    /// # Errors
+    /// Returns [`Error::Bus`] if the bus transaction fails and [`Error::Decode`] if bits that
+    /// do not correspond to a flag are set and the register's policy is
+    /// [`UnknownBits::Reject`](crate::flags::UnknownBits::Reject).
+    #[cfg(feature = "bitflags")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bitflags")))]
+    fn read_flags<Reg>(&mut self) -> Result<Reg, Error<Self::Error>>
+    where
+        Reg: SPIRegister8 + FlagsRegister,
+    {
+        access::read_flags(self, Reg::REGISTER_ADDRESS)
+    }
+
+    /// Reads a register until it satisfies the predicate, blocking for the poll interval
+    /// between the reads, and returns the first satisfying value.
+    ///
+    /// See the [`poll`](crate::poll) module for how the elapsed time is accounted for.
+    ///
+    /// # Errors
+    /// Returns [`Error::Bus`] if a bus transaction fails and [`Error::Timeout`] if the
+    /// register does not satisfy the predicate within the timeout.
+    fn wait_until<Reg, R, Size, P, D>(
+        &mut self,
+        predicate: P,
+        timeout: Timeout,
+        delay: &mut D,
+    ) -> Result<Reg, Error<Self::Error>>
+    where
+        Reg: SPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+        P: FnMut(&Reg) -> bool,
+        D: DelayNs,
+    {
+        access::wait_until(self, Reg::REGISTER_ADDRESS, predicate, timeout, delay)
+    }
+
+    /// Reads a register until it satisfies the predicate, awaiting the poll interval
+    /// between the reads, and returns the first satisfying value.
+    ///
+    /// The register reads themselves are blocking; only the delays yield to the executor.
+    ///
+    /// # Errors
+    /// Returns [`Error::Bus`] if a bus transaction fails and [`Error::Timeout`] if the
+    /// register does not satisfy the predicate within the timeout.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    #[allow(async_fn_in_trait)]
+    async fn wait_until_async<Reg, R, Size, P, D>(
+        &mut self,
+        predicate: P,
+        timeout: Timeout,
+        delay: &mut D,
+    ) -> Result<Reg, Error<Self::Error>>
+    where
+        Reg: SPIRegister<R, Size>,
+        Reg::Backing: RegisterBacking,
+        R: RegisterAddress + Copy,
+        Size: RegisterSizeInformation,
+        P: FnMut(&Reg) -> bool,
+        D: embedded_hal_async::delay::DelayNs,
+    {
+        access::wait_until_async(self, Reg::REGISTER_ADDRESS, predicate, timeout, delay).await
+    }
+}
+
 /// Marker trait for readable 8-bit SPI registers.
 pub trait SPIRegister8:
     SPIRegister<RegisterAddress8, R1, Backing = u8>
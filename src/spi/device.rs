@@ -0,0 +1,233 @@
+use crate::access::{encode_address, encode_value, RegisterAccess};
+use crate::addressing::AddressingMode;
+use crate::command::CommandAccess;
+use crate::register_address::RegisterAddress;
+use crate::spi::SPIRegisterAccess;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// A device on an SPI bus, providing access to its registers.
+///
+/// Each register access is a single SPI transaction: the register address is transmitted
+/// first, followed by the data being written or read. Reads are marked by setting the
+/// read flag in the first address byte; by default this is the most significant bit.
+/// Multi-byte transfers are split or flagged according to the device's [`AddressingMode`],
+/// which defaults to address auto-increment.
+#[derive(Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub struct SPIDevice<B> {
+    bus: B,
+    read_flag: u8,
+    addressing: AddressingMode,
+}
+
+impl<B> SPIDevice<B> {
+    /// The default read flag, set in the first address byte of a read transfer.
+    pub const DEFAULT_READ_FLAG: u8 = 0b1000_0000;
+
+    /// Constructs a new device using the [default read flag](Self::DEFAULT_READ_FLAG).
+    pub const fn new(bus: B) -> Self {
+        Self {
+            bus,
+            read_flag: Self::DEFAULT_READ_FLAG,
+            addressing: AddressingMode::AutoIncrement,
+        }
+    }
+
+    /// Sets the bits to set in the first address byte of a read transfer.
+    #[must_use]
+    pub const fn with_read_flag(mut self, read_flag: u8) -> Self {
+        self.read_flag = read_flag;
+        self
+    }
+
+    /// Sets how the device advances the register address during multi-byte transfers.
+    #[must_use]
+    pub const fn with_addressing(mut self, addressing: AddressingMode) -> Self {
+        self.addressing = addressing;
+        self
+    }
+
+    /// Returns the bits set in the first address byte of a read transfer.
+    pub const fn read_flag(&self) -> u8 {
+        self.read_flag
+    }
+
+    /// Returns how the device advances the register address during multi-byte transfers.
+    pub const fn addressing(&self) -> AddressingMode {
+        self.addressing
+    }
+
+    /// Consumes self and returns the underlying bus.
+    pub fn release(self) -> B {
+        self.bus
+    }
+}
+
+impl<B> RegisterAccess for SPIDevice<B>
+where
+    B: SpiDevice,
+{
+    type Error = B::Error;
+
+    fn read_raw<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        for segment in self.addressing.segments(address.value(), buffer.len()) {
+            let (mut register, len) = encode_value::<A>(segment.address);
+            register[0] |= self.read_flag | segment.flag;
+            self.bus.transaction(&mut [
+                Operation::Write(&register[..len]),
+                Operation::Read(&mut buffer[segment.range]),
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn write_raw<A>(&mut self, address: A, data: &[u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        for segment in self.addressing.segments(address.value(), data.len()) {
+            let (mut register, len) = encode_value::<A>(segment.address);
+            register[0] &= !self.read_flag;
+            register[0] |= segment.flag;
+            self.bus.transaction(&mut [
+                Operation::Write(&register[..len]),
+                Operation::Write(&data[segment.range]),
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn read_raw_burst<A>(&mut self, address: A, buffer: &mut [u8]) -> Result<(), Self::Error>
+    where
+        A: RegisterAddress,
+    {
+        let (mut register, len) = encode_address(&address);
+        register[0] |= self.read_flag;
+        self.bus
+            .transaction(&mut [Operation::Write(&register[..len]), Operation::Read(buffer)])
+    }
+}
+
+impl<B> SPIRegisterAccess for SPIDevice<B> where B: SpiDevice {}
+
+/// Command transfers are not marked with the read flag.
+impl<B> CommandAccess for SPIDevice<B>
+where
+    B: SpiDevice,
+{
+    type Error = B::Error;
+
+    fn write_command(&mut self, command: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(command)
+    }
+
+    fn read_response(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus.read(buffer)
+    }
+
+    fn transfer_command(&mut self, command: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus
+            .transaction(&mut [Operation::Write(command), Operation::Read(buffer)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sizes::R1;
+    use crate::spi::{RegisterAddress8, SPIRegister};
+    use crate::test_support::test_register;
+    use embedded_hal::spi::{ErrorKind, ErrorType};
+
+    /// Records the last transaction's written bytes and answers reads with `0x5A`.
+    #[derive(Default)]
+    struct Bus {
+        written: [u8; 4],
+        count: usize,
+    }
+
+    impl ErrorType for Bus {
+        type Error = ErrorKind;
+    }
+
+    impl SpiDevice for Bus {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), ErrorKind> {
+            self.count = 0;
+            for operation in operations {
+                match operation {
+                    Operation::Write(bytes) => {
+                        for byte in &**bytes {
+                            self.written[self.count] = *byte;
+                            self.count += 1;
+                        }
+                    }
+                    Operation::Read(buffer) => buffer.fill(0x5A),
+                    _ => return Err(ErrorKind::Other),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    test_register!(Control(u8): R1, writable);
+
+    impl SPIRegister<RegisterAddress8, R1> for Control {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        const RESET_VALUE: Option<u8> = Some(0x07);
+        type Backing = u8;
+    }
+
+    #[test]
+    fn read_sets_read_flag() {
+        let mut device = SPIDevice::new(Bus::default());
+        assert_eq!(device.read_register(), Ok(Control(0x5A)));
+        let bus = device.release();
+        assert_eq!(&bus.written[..bus.count], &[0xA0]);
+    }
+
+    #[test]
+    fn write() {
+        let mut device = SPIDevice::new(Bus::default());
+        assert_eq!(device.write_register(&Control(0x47)), Ok(()));
+        let bus = device.release();
+        assert_eq!(&bus.written[..bus.count], &[0x20, 0x47]);
+    }
+
+    #[test]
+    fn reset() {
+        let mut device = SPIDevice::new(Bus::default());
+        let value = device.reset_register::<Control, _, _>();
+        assert_eq!(value, Ok(Some(Control(0x07))));
+        let bus = device.release();
+        assert_eq!(&bus.written[..bus.count], &[0x20, 0x07]);
+    }
+
+    #[test]
+    fn increment_flag() {
+        let mut device = SPIDevice::new(Bus::default())
+            .with_addressing(AddressingMode::IncrementFlag { flag: 0b0100_0000 });
+        let mut buffer = [0_u8; 2];
+        assert_eq!(
+            device.read_raw(RegisterAddress8::new(0x28), &mut buffer),
+            Ok(())
+        );
+        assert_eq!(buffer, [0x5A, 0x5A]);
+        let bus = device.release();
+        assert_eq!(&bus.written[..bus.count], &[0xE8]);
+    }
+
+    #[test]
+    fn no_increment() {
+        let mut device =
+            SPIDevice::new(Bus::default()).with_addressing(AddressingMode::NoIncrement);
+        assert_eq!(
+            device.write_raw(RegisterAddress8::new(0x20), &[0x01, 0x02]),
+            Ok(())
+        );
+        let bus = device.release();
+        assert_eq!(&bus.written[..bus.count], &[0x21, 0x02]);
+    }
+}
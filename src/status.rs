@@ -0,0 +1,137 @@
+//! Interrupt and status registers whose flags are cleared by the device protocol.
+//!
+//! Status flags are cleared in one of several ways: reading the register clears all flags,
+//! or individual flags are cleared by writing a one (or a zero) to their bits. A
+//! [`StatusRegister`] declares its [`ClearSemantics`], so that the `clear_status` and
+//! `take_status` methods of [`I2CRegisterAccess`](crate::i2c::I2CRegisterAccess) and
+//! [`SPIRegisterAccess`](crate::spi::SPIRegisterAccess) can clear flags correctly.
+//!
+//! The register type itself is the typed set of flags, e.g. a `bitflags` type or a generated
+//! register with one boolean field per flag.
+
+use crate::access::RegisterAccess;
+use crate::register_address::RegisterAddress;
+use crate::sizes::RegisterSizeInformation;
+use crate::HardwareRegister;
+
+/// How the flags of a [`StatusRegister`] are cleared.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClearSemantics {
+    /// Reading the register clears all flags; individual flags cannot be cleared.
+    ClearOnRead,
+    /// Writing a one to a flag clears it; zeros are ignored.
+    WriteOneToClear,
+    /// Writing a zero to a flag clears it; ones are ignored.
+    WriteZeroToClear,
+}
+
+/// A register of status or interrupt flags.
+pub trait StatusRegister<Size>: HardwareRegister<Size>
+where
+    Size: RegisterSizeInformation,
+{
+    /// How the flags are cleared.
+    const CLEAR: ClearSemantics;
+}
+
+/// Clears the flags given by their register bytes.
+///
+/// Writes to the register regardless of its access policy, since writing is how
+/// [`ClearSemantics::WriteOneToClear`] and [`ClearSemantics::WriteZeroToClear`] flags are
+/// cleared.
+pub(crate) fn clear<A, T>(
+    access: &mut T,
+    address: A,
+    semantics: ClearSemantics,
+    flags: &mut [u8],
+) -> Result<(), T::Error>
+where
+    A: RegisterAddress,
+    T: RegisterAccess + ?Sized,
+{
+    match semantics {
+        ClearSemantics::ClearOnRead => access.read_raw(address, flags),
+        ClearSemantics::WriteOneToClear => access.write_raw(address, flags),
+        ClearSemantics::WriteZeroToClear => {
+            for byte in flags.iter_mut() {
+                *byte = !*byte;
+            }
+            access.write_raw(address, flags)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "i2c", feature = "spi"))]
+mod tests {
+    use super::*;
+    use crate::i2c::{DeviceAddress7, I2CDevice, I2CRegister, I2CRegisterAccess, RegisterAddress8};
+    use crate::mock::{MockDevice, MockI2c, MockSpi};
+    use crate::sizes::R1;
+    use crate::spi::{SPIDevice, SPIRegister, SPIRegisterAccess};
+    use crate::test_support::test_register;
+
+    macro_rules! status_register {
+        ($name:ident, $address:literal, $clear:expr) => {
+            test_register!($name(u8): R1);
+
+            impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for $name {
+                const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+                const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new($address);
+                type Backing = u8;
+            }
+
+            impl SPIRegister<RegisterAddress8, R1> for $name {
+                const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new($address);
+                type Backing = u8;
+            }
+
+            impl StatusRegister<R1> for $name {
+                const CLEAR: ClearSemantics = $clear;
+            }
+        };
+    }
+
+    status_register!(InterruptSource, 0x31, ClearSemantics::ClearOnRead);
+    status_register!(FaultStatus, 0x40, ClearSemantics::WriteOneToClear);
+    status_register!(AlarmStatus, 0x41, ClearSemantics::WriteZeroToClear);
+
+    fn mock() -> MockDevice {
+        let mut device = MockDevice::new::<RegisterAddress8>()
+            .on_read(RegisterAddress8::new(0x31), |r| r.set_byte(0x31, 0))
+            .on_write(RegisterAddress8::new(0x40), |r, written, previous| {
+                r.set_byte(0x40, previous & !written);
+            })
+            .on_write(RegisterAddress8::new(0x41), |r, written, previous| {
+                r.set_byte(0x41, previous & written);
+            });
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x31), &[0b0110]);
+        device
+            .registers_mut()
+            .set(RegisterAddress8::new(0x40), &[0b1011, 0b1100]);
+        device
+    }
+
+    #[test]
+    fn i2c_take_status() {
+        let bus = MockI2c::new().with_device(DeviceAddress7::new(0x18), mock());
+        let mut device = I2CDevice::new(bus, DeviceAddress7::new(0x18));
+        assert_eq!(device.take_status(), Ok(InterruptSource(0b0110)));
+        assert_eq!(device.take_status(), Ok(InterruptSource(0)));
+        assert_eq!(device.take_status(), Ok(FaultStatus(0b1011)));
+        assert_eq!(device.read_register(), Ok(FaultStatus(0)));
+    }
+
+    #[test]
+    fn spi_clear_status() {
+        let mut device = SPIDevice::new(MockSpi::new(mock()));
+        assert_eq!(device.clear_status(&FaultStatus(0b0001)), Ok(()));
+        assert_eq!(device.read_register(), Ok(FaultStatus(0b1010)));
+        assert_eq!(device.clear_status(&AlarmStatus(0b0100)), Ok(()));
+        assert_eq!(device.read_register(), Ok(AlarmStatus(0b1000)));
+        assert_eq!(device.clear_status(&InterruptSource(0b0010)), Ok(()));
+        assert_eq!(device.read_register(), Ok(InterruptSource(0)));
+    }
+}
@@ -0,0 +1,68 @@
+//! Helpers shared by the unit tests.
+
+use embedded_hal::delay::DelayNs;
+
+/// Declares a test register wrapping its backing integer, e.g.
+/// `test_register!(Threshold(u16): R2, writable)`.
+///
+/// The register implements [`HardwareRegister`](crate::HardwareRegister),
+/// [`ToBits`](crate::ToBits) and [`FromBits`](crate::FromBits). With `writable`, it also
+/// implements [`WritableHardwareRegister`](crate::WritableHardwareRegister); a trailing
+/// [`ByteOrder`](crate::backing::ByteOrder) overrides the big-endian default.
+macro_rules! test_register {
+    ($(#[$meta:meta])* $name:ident($backing:ty): $size:ty) => {
+        test_register!(@register $(#[$meta])* $name($backing): $size, $crate::backing::ByteOrder::BigEndian);
+    };
+    ($(#[$meta:meta])* $name:ident($backing:ty): $size:ty, writable) => {
+        test_register!($(#[$meta])* $name($backing): $size, writable, $crate::backing::ByteOrder::BigEndian);
+    };
+    ($(#[$meta:meta])* $name:ident($backing:ty): $size:ty, writable, $order:expr) => {
+        test_register!(@register $(#[$meta])* $name($backing): $size, $order);
+
+        impl $crate::WritableHardwareRegister<$size> for $name {}
+    };
+    (@register $(#[$meta:meta])* $name:ident($backing:ty): $size:ty, $order:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+        struct $name($backing);
+
+        impl $crate::HardwareRegister<$size> for $name {
+            const BYTE_ORDER: $crate::backing::ByteOrder = $order;
+        }
+
+        impl $crate::ToBits for $name {
+            type Target = $backing;
+
+            fn to_bits(&self) -> Self::Target {
+                self.0
+            }
+        }
+
+        impl $crate::FromBits<$backing> for $name {
+            fn from_bits_ref(value: &$backing) -> Self {
+                Self(*value)
+            }
+        }
+    };
+}
+
+pub(crate) use test_register;
+
+/// A delay that only records the time waited.
+#[derive(Default)]
+pub(crate) struct Clock {
+    pub elapsed_ns: u64,
+}
+
+impl DelayNs for Clock {
+    fn delay_ns(&mut self, ns: u32) {
+        self.elapsed_ns += u64::from(ns);
+    }
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for Clock {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.elapsed_ns += u64::from(ns);
+    }
+}
@@ -0,0 +1,256 @@
+// This file was generated by hardware-registers. Do not edit.
+
+/// 3-axis MEMS accelerometer
+pub mod lis3dh {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R1, R2};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::addressing::AddressingMode;
+    use hardware_registers::i2c::{DeviceAddress7, I2CRegister, IdentityRegister};
+    use hardware_registers::spi::SPIRegister;
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// The factory-default I²C device address.
+    pub const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x18);
+
+    /// All I²C device addresses the device can be configured to.
+    pub const DEVICE_ADDRESSES: &[DeviceAddress7] = &[DeviceAddress7::new(0x18), DeviceAddress7::new(0x19)];
+
+    /// How the register address advances, for use with `I2CDevice::with_addressing`.
+    pub const I2C_ADDRESSING: AddressingMode = AddressingMode::IncrementFlag { flag: 0x80 };
+
+    /// The flag marking SPI reads, for use with `SPIDevice::with_read_flag`.
+    pub const SPI_READ_FLAG: u8 = 0x80;
+
+    /// How the register address advances, for use with `SPIDevice::with_addressing`.
+    pub const SPI_ADDRESSING: AddressingMode = AddressingMode::IncrementFlag { flag: 0x40 };
+
+    /// `WHO_AM_I`: Device identification register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct WhoAmI(pub u8);
+
+    impl Default for WhoAmI {
+        fn default() -> Self {
+            Self(0x33)
+        }
+    }
+
+    impl HardwareRegister<R1> for WhoAmI {}
+
+    impl ToBits for WhoAmI {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for WhoAmI {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for WhoAmI {
+        const NAME: &'static str = "WHO_AM_I";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x0F);
+        const RESET_VALUE: Option<u8> = Some(0x33);
+        type Backing = u8;
+    }
+
+    impl IdentityRegister<DeviceAddress7, RegisterAddress8, R1> for WhoAmI {
+        const EXPECTED: &'static [u8] = &[0x33];
+        const ALTERNATE_DEVICE_ADDRESSES: &'static [DeviceAddress7] = DEVICE_ADDRESSES;
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for WhoAmI {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x0F);
+        const RESET_VALUE: Option<u8> = Some(0x33);
+        type Backing = u8;
+    }
+
+    /// `CTRL_REG1`: Data rate selection and axis enable
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct CtrlReg1(pub u8);
+
+    impl CtrlReg1 {
+        /// `ODR`: Data rate selection (bits 7..=4)
+        #[must_use]
+        pub const fn odr(self) -> u8 {
+            (self.0 >> 4) & 0xF
+        }
+
+        /// Sets the `ODR` field (bits 7..=4).
+        #[must_use]
+        pub const fn with_odr(self, value: u8) -> Self {
+            Self((self.0 & !(0xF << 4)) | ((value & 0xF) << 4))
+        }
+
+        /// `LPen`: Low-power mode enable (bit 3)
+        #[must_use]
+        pub const fn lpen(self) -> bool {
+            (self.0 >> 3) & 0x1 != 0
+        }
+
+        /// Sets the `LPen` field (bit 3).
+        #[must_use]
+        pub const fn with_lpen(self, value: bool) -> Self {
+            Self((self.0 & !(0x1 << 3)) | if value { 0x8 } else { 0 })
+        }
+
+        /// The `Zen` field (bit 2).
+        #[must_use]
+        pub const fn zen(self) -> bool {
+            (self.0 >> 2) & 0x1 != 0
+        }
+
+        /// Sets the `Zen` field (bit 2).
+        #[must_use]
+        pub const fn with_zen(self, value: bool) -> Self {
+            Self((self.0 & !(0x1 << 2)) | if value { 0x4 } else { 0 })
+        }
+
+        /// The `Yen` field (bit 1).
+        #[must_use]
+        pub const fn yen(self) -> bool {
+            (self.0 >> 1) & 0x1 != 0
+        }
+
+        /// Sets the `Yen` field (bit 1).
+        #[must_use]
+        pub const fn with_yen(self, value: bool) -> Self {
+            Self((self.0 & !(0x1 << 1)) | if value { 0x2 } else { 0 })
+        }
+
+        /// The `Xen` field (bit 0).
+        #[must_use]
+        pub const fn xen(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// Sets the `Xen` field (bit 0).
+        #[must_use]
+        pub const fn with_xen(self, value: bool) -> Self {
+            Self((self.0 & !0x1) | if value { 0x1 } else { 0 })
+        }
+    }
+
+    impl Default for CtrlReg1 {
+        fn default() -> Self {
+            Self(0x07)
+        }
+    }
+
+    impl HardwareRegister<R1> for CtrlReg1 {}
+    impl WritableHardwareRegister<R1> for CtrlReg1 {}
+
+    impl ToBits for CtrlReg1 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for CtrlReg1 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for CtrlReg1 {
+        const NAME: &'static str = "CTRL_REG1";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("ODR", FieldValue::Unsigned(u128::from((self.0 >> 4) & 0xF)));
+            visitor.field("LPen", FieldValue::Bit((self.0 >> 3) & 0x1 != 0));
+            visitor.field("Zen", FieldValue::Bit((self.0 >> 2) & 0x1 != 0));
+            visitor.field("Yen", FieldValue::Bit((self.0 >> 1) & 0x1 != 0));
+            visitor.field("Xen", FieldValue::Bit(self.0 & 0x1 != 0));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for CtrlReg1 {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        const RESET_VALUE: Option<u8> = Some(0x07);
+        type Backing = u8;
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for CtrlReg1 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        const RESET_VALUE: Option<u8> = Some(0x07);
+        type Backing = u8;
+    }
+
+    /// `OUT_X`: X-axis acceleration data
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct OutX(pub u16);
+
+    impl HardwareRegister<R2> for OutX {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+
+    impl ToBits for OutX {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for OutX {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for OutX {
+        const NAME: &'static str = "OUT_X";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R2> for OutX {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x28);
+        const RESET_VALUE: Option<u16> = None;
+        type Backing = u16;
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for OutX {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x28);
+        const RESET_VALUE: Option<u16> = None;
+        type Backing = u16;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("WHO_AM_I", RegisterAddress8::new(0x0F), 1, Access::ReadOnly)
+            .with_reset(&[0x33]),
+        RegisterInfo::new("CTRL_REG1", RegisterAddress8::new(0x20), 1, Access::ReadWrite)
+            .with_reset(&[0x07])
+            .with_fields(&[
+                FieldInfo::new("ODR", 4, 4, Access::ReadWrite),
+                FieldInfo::new("LPen", 3, 1, Access::ReadWrite),
+                FieldInfo::new("Zen", 2, 1, Access::ReadWrite),
+                FieldInfo::new("Yen", 1, 1, Access::ReadWrite),
+                FieldInfo::new("Xen", 0, 1, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("OUT_X", RegisterAddress8::new(0x28), 2, Access::ReadOnly)
+            .with_byte_order(ByteOrder::LittleEndian),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("LIS3DH", REGISTERS);
+}
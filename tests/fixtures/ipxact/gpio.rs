@@ -0,0 +1,356 @@
+// This file was generated by hardware-registers. Do not edit.
+
+/// Global configuration
+pub mod gpio_control {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R2};
+    use hardware_registers::spi::SPIRegister;
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// `MODE`: Drive mode of all pins
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Mode(pub u16);
+
+    impl Mode {
+        /// The `DRIVE` field (bits 2..=0).
+        #[must_use]
+        pub const fn drive(self) -> u8 {
+            (self.0 & 0x7) as u8
+        }
+
+        /// Sets the `DRIVE` field (bits 2..=0).
+        #[must_use]
+        pub const fn with_drive(self, value: u8) -> Self {
+            Self((self.0 & !0x7) | ((value as u16) & 0x7))
+        }
+
+        /// The `PULL` field (bits 9..=8).
+        #[must_use]
+        pub const fn pull(self) -> u8 {
+            ((self.0 >> 8) & 0x3) as u8
+        }
+
+        /// Sets the `PULL` field (bits 9..=8).
+        #[must_use]
+        pub const fn with_pull(self, value: u8) -> Self {
+            Self((self.0 & !(0x3 << 8)) | (((value as u16) & 0x3) << 8))
+        }
+    }
+
+    impl Default for Mode {
+        fn default() -> Self {
+            Self(0x102)
+        }
+    }
+
+    impl HardwareRegister<R2> for Mode {}
+    impl WritableHardwareRegister<R2> for Mode {}
+
+    impl ToBits for Mode {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Mode {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Mode {
+        const NAME: &'static str = "MODE";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("DRIVE", FieldValue::Unsigned(u128::from((self.0 & 0x7) as u8)));
+            visitor.field("PULL", FieldValue::Unsigned(u128::from(((self.0 >> 8) & 0x3) as u8)));
+        }
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for Mode {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x00);
+        const RESET_VALUE: Option<u16> = Some(0x102);
+        type Backing = u16;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("MODE", RegisterAddress8::new(0x00), 2, Access::ReadWrite)
+            .with_reset(&[0x01, 0x02])
+            .with_fields(&[
+                FieldInfo::new("DRIVE", 0, 3, Access::ReadWrite),
+                FieldInfo::new("PULL", 8, 2, Access::ReadWrite),
+            ]),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("gpio_control", REGISTERS);
+}
+
+/// The `gpio_pins` device.
+pub mod gpio_pins {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldVisitor};
+    use hardware_registers::map::{Access, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R1, R2};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::spi::SPIRegister;
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// `port0_DIR`: Pin direction, 1 for input
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Port0Dir(pub u16);
+
+    impl Default for Port0Dir {
+        fn default() -> Self {
+            Self(0xFFFF)
+        }
+    }
+
+    impl HardwareRegister<R2> for Port0Dir {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Port0Dir {}
+
+    impl ToBits for Port0Dir {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Port0Dir {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Port0Dir {
+        const NAME: &'static str = "port0_DIR";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for Port0Dir {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x10);
+        const RESET_VALUE: Option<u16> = Some(0xFFFF);
+        type Backing = u16;
+    }
+
+    /// `port0_OUT`: Output levels
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Port0Out(pub u16);
+
+    impl Default for Port0Out {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R2> for Port0Out {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Port0Out {}
+
+    impl ToBits for Port0Out {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Port0Out {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Port0Out {
+        const NAME: &'static str = "port0_OUT";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for Port0Out {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x12);
+        const RESET_VALUE: Option<u16> = Some(0x00);
+        type Backing = u16;
+    }
+
+    /// `port1_DIR`: Pin direction, 1 for input
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Port1Dir(pub u16);
+
+    impl Default for Port1Dir {
+        fn default() -> Self {
+            Self(0xFFFF)
+        }
+    }
+
+    impl HardwareRegister<R2> for Port1Dir {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Port1Dir {}
+
+    impl ToBits for Port1Dir {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Port1Dir {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Port1Dir {
+        const NAME: &'static str = "port1_DIR";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for Port1Dir {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x14);
+        const RESET_VALUE: Option<u16> = Some(0xFFFF);
+        type Backing = u16;
+    }
+
+    /// `port1_OUT`: Output levels
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Port1Out(pub u16);
+
+    impl Default for Port1Out {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R2> for Port1Out {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Port1Out {}
+
+    impl ToBits for Port1Out {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Port1Out {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Port1Out {
+        const NAME: &'static str = "port1_OUT";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R2> for Port1Out {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x16);
+        const RESET_VALUE: Option<u16> = Some(0x00);
+        type Backing = u16;
+    }
+
+    /// `IN0`: Input levels
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct In0(pub u8);
+
+    impl HardwareRegister<R1> for In0 {}
+
+    impl ToBits for In0 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for In0 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for In0 {
+        const NAME: &'static str = "IN0";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for In0 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x20);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// `IN1`: Input levels
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct In1(pub u8);
+
+    impl HardwareRegister<R1> for In1 {}
+
+    impl ToBits for In1 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for In1 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for In1 {
+        const NAME: &'static str = "IN1";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for In1 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x21);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("port0_DIR", RegisterAddress8::new(0x10), 2, Access::ReadWrite)
+            .with_reset(&[0xFF, 0xFF])
+            .with_byte_order(ByteOrder::LittleEndian),
+        RegisterInfo::new("port0_OUT", RegisterAddress8::new(0x12), 2, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian),
+        RegisterInfo::new("port1_DIR", RegisterAddress8::new(0x14), 2, Access::ReadWrite)
+            .with_reset(&[0xFF, 0xFF])
+            .with_byte_order(ByteOrder::LittleEndian),
+        RegisterInfo::new("port1_OUT", RegisterAddress8::new(0x16), 2, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian),
+        RegisterInfo::new("IN0", RegisterAddress8::new(0x20), 1, Access::ReadOnly),
+        RegisterInfo::new("IN1", RegisterAddress8::new(0x21), 1, Access::ReadOnly),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("gpio_pins", REGISTERS);
+}
@@ -0,0 +1,271 @@
+// This file was generated by hardware-registers. Do not edit.
+
+/// Minimal UART with a byte-wide data port
+pub mod uart {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R1, R2, R4};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::i2c::{DeviceAddress7, I2CRegister};
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// The factory-default I²C device address.
+    pub const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x2A);
+
+    /// `DATA`: Transmit and receive data
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Data(pub u8);
+
+    impl HardwareRegister<R1> for Data {}
+    impl WritableHardwareRegister<R1> for Data {}
+
+    impl ToBits for Data {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for Data {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Data {
+        const NAME: &'static str = "DATA";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for Data {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x00);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// `STATUS`: Status flags
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Status(pub u8);
+
+    impl Status {
+        /// `RXNE`: Receive data available (bit 0)
+        #[must_use]
+        pub const fn rxne(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// `TXE`: Transmit buffer empty (bit 1)
+        #[must_use]
+        pub const fn txe(self) -> bool {
+            (self.0 >> 1) & 0x1 != 0
+        }
+    }
+
+    impl Default for Status {
+        fn default() -> Self {
+            Self(0x02)
+        }
+    }
+
+    impl HardwareRegister<R1> for Status {}
+
+    impl ToBits for Status {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for Status {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Status {
+        const NAME: &'static str = "STATUS";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("RXNE", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("TXE", FieldValue::Bit((self.0 >> 1) & 0x1 != 0));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R1> for Status {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x01);
+        const RESET_VALUE: Option<u8> = Some(0x02);
+        type Backing = u8;
+    }
+
+    /// `BAUD`: Baud rate divider
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Baud(pub u16);
+
+    impl Baud {
+        /// The `DIV` field (bits 15..=0).
+        #[must_use]
+        pub const fn div(self) -> u16 {
+            self.0
+        }
+
+        /// Sets the `DIV` field (bits 15..=0).
+        #[must_use]
+        pub const fn with_div(self, value: u16) -> Self {
+            Self(value)
+        }
+    }
+
+    impl Default for Baud {
+        fn default() -> Self {
+            Self(0x100)
+        }
+    }
+
+    impl HardwareRegister<R2> for Baud {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Baud {}
+
+    impl ToBits for Baud {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Baud {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Baud {
+        const NAME: &'static str = "BAUD";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("DIV", FieldValue::Unsigned(u128::from(self.0)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R2> for Baud {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x02);
+        const RESET_VALUE: Option<u16> = Some(0x100);
+        type Backing = u16;
+    }
+
+    /// `CTRL`: Control register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Ctrl(pub u32);
+
+    impl Ctrl {
+        /// `EN`: Enables the UART (bit 0)
+        #[must_use]
+        pub const fn en(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// Sets the `EN` field (bit 0).
+        #[must_use]
+        pub const fn with_en(self, value: bool) -> Self {
+            Self((self.0 & !0x1) | if value { 0x1 } else { 0 })
+        }
+
+        /// `PARITY`: Parity mode (bits 2..=1)
+        #[must_use]
+        pub const fn parity(self) -> u8 {
+            ((self.0 >> 1) & 0x3) as u8
+        }
+
+        /// Sets the `PARITY` field (bits 2..=1).
+        #[must_use]
+        pub const fn with_parity(self, value: u8) -> Self {
+            Self((self.0 & !(0x3 << 1)) | (((value as u32) & 0x3) << 1))
+        }
+
+        /// `BUSY`: Transmission in progress (bit 31)
+        #[must_use]
+        pub const fn busy(self) -> bool {
+            (self.0 >> 31) & 0x1 != 0
+        }
+    }
+
+    impl Default for Ctrl {
+        fn default() -> Self {
+            Self(0x01)
+        }
+    }
+
+    impl HardwareRegister<R4> for Ctrl {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R4> for Ctrl {}
+
+    impl ToBits for Ctrl {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Ctrl {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Ctrl {
+        const NAME: &'static str = "CTRL";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("EN", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("PARITY", FieldValue::Unsigned(u128::from(((self.0 >> 1) & 0x3) as u8)));
+            visitor.field("BUSY", FieldValue::Bit((self.0 >> 31) & 0x1 != 0));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress8, R4> for Ctrl {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x04);
+        const RESET_VALUE: Option<u32> = Some(0x01);
+        type Backing = u32;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("DATA", RegisterAddress8::new(0x00), 1, Access::ReadWrite),
+        RegisterInfo::new("STATUS", RegisterAddress8::new(0x01), 1, Access::ReadOnly)
+            .with_reset(&[0x02])
+            .with_fields(&[
+                FieldInfo::new("RXNE", 0, 1, Access::ReadOnly),
+                FieldInfo::new("TXE", 1, 1, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("BAUD", RegisterAddress8::new(0x02), 2, Access::ReadWrite)
+            .with_reset(&[0x00, 0x01])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("DIV", 0, 16, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("CTRL", RegisterAddress8::new(0x04), 4, Access::ReadWrite)
+            .with_reset(&[0x01, 0x00, 0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("EN", 0, 1, Access::ReadWrite),
+                FieldInfo::new("PARITY", 1, 2, Access::ReadWrite),
+                FieldInfo::new("BUSY", 31, 1, Access::ReadOnly),
+            ]),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("uart", REGISTERS);
+}
@@ -0,0 +1,487 @@
+// This file was generated by hardware-registers. Do not edit.
+
+/// Universal asynchronous receiver/transmitter
+pub mod uart0 {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R1, R4};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::spi::SPIRegister;
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// The base address of the peripheral.
+    pub const BASE_ADDRESS: u64 = 0x40001000;
+
+    /// `CTRL`: Control register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Ctrl(pub u32);
+
+    impl Ctrl {
+        /// `EN`: Enables the UART (bit 0)
+        #[must_use]
+        pub const fn en(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// Sets the `EN` field (bit 0).
+        #[must_use]
+        pub const fn with_en(self, value: bool) -> Self {
+            Self((self.0 & !0x1) | if value { 0x1 } else { 0 })
+        }
+
+        /// The `MODE` field (bits 2..=1).
+        #[must_use]
+        pub const fn mode(self) -> u8 {
+            ((self.0 >> 1) & 0x3) as u8
+        }
+
+        /// Sets the `MODE` field (bits 2..=1).
+        #[must_use]
+        pub const fn with_mode(self, value: u8) -> Self {
+            Self((self.0 & !(0x3 << 1)) | (((value as u32) & 0x3) << 1))
+        }
+
+        /// `BAUD`: Baud rate divider (bits 31..=16)
+        #[must_use]
+        pub const fn baud(self) -> u16 {
+            ((self.0 >> 16) & 0xFFFF) as u16
+        }
+
+        /// Sets the `BAUD` field (bits 31..=16).
+        #[must_use]
+        pub const fn with_baud(self, value: u16) -> Self {
+            Self((self.0 & !(0xFFFF << 16)) | (((value as u32) & 0xFFFF) << 16))
+        }
+    }
+
+    impl Default for Ctrl {
+        fn default() -> Self {
+            Self(0x10000)
+        }
+    }
+
+    impl HardwareRegister<R4> for Ctrl {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R4> for Ctrl {}
+
+    impl ToBits for Ctrl {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Ctrl {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Ctrl {
+        const NAME: &'static str = "CTRL";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("EN", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("MODE", FieldValue::Unsigned(u128::from(((self.0 >> 1) & 0x3) as u8)));
+            visitor.field("BAUD", FieldValue::Unsigned(u128::from(((self.0 >> 16) & 0xFFFF) as u16)));
+        }
+    }
+
+    impl SPIRegister<RegisterAddress8, R4> for Ctrl {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x00);
+        const RESET_VALUE: Option<u32> = Some(0x10000);
+        type Backing = u32;
+    }
+
+    /// `STATUS`: Status register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Status(pub u8);
+
+    impl Status {
+        /// `RXNE`: Receive buffer not empty (bit 0)
+        #[must_use]
+        pub const fn rxne(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// `TXE`: Transmit buffer empty (bit 1)
+        #[must_use]
+        pub const fn txe(self) -> bool {
+            (self.0 >> 1) & 0x1 != 0
+        }
+    }
+
+    impl Default for Status {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R1> for Status {}
+
+    impl ToBits for Status {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for Status {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Status {
+        const NAME: &'static str = "STATUS";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("RXNE", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("TXE", FieldValue::Bit((self.0 >> 1) & 0x1 != 0));
+        }
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for Status {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x04);
+        const RESET_VALUE: Option<u8> = Some(0x00);
+        type Backing = u8;
+    }
+
+    /// `FIFO_DATA0`: FIFO data slot
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct FifoData0(pub u8);
+
+    impl HardwareRegister<R1> for FifoData0 {}
+    impl WritableHardwareRegister<R1> for FifoData0 {}
+
+    impl ToBits for FifoData0 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for FifoData0 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for FifoData0 {
+        const NAME: &'static str = "FIFO_DATA0";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for FifoData0 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x10);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// `FIFO_DATA1`: FIFO data slot
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct FifoData1(pub u8);
+
+    impl HardwareRegister<R1> for FifoData1 {}
+    impl WritableHardwareRegister<R1> for FifoData1 {}
+
+    impl ToBits for FifoData1 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for FifoData1 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for FifoData1 {
+        const NAME: &'static str = "FIFO_DATA1";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for FifoData1 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x11);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("CTRL", RegisterAddress8::new(0x00), 4, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00, 0x01, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("EN", 0, 1, Access::ReadWrite),
+                FieldInfo::new("MODE", 1, 2, Access::ReadWrite),
+                FieldInfo::new("BAUD", 16, 16, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("STATUS", RegisterAddress8::new(0x04), 1, Access::ReadOnly)
+            .with_reset(&[0x00])
+            .with_fields(&[
+                FieldInfo::new("RXNE", 0, 1, Access::ReadOnly),
+                FieldInfo::new("TXE", 1, 1, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("FIFO_DATA0", RegisterAddress8::new(0x10), 1, Access::ReadWrite),
+        RegisterInfo::new("FIFO_DATA1", RegisterAddress8::new(0x11), 1, Access::ReadWrite),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("UART0", REGISTERS);
+}
+
+/// The `UART1` device.
+pub mod uart1 {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress8;
+    use hardware_registers::sizes::{R1, R4};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::spi::SPIRegister;
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// The base address of the peripheral.
+    pub const BASE_ADDRESS: u64 = 0x40002000;
+
+    /// `CTRL`: Control register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Ctrl(pub u32);
+
+    impl Ctrl {
+        /// `EN`: Enables the UART (bit 0)
+        #[must_use]
+        pub const fn en(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// Sets the `EN` field (bit 0).
+        #[must_use]
+        pub const fn with_en(self, value: bool) -> Self {
+            Self((self.0 & !0x1) | if value { 0x1 } else { 0 })
+        }
+
+        /// The `MODE` field (bits 2..=1).
+        #[must_use]
+        pub const fn mode(self) -> u8 {
+            ((self.0 >> 1) & 0x3) as u8
+        }
+
+        /// Sets the `MODE` field (bits 2..=1).
+        #[must_use]
+        pub const fn with_mode(self, value: u8) -> Self {
+            Self((self.0 & !(0x3 << 1)) | (((value as u32) & 0x3) << 1))
+        }
+
+        /// `BAUD`: Baud rate divider (bits 31..=16)
+        #[must_use]
+        pub const fn baud(self) -> u16 {
+            ((self.0 >> 16) & 0xFFFF) as u16
+        }
+
+        /// Sets the `BAUD` field (bits 31..=16).
+        #[must_use]
+        pub const fn with_baud(self, value: u16) -> Self {
+            Self((self.0 & !(0xFFFF << 16)) | (((value as u32) & 0xFFFF) << 16))
+        }
+    }
+
+    impl Default for Ctrl {
+        fn default() -> Self {
+            Self(0x10000)
+        }
+    }
+
+    impl HardwareRegister<R4> for Ctrl {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R4> for Ctrl {}
+
+    impl ToBits for Ctrl {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Ctrl {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Ctrl {
+        const NAME: &'static str = "CTRL";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("EN", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("MODE", FieldValue::Unsigned(u128::from(((self.0 >> 1) & 0x3) as u8)));
+            visitor.field("BAUD", FieldValue::Unsigned(u128::from(((self.0 >> 16) & 0xFFFF) as u16)));
+        }
+    }
+
+    impl SPIRegister<RegisterAddress8, R4> for Ctrl {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x00);
+        const RESET_VALUE: Option<u32> = Some(0x10000);
+        type Backing = u32;
+    }
+
+    /// `STATUS`: Status register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Status(pub u8);
+
+    impl Status {
+        /// `RXNE`: Receive buffer not empty (bit 0)
+        #[must_use]
+        pub const fn rxne(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// `TXE`: Transmit buffer empty (bit 1)
+        #[must_use]
+        pub const fn txe(self) -> bool {
+            (self.0 >> 1) & 0x1 != 0
+        }
+    }
+
+    impl Default for Status {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R1> for Status {}
+
+    impl ToBits for Status {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for Status {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Status {
+        const NAME: &'static str = "STATUS";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("RXNE", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("TXE", FieldValue::Bit((self.0 >> 1) & 0x1 != 0));
+        }
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for Status {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x04);
+        const RESET_VALUE: Option<u8> = Some(0x00);
+        type Backing = u8;
+    }
+
+    /// `FIFO_DATA0`: FIFO data slot
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct FifoData0(pub u8);
+
+    impl HardwareRegister<R1> for FifoData0 {}
+    impl WritableHardwareRegister<R1> for FifoData0 {}
+
+    impl ToBits for FifoData0 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for FifoData0 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for FifoData0 {
+        const NAME: &'static str = "FIFO_DATA0";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for FifoData0 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x10);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// `FIFO_DATA1`: FIFO data slot
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct FifoData1(pub u8);
+
+    impl HardwareRegister<R1> for FifoData1 {}
+    impl WritableHardwareRegister<R1> for FifoData1 {}
+
+    impl ToBits for FifoData1 {
+        type Target = u8;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u8> for FifoData1 {
+        fn from_bits_ref(value: &u8) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for FifoData1 {
+        const NAME: &'static str = "FIFO_DATA1";
+
+        fn decode(&self, _visitor: &mut dyn FieldVisitor) {}
+    }
+
+    impl SPIRegister<RegisterAddress8, R1> for FifoData1 {
+        const REGISTER_ADDRESS: RegisterAddress8 = RegisterAddress8::new(0x11);
+        const RESET_VALUE: Option<u8> = None;
+        type Backing = u8;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress8>] = &[
+        RegisterInfo::new("CTRL", RegisterAddress8::new(0x00), 4, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00, 0x01, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("EN", 0, 1, Access::ReadWrite),
+                FieldInfo::new("MODE", 1, 2, Access::ReadWrite),
+                FieldInfo::new("BAUD", 16, 16, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("STATUS", RegisterAddress8::new(0x04), 1, Access::ReadOnly)
+            .with_reset(&[0x00])
+            .with_fields(&[
+                FieldInfo::new("RXNE", 0, 1, Access::ReadOnly),
+                FieldInfo::new("TXE", 1, 1, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("FIFO_DATA0", RegisterAddress8::new(0x10), 1, Access::ReadWrite),
+        RegisterInfo::new("FIFO_DATA1", RegisterAddress8::new(0x11), 1, Access::ReadWrite),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress8> = RegisterMap::new("UART1", REGISTERS);
+}
@@ -0,0 +1,490 @@
+// This file was generated by hardware-registers. Do not edit.
+
+/// FPGA control block
+pub mod fpga_ctrl {
+    #![allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::derivable_impls, clippy::unreadable_literal, clippy::unused_self)]
+
+    use hardware_registers::format::{DecodeRegister, FieldValue, FieldVisitor};
+    use hardware_registers::map::{Access, FieldInfo, RegisterInfo, RegisterMap};
+    use hardware_registers::register_address::RegisterAddress16;
+    use hardware_registers::sizes::{R2, R4};
+    use hardware_registers::backing::ByteOrder;
+    use hardware_registers::i2c::{DeviceAddress7, I2CRegister};
+    use hardware_registers::{FromBits, HardwareRegister, ToBits, WritableHardwareRegister};
+
+    /// The factory-default I²C device address.
+    pub const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DeviceAddress7::new(0x40);
+
+    /// `ID`: Design identification
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Id(pub u32);
+
+    impl Id {
+        /// `MAGIC`: Magic number (bits 31..=16)
+        #[must_use]
+        pub const fn magic(self) -> u16 {
+            ((self.0 >> 16) & 0xFFFF) as u16
+        }
+
+        /// The `MAJOR` field (bits 15..=8).
+        #[must_use]
+        pub const fn major(self) -> u8 {
+            ((self.0 >> 8) & 0xFF) as u8
+        }
+
+        /// The `MINOR` field (bits 7..=0).
+        #[must_use]
+        pub const fn minor(self) -> u8 {
+            (self.0 & 0xFF) as u8
+        }
+    }
+
+    impl Default for Id {
+        fn default() -> Self {
+            Self(0xA5A0102)
+        }
+    }
+
+    impl HardwareRegister<R4> for Id {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+
+    impl ToBits for Id {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Id {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Id {
+        const NAME: &'static str = "ID";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("MAGIC", FieldValue::Unsigned(u128::from(((self.0 >> 16) & 0xFFFF) as u16)));
+            visitor.field("MAJOR", FieldValue::Unsigned(u128::from(((self.0 >> 8) & 0xFF) as u8)));
+            visitor.field("MINOR", FieldValue::Unsigned(u128::from((self.0 & 0xFF) as u8)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R4> for Id {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x00);
+        const RESET_VALUE: Option<u32> = Some(0xA5A0102);
+        type Backing = u32;
+    }
+
+    /// `CTRL`: Control register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Ctrl(pub u16);
+
+    impl Ctrl {
+        /// `EN`: Enables the core (bit 0)
+        #[must_use]
+        pub const fn en(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// Sets the `EN` field (bit 0).
+        #[must_use]
+        pub const fn with_en(self, value: bool) -> Self {
+            Self((self.0 & !0x1) | if value { 0x1 } else { 0 })
+        }
+
+        /// `MODE`: Operating mode (bits 2..=1)
+        #[must_use]
+        pub const fn mode(self) -> u8 {
+            ((self.0 >> 1) & 0x3) as u8
+        }
+
+        /// Sets the `MODE` field (bits 2..=1).
+        #[must_use]
+        pub const fn with_mode(self, value: u8) -> Self {
+            Self((self.0 & !(0x3 << 1)) | (((value as u16) & 0x3) << 1))
+        }
+
+        /// Sets the `SOFT_RESET` field (bit 15).
+        #[must_use]
+        pub const fn with_soft_reset(self, value: bool) -> Self {
+            Self((self.0 & !(0x1 << 15)) | if value { 0x8000 } else { 0 })
+        }
+    }
+
+    impl Default for Ctrl {
+        fn default() -> Self {
+            Self(0x01)
+        }
+    }
+
+    impl HardwareRegister<R2> for Ctrl {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Ctrl {}
+
+    impl ToBits for Ctrl {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Ctrl {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Ctrl {
+        const NAME: &'static str = "CTRL";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("EN", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("MODE", FieldValue::Unsigned(u128::from(((self.0 >> 1) & 0x3) as u8)));
+            visitor.field("SOFT_RESET", FieldValue::Bit((self.0 >> 15) & 0x1 != 0));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R2> for Ctrl {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x04);
+        const RESET_VALUE: Option<u16> = Some(0x01);
+        type Backing = u16;
+    }
+
+    /// `STATUS`: Status register
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Status(pub u16);
+
+    impl Status {
+        /// The `BUSY` field (bit 0).
+        #[must_use]
+        pub const fn busy(self) -> bool {
+            self.0 & 0x1 != 0
+        }
+
+        /// The `ERROR` field (bits 4..=1).
+        #[must_use]
+        pub const fn error(self) -> u8 {
+            ((self.0 >> 1) & 0xF) as u8
+        }
+    }
+
+    impl Default for Status {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R2> for Status {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+
+    impl ToBits for Status {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Status {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Status {
+        const NAME: &'static str = "STATUS";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("BUSY", FieldValue::Bit(self.0 & 0x1 != 0));
+            visitor.field("ERROR", FieldValue::Unsigned(u128::from(((self.0 >> 1) & 0xF) as u8)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R2> for Status {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x06);
+        const RESET_VALUE: Option<u16> = Some(0x00);
+        type Backing = u16;
+    }
+
+    /// `dma0_SRC`: Source address
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Dma0Src(pub u32);
+
+    impl Dma0Src {
+        /// The `ADDR` field (bits 31..=0).
+        #[must_use]
+        pub const fn addr(self) -> u32 {
+            self.0
+        }
+
+        /// Sets the `ADDR` field (bits 31..=0).
+        #[must_use]
+        pub const fn with_addr(self, value: u32) -> Self {
+            Self(value)
+        }
+    }
+
+    impl HardwareRegister<R4> for Dma0Src {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R4> for Dma0Src {}
+
+    impl ToBits for Dma0Src {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Dma0Src {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Dma0Src {
+        const NAME: &'static str = "dma0_SRC";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("ADDR", FieldValue::Unsigned(u128::from(self.0)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R4> for Dma0Src {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x100);
+        const RESET_VALUE: Option<u32> = None;
+        type Backing = u32;
+    }
+
+    /// `dma0_LEN`: Transfer length in bytes
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Dma0Len(pub u16);
+
+    impl Dma0Len {
+        /// The `COUNT` field (bits 15..=0).
+        #[must_use]
+        pub const fn count(self) -> u16 {
+            self.0
+        }
+
+        /// Sets the `COUNT` field (bits 15..=0).
+        #[must_use]
+        pub const fn with_count(self, value: u16) -> Self {
+            Self(value)
+        }
+    }
+
+    impl Default for Dma0Len {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R2> for Dma0Len {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Dma0Len {}
+
+    impl ToBits for Dma0Len {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Dma0Len {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Dma0Len {
+        const NAME: &'static str = "dma0_LEN";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("COUNT", FieldValue::Unsigned(u128::from(self.0)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R2> for Dma0Len {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x104);
+        const RESET_VALUE: Option<u16> = Some(0x00);
+        type Backing = u16;
+    }
+
+    /// `dma1_SRC`: Source address
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Dma1Src(pub u32);
+
+    impl Dma1Src {
+        /// The `ADDR` field (bits 31..=0).
+        #[must_use]
+        pub const fn addr(self) -> u32 {
+            self.0
+        }
+
+        /// Sets the `ADDR` field (bits 31..=0).
+        #[must_use]
+        pub const fn with_addr(self, value: u32) -> Self {
+            Self(value)
+        }
+    }
+
+    impl HardwareRegister<R4> for Dma1Src {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R4> for Dma1Src {}
+
+    impl ToBits for Dma1Src {
+        type Target = u32;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u32> for Dma1Src {
+        fn from_bits_ref(value: &u32) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Dma1Src {
+        const NAME: &'static str = "dma1_SRC";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("ADDR", FieldValue::Unsigned(u128::from(self.0)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R4> for Dma1Src {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x108);
+        const RESET_VALUE: Option<u32> = None;
+        type Backing = u32;
+    }
+
+    /// `dma1_LEN`: Transfer length in bytes
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub struct Dma1Len(pub u16);
+
+    impl Dma1Len {
+        /// The `COUNT` field (bits 15..=0).
+        #[must_use]
+        pub const fn count(self) -> u16 {
+            self.0
+        }
+
+        /// Sets the `COUNT` field (bits 15..=0).
+        #[must_use]
+        pub const fn with_count(self, value: u16) -> Self {
+            Self(value)
+        }
+    }
+
+    impl Default for Dma1Len {
+        fn default() -> Self {
+            Self(0x00)
+        }
+    }
+
+    impl HardwareRegister<R2> for Dma1Len {
+        const BYTE_ORDER: ByteOrder = ByteOrder::LittleEndian;
+    }
+    impl WritableHardwareRegister<R2> for Dma1Len {}
+
+    impl ToBits for Dma1Len {
+        type Target = u16;
+
+        fn to_bits(&self) -> Self::Target {
+            self.0
+        }
+    }
+
+    impl FromBits<u16> for Dma1Len {
+        fn from_bits_ref(value: &u16) -> Self {
+            Self(*value)
+        }
+    }
+
+    impl DecodeRegister for Dma1Len {
+        const NAME: &'static str = "dma1_LEN";
+
+        fn decode(&self, visitor: &mut dyn FieldVisitor) {
+            visitor.field("COUNT", FieldValue::Unsigned(u128::from(self.0)));
+        }
+    }
+
+    impl I2CRegister<DeviceAddress7, RegisterAddress16, R2> for Dma1Len {
+        const DEFAULT_DEVICE_ADDRESS: DeviceAddress7 = DEFAULT_DEVICE_ADDRESS;
+        const REGISTER_ADDRESS: RegisterAddress16 = RegisterAddress16::new(0x10C);
+        const RESET_VALUE: Option<u16> = Some(0x00);
+        type Backing = u16;
+    }
+
+    /// The registers of the device.
+    pub const REGISTERS: &[RegisterInfo<RegisterAddress16>] = &[
+        RegisterInfo::new("ID", RegisterAddress16::new(0x00), 4, Access::ReadOnly)
+            .with_reset(&[0x02, 0x01, 0x5A, 0x0A])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("MAGIC", 16, 16, Access::ReadOnly),
+                FieldInfo::new("MAJOR", 8, 8, Access::ReadOnly),
+                FieldInfo::new("MINOR", 0, 8, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("CTRL", RegisterAddress16::new(0x04), 2, Access::ReadWrite)
+            .with_reset(&[0x01, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("EN", 0, 1, Access::ReadWrite),
+                FieldInfo::new("MODE", 1, 2, Access::ReadWrite),
+                FieldInfo::new("SOFT_RESET", 15, 1, Access::WriteOnly),
+            ]),
+        RegisterInfo::new("STATUS", RegisterAddress16::new(0x06), 2, Access::ReadOnly)
+            .with_reset(&[0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("BUSY", 0, 1, Access::ReadOnly),
+                FieldInfo::new("ERROR", 1, 4, Access::ReadOnly),
+            ]),
+        RegisterInfo::new("dma0_SRC", RegisterAddress16::new(0x100), 4, Access::ReadWrite)
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("ADDR", 0, 32, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("dma0_LEN", RegisterAddress16::new(0x104), 2, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("COUNT", 0, 16, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("dma1_SRC", RegisterAddress16::new(0x108), 4, Access::ReadWrite)
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("ADDR", 0, 32, Access::ReadWrite),
+            ]),
+        RegisterInfo::new("dma1_LEN", RegisterAddress16::new(0x10C), 2, Access::ReadWrite)
+            .with_reset(&[0x00, 0x00])
+            .with_byte_order(ByteOrder::LittleEndian)
+            .with_fields(&[
+                FieldInfo::new("COUNT", 0, 16, Access::ReadWrite),
+            ]),
+    ];
+
+    /// The register map of the device.
+    pub const MAP: RegisterMap<RegisterAddress16> = RegisterMap::new("fpga_ctrl", REGISTERS);
+}